@@ -13,7 +13,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use snarkos_node::bft::helpers::proposal_cache_path;
+use snarkos_node::{
+    bft::helpers::proposal_cache_path,
+    consensus::memory_pool_journal_path,
+    rest::{INDEX_NAMES, index_store_path},
+};
 
 use aleo_std::StorageMode;
 use anyhow::{Result, bail};
@@ -45,11 +49,28 @@ impl Clean {
                 bail!("Failed to remove the current proposal cache file at {}: {err}", proposal_cache_path.display());
             }
         }
-        // Remove the specified ledger from storage.
-        Self::remove_ledger(self.network, match self.path {
+        // Remove the memory pool journal file, if it exists.
+        let memory_pool_journal_path = memory_pool_journal_path(self.network, self.dev);
+        if memory_pool_journal_path.exists() {
+            if let Err(err) = std::fs::remove_file(&memory_pool_journal_path) {
+                bail!("Failed to remove the memory pool journal file at {}: {err}", memory_pool_journal_path.display());
+            }
+        }
+        let storage_mode = match self.path {
             Some(path) => StorageMode::Custom(path),
             None => StorageMode::from(self.dev),
-        })
+        };
+        // Remove the indexes of the REST server, if they exist.
+        for name in INDEX_NAMES {
+            let index_path = index_store_path(name, self.network, storage_mode.clone());
+            if index_path.exists() {
+                if let Err(err) = std::fs::remove_dir_all(&index_path) {
+                    bail!("Failed to remove the REST index at {}: {err}", index_path.display());
+                }
+            }
+        }
+        // Remove the specified ledger from storage.
+        Self::remove_ledger(self.network, storage_mode)
     }
 
     /// Removes the specified ledger from storage.
@@ -25,6 +25,9 @@ pub use developer::*;
 mod start;
 pub use start::*;
 
+mod token;
+pub use token::*;
+
 mod update;
 pub use update::*;
 
@@ -61,6 +64,8 @@ pub enum Command {
     Developer(Developer),
     #[clap(name = "start")]
     Start(Box<Start>),
+    #[clap(name = "token")]
+    Token(Token),
     #[clap(name = "update")]
     Update(Update),
 }
@@ -73,6 +78,7 @@ impl Command {
             Self::Clean(command) => command.parse(),
             Self::Developer(command) => command.parse(),
             Self::Start(command) => command.parse(),
+            Self::Token(command) => command.parse(),
             Self::Update(command) => command.parse(),
         }
     }
@@ -15,7 +15,7 @@
 
 use snarkos_account::Account;
 use snarkos_display::Display;
-use snarkos_node::{Node, bft::MEMORY_POOL_PORT, router::messages::NodeType};
+use snarkos_node::{Node, bft::MEMORY_POOL_PORT, consensus::ConsensusConfig, router::messages::NodeType};
 use snarkvm::{
     console::{
         account::{Address, PrivateKey},
@@ -115,6 +115,21 @@ pub struct Start {
     /// If the flag is set, a client will periodically evict more external peers
     #[clap(long = "rotate-external-peers")]
     pub rotate_external_peers: bool,
+    /// Specify the capacity of the memory pool queue for deployments (default: 1024)
+    #[clap(long = "mempool-deployments")]
+    pub mempool_deployments: Option<usize>,
+    /// Specify the capacity of the memory pool queue for executions (default: 1024)
+    #[clap(long = "mempool-executions")]
+    pub mempool_executions: Option<usize>,
+    /// Specify the capacity of the memory pool queue for solutions (default: 1024)
+    #[clap(long = "mempool-solutions")]
+    pub mempool_solutions: Option<usize>,
+    /// Specify the suggested maximum number of deployments sent from the memory pool in each interval (default: 1)
+    #[clap(long = "mempool-deployments-per-interval")]
+    pub mempool_deployments_per_interval: Option<usize>,
+    /// Specify the maximum number of transactions that a fee payer may have pending in the memory pool (default: 64)
+    #[clap(long = "mempool-max-per-payer")]
+    pub mempool_max_per_payer: Option<usize>,
 
     /// Specify the IP address and port for the REST server
     #[clap(long = "rest")]
@@ -122,9 +137,54 @@ pub struct Start {
     /// Specify the requests per second (RPS) rate limit per IP for the REST server
     #[clap(default_value = "10", long = "rest-rps")]
     pub rest_rps: u32,
+    /// Specify the RPS rate limit per IP for the bulk REST routes, such as `/blocks` (default: `--rest-rps`)
+    #[clap(long = "rest-bulk-rps")]
+    pub rest_bulk_rps: Option<u32>,
+    /// Specify the RPS rate limit per IP for the REST broadcast routes (default: `--rest-rps`)
+    #[clap(long = "rest-broadcast-rps")]
+    pub rest_broadcast_rps: Option<u32>,
+    /// Specify the path to a JSON file overriding the REST rate limits, and listing the API keys with their RPS quota
+    #[clap(long = "rest-rate-limits")]
+    pub rest_rate_limits: Option<PathBuf>,
+    /// Specify the path to the PEM-encoded TLS certificate chain, to serve the REST API over HTTPS
+    #[clap(long = "rest-tls-cert", requires = "rest_tls_key")]
+    pub rest_tls_cert: Option<PathBuf>,
+    /// Specify the path to the PEM-encoded TLS private key, to serve the REST API over HTTPS
+    #[clap(long = "rest-tls-key", requires = "rest_tls_cert")]
+    pub rest_tls_key: Option<PathBuf>,
+    /// Specify the comma-separated origins allowed by CORS, or `*` for any origin
+    #[clap(default_value = "*", long = "rest-cors-origins")]
+    pub rest_cors_origins: String,
+    /// Specify the comma-separated methods allowed by CORS
+    #[clap(default_value = "GET,POST,OPTIONS", long = "rest-cors-methods")]
+    pub rest_cors_methods: String,
+    /// Specify the comma-separated request headers allowed by CORS, or `*` for any header
+    #[clap(default_value = "content-type,x-api-key,authorization,if-none-match", long = "rest-cors-headers")]
+    pub rest_cors_headers: String,
+    /// If the flag is set, the REST server indexes the transitions of each address, to serve `/address/{address}/transitions`
+    #[clap(long = "rest-address-index")]
+    pub rest_address_index: bool,
+    /// If the flag is set, the REST server indexes the keys of each mapping, to serve `/program/{id}/mapping/{name}` by page
+    #[clap(long = "rest-mapping-index")]
+    pub rest_mapping_index: bool,
+    /// If the flag is set, the REST server records the changes to each mapping entry, to serve `/program/{id}/mapping/{name}/{key}/history` (requires the `history` feature)
+    #[clap(long = "rest-mapping-history")]
+    pub rest_mapping_history: bool,
     /// If the flag is set, the node will not initialize the REST server
     #[clap(long)]
     pub norest: bool,
+    /// Specify the path to a file containing the JWT secret (default: the `SNARKOS_REST_JWT_SECRET` env var, or random)
+    #[clap(long = "jwt-secret-file")]
+    pub jwt_secret_file: Option<PathBuf>,
+    /// Specify the path to a file listing the revoked JWT IDs, one per line
+    #[clap(long = "jwt-revocation-file")]
+    pub jwt_revocation_file: Option<PathBuf>,
+    /// Specify the number of seconds the read-only JWT printed at startup is valid for (default: 1 day; it was 10 years in earlier releases)
+    #[clap(long = "jwt-expiration")]
+    pub jwt_expiration: Option<i64>,
+    /// If the flag is set, broadcasting through the REST server requires a JWT with the `broadcast` scope
+    #[clap(long = "rest-auth-broadcast")]
+    pub rest_auth_broadcast: bool,
 
     /// If the flag is set, the node will not render the display
     #[clap(long)]
@@ -513,6 +573,22 @@ impl Start {
         }
     }
 
+    /// Returns the memory pool limits, with the defaults for the limits that are not specified.
+    fn parse_consensus_config<N: Network>(&self) -> Result<ConsensusConfig> {
+        let defaults = ConsensusConfig::default();
+        let config = ConsensusConfig {
+            capacity_for_deployments: self.mempool_deployments.unwrap_or(defaults.capacity_for_deployments),
+            capacity_for_executions: self.mempool_executions.unwrap_or(defaults.capacity_for_executions),
+            capacity_for_solutions: self.mempool_solutions.unwrap_or(defaults.capacity_for_solutions),
+            max_deployments_per_interval: self
+                .mempool_deployments_per_interval
+                .unwrap_or(defaults.max_deployments_per_interval),
+            max_pending_per_payer: self.mempool_max_per_payer.unwrap_or(defaults.max_pending_per_payer),
+        };
+        config.check::<N>()?;
+        Ok(config)
+    }
+
     /// Returns the node type corresponding to the given configurations.
     #[rustfmt::skip]
     async fn parse_node<N: Network>(&mut self, shutdown: Arc<AtomicBool>) -> Result<Node<N>> {
@@ -554,6 +630,37 @@ impl Start {
             false => self.rest.or_else(|| Some("0.0.0.0:3030".parse().unwrap())),
         };
 
+        // Parse the REST rate limits.
+        let mut rest_config = snarkos_node_rest::RestConfig::new(self.rest_rps);
+        rest_config.rate_limits.bulk = self.rest_bulk_rps.unwrap_or(self.rest_rps);
+        rest_config.rate_limits.broadcast = self.rest_broadcast_rps.unwrap_or(self.rest_rps);
+        if let Some(path) = &self.rest_rate_limits {
+            rest_config.rate_limits = rest_config.rate_limits.with_file(path)?;
+        }
+        // Parse the REST TLS settings.
+        if let (Some(cert_path), Some(key_path)) = (&self.rest_tls_cert, &self.rest_tls_key) {
+            rest_config.tls = Some(snarkos_node_rest::TlsConfig { cert_path: cert_path.clone(), key_path: key_path.clone() });
+        }
+        // Parse the REST CORS settings.
+        let parse_list = |list: &str| list.split(',').map(str::trim).filter(|item| !item.is_empty()).map(str::to_string).collect();
+        rest_config.cors.origins = parse_list(&self.rest_cors_origins);
+        rest_config.cors.methods = parse_list(&self.rest_cors_methods);
+        rest_config.cors.headers = parse_list(&self.rest_cors_headers);
+        // Parse the REST server authentication.
+        if rest_ip.is_some() {
+            let jwt = snarkos_node_rest::JwtConfig::new(self.jwt_secret_file.as_deref(), self.jwt_revocation_file.clone(), self.rest_auth_broadcast)?;
+            rest_config.jwt = Arc::new(jwt);
+        }
+        // Enable the address index, if requested.
+        rest_config.address_index = self.rest_address_index;
+        // Enable the mapping key index, if requested.
+        rest_config.mapping_index = self.rest_mapping_index;
+        // Enable the mapping history, if requested.
+        rest_config.mapping_history = self.rest_mapping_history;
+
+        // Parse the memory pool limits.
+        let consensus_config = self.parse_consensus_config::<N>()?;
+
         // If the display is not enabled, render the welcome message.
         if self.nodisplay {
             // Print the Aleo address.
@@ -571,8 +678,11 @@ impl Start {
                 if let Some(rest_ip) = rest_ip {
                     println!("🌐 Starting the REST server at {}.\n", rest_ip.to_string().bold());
 
-                    if let Ok(jwt_token) = snarkos_node_rest::Claims::new(account.address()).to_jwt_string() {
-                        println!("🔑 Your one-time JWT token is {}\n", jwt_token.dimmed());
+                    // Note: The printed token only reads the node data; the admin tokens are minted with `snarkos token`.
+                    let expiration = self.jwt_expiration.unwrap_or(snarkos_node_rest::EXPIRATION);
+                    let claims = snarkos_node_rest::Claims::with_scopes(account.address(), &snarkos_node_rest::Scope::READ, expiration);
+                    if let Ok(jwt_token) = rest_config.jwt.sign(&claims) {
+                        println!("🔑 Your one-time JWT token (read-only) is {}\n", jwt_token.dimmed());
                     }
                 }
             }
@@ -611,9 +721,9 @@ impl Start {
 
         // Initialize the node.
         match node_type {
-            NodeType::Validator => Node::new_validator(node_ip, self.bft, rest_ip, self.rest_rps, account, &trusted_peers, &trusted_validators, genesis, cdn, storage_mode, self.allow_external_peers, dev_txs, shutdown.clone()).await,
+            NodeType::Validator => Node::new_validator(node_ip, self.bft, rest_ip, rest_config, account, &trusted_peers, &trusted_validators, genesis, cdn, storage_mode, self.allow_external_peers, dev_txs, consensus_config, shutdown.clone()).await,
             NodeType::Prover => Node::new_prover(node_ip, account, &trusted_peers, genesis, storage_mode, shutdown.clone()).await,
-            NodeType::Client => Node::new_client(node_ip, rest_ip, self.rest_rps, account, &trusted_peers, genesis, cdn, storage_mode, self.rotate_external_peers, shutdown).await,
+            NodeType::Client => Node::new_client(node_ip, rest_ip, rest_config, account, &trusted_peers, genesis, cdn, storage_mode, self.rotate_external_peers, shutdown).await,
         }
     }
 
@@ -779,6 +889,7 @@ fn load_or_compute_genesis<N: Network>(
 mod tests {
     use super::*;
     use crate::commands::{CLI, Command};
+    use snarkos_node::bft::{Primary, Worker};
     use snarkvm::prelude::MainnetV0;
 
     type CurrentNetwork = MainnetV0;
@@ -819,6 +930,62 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn test_parse_consensus_config() {
+        let config = Start::try_parse_from(["snarkos"].iter()).unwrap();
+        assert_eq!(config.parse_consensus_config::<CurrentNetwork>().unwrap(), ConsensusConfig::default());
+
+        let config = Start::try_parse_from(
+            [
+                "snarkos",
+                "--mempool-deployments",
+                "8",
+                "--mempool-executions",
+                "100000",
+                "--mempool-solutions",
+                "100000",
+                "--mempool-deployments-per-interval",
+                "2",
+                "--mempool-max-per-payer",
+                "16",
+            ]
+            .iter(),
+        )
+        .unwrap();
+        assert_eq!(config.parse_consensus_config::<CurrentNetwork>().unwrap(), ConsensusConfig {
+            capacity_for_deployments: 8,
+            capacity_for_executions: 100000,
+            capacity_for_solutions: 100000,
+            max_deployments_per_interval: 2,
+            max_pending_per_payer: 16,
+        });
+
+        let _config = Start::try_parse_from(["snarkos", "--mempool-executions", ""].iter()).unwrap_err();
+        let _config = Start::try_parse_from(["snarkos", "--mempool-executions", "-1"].iter()).unwrap_err();
+    }
+
+    #[test]
+    fn test_parse_consensus_config_rejected() {
+        let parse = |args: &[&str]| {
+            let config = Start::try_parse_from(["snarkos"].iter().chain(args)).unwrap();
+            config.parse_consensus_config::<CurrentNetwork>()
+        };
+        // The capacities and the maximum pending transactions per fee payer must be positive.
+        assert!(parse(&["--mempool-deployments", "0"]).is_err());
+        assert!(parse(&["--mempool-executions", "0"]).is_err());
+        assert!(parse(&["--mempool-solutions", "0"]).is_err());
+        assert!(parse(&["--mempool-max-per-payer", "0"]).is_err());
+
+        // The deployments per interval must be positive, and fit in the queue and in the memory pool.
+        let limit = Primary::<CurrentNetwork>::MAX_TRANSMISSIONS_TOLERANCE
+            .min(Worker::<CurrentNetwork>::MAX_TRANSMISSIONS_PER_WORKER);
+        let (limit, above) = (limit.to_string(), (limit + 1).to_string());
+        assert!(parse(&["--mempool-deployments-per-interval", "0"]).is_err());
+        assert!(parse(&["--mempool-deployments", "1", "--mempool-deployments-per-interval", "2"]).is_err());
+        assert!(parse(&["--mempool-deployments", "100000", "--mempool-deployments-per-interval", &limit]).is_ok());
+        assert!(parse(&["--mempool-deployments", "100000", "--mempool-deployments-per-interval", &above]).is_err());
+    }
+
     #[test]
     fn test_parse_cdn() {
         // Validator (Prod)
@@ -0,0 +1,73 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkos_node_rest::{Claims, Scope, load_jwt_secret};
+use snarkvm::{
+    console::network::{CanaryV0, MainnetV0, Network, TestnetV0},
+    prelude::Address,
+};
+
+use anyhow::{Result, bail};
+use clap::Parser;
+use std::{path::PathBuf, str::FromStr};
+
+/// Mints a JSON Web Token for the REST server of a node.
+#[derive(Debug, Parser)]
+pub struct Token {
+    /// Specify the network of the node.
+    #[clap(default_value = "0", long = "network")]
+    pub network: u16,
+    /// Specify the Aleo address of the node, which is the subject of the token
+    #[clap(long = "address")]
+    pub address: String,
+    /// Specify the comma-separated scopes granted by the token [options: read-mapping, admin, broadcast]
+    #[clap(default_value = "read-mapping", long = "scopes", value_delimiter = ',')]
+    pub scopes: Vec<Scope>,
+    /// Specify the number of seconds the token is valid for
+    #[clap(default_value = "86400", long = "expiration")]
+    pub expiration: i64,
+    /// Specify the path to the JWT secret file of the node (default: the `SNARKOS_REST_JWT_SECRET` env var)
+    #[clap(long = "jwt-secret-file")]
+    pub jwt_secret_file: Option<PathBuf>,
+}
+
+impl Token {
+    /// Mints the token.
+    pub fn parse(self) -> Result<String> {
+        match self.network {
+            MainnetV0::ID => self.mint::<MainnetV0>(),
+            TestnetV0::ID => self.mint::<TestnetV0>(),
+            CanaryV0::ID => self.mint::<CanaryV0>(),
+            unknown_id => bail!("Unknown network ID ({unknown_id})"),
+        }
+    }
+
+    /// Mints the token for the given network.
+    fn mint<N: Network>(&self) -> Result<String> {
+        // Ensure the token expires.
+        if self.expiration <= 0 {
+            bail!("The expiration must be a positive number of seconds");
+        }
+        // Load the secret of the node; a random secret would produce a token that no node accepts.
+        let Some(secret) = load_jwt_secret(self.jwt_secret_file.as_deref())? else {
+            bail!("No JWT secret found, specify '--jwt-secret-file' or set 'SNARKOS_REST_JWT_SECRET'");
+        };
+        // Prepare the claims.
+        let address = Address::<N>::from_str(&self.address)?;
+        let claims = Claims::with_scopes(address, &self.scopes, self.expiration);
+
+        Ok(format!("{}\n(token ID: {})", claims.to_jwt_string_with_secret(&secret)?, claims.id()))
+    }
+}
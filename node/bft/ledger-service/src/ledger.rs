@@ -38,6 +38,7 @@ use std::{
         atomic::{AtomicBool, Ordering},
     },
 };
+use tokio::sync::watch;
 
 /// The capacity of the LRU holding the recently queried committees.
 const COMMITTEE_CACHE_SIZE: usize = 16;
@@ -48,6 +49,7 @@ pub struct CoreLedgerService<N: Network, C: ConsensusStorage<N>> {
     ledger: Ledger<N, C>,
     committee_cache: Arc<Mutex<LruCache<u64, Committee<N>>>>,
     latest_leader: Arc<RwLock<Option<(u64, Address<N>)>>>,
+    latest_block_height: watch::Sender<u32>,
     shutdown: Arc<AtomicBool>,
 }
 
@@ -55,7 +57,13 @@ impl<N: Network, C: ConsensusStorage<N>> CoreLedgerService<N, C> {
     /// Initializes a new core ledger service.
     pub fn new(ledger: Ledger<N, C>, shutdown: Arc<AtomicBool>) -> Self {
         let committee_cache = Arc::new(Mutex::new(LruCache::new(COMMITTEE_CACHE_SIZE.try_into().unwrap())));
-        Self { ledger, committee_cache, latest_leader: Default::default(), shutdown }
+        let (latest_block_height, _) = watch::channel(ledger.latest_height());
+        Self { ledger, committee_cache, latest_leader: Default::default(), latest_block_height, shutdown }
+    }
+
+    /// Returns a receiver that is notified with the new block height every time the ledger advances.
+    pub fn subscribe_to_blocks(&self) -> watch::Receiver<u32> {
+        self.latest_block_height.subscribe()
     }
 }
 
@@ -365,6 +373,8 @@ impl<N: Network, C: ConsensusStorage<N>> LedgerService<N> for CoreLedgerService<
         }
         // Advance to the next block.
         self.ledger.advance_to_next_block(block)?;
+        // Notify the subscribers of the new block height.
+        self.latest_block_height.send_replace(block.height());
         // Update BFT metrics.
         #[cfg(feature = "metrics")]
         {
@@ -54,12 +54,13 @@ impl<N: Network> std::ops::Deref for Storage<N> {
 /// - `certificate ID` to `certificate` entries.
 /// - `batch ID` to `round` entries.
 /// - `transmission ID` to `(transmission, certificate IDs)` entries.
+/// - `transaction ID` to `certificate IDs` entries.
 ///
 /// The chain of events is as follows:
 /// 1. A `transmission` is received.
 /// 2. After a `batch` is ready to be stored:
 ///   - The `certificate` is inserted, triggering updates to the
-///     `rounds`, `certificates`, `batch_ids`, and `transmissions` maps.
+///     `rounds`, `certificates`, `batch_ids`, `transmissions`, and `transactions` maps.
 ///   - The missing `transmissions` from storage are inserted into the `transmissions` map.
 ///   - The certificate ID is inserted into the `transmissions` map.
 /// 3. After a `round` reaches quorum threshold:
@@ -87,6 +88,8 @@ pub struct StorageInner<N: Network> {
     batch_ids: RwLock<IndexMap<Field<N>, u64>>,
     /// The map of `transmission ID` to `(transmission, certificate IDs)` entries.
     transmissions: Arc<dyn StorageService<N>>,
+    /// The map of `transaction ID` to `certificate IDs`.
+    transactions: RwLock<IndexMap<N::TransactionID, IndexSet<Field<N>>>>,
 }
 
 impl<N: Network> Storage<N> {
@@ -112,6 +115,7 @@ impl<N: Network> Storage<N> {
             certificates: Default::default(),
             batch_ids: Default::default(),
             transmissions,
+            transactions: Default::default(),
         }));
         // Update the storage to the current round.
         storage.update_current_round(current_round);
@@ -237,6 +241,11 @@ impl<N: Network> Storage<N> {
         self.rounds.read().contains_key(&round)
     }
 
+    /// Returns an iterator over the `(round, (certificate ID, batch ID, author))` entries.
+    pub fn rounds_iter(&self) -> impl Iterator<Item = (u64, IndexSet<(Field<N>, Field<N>, Address<N>)>)> {
+        self.rounds.read().clone().into_iter()
+    }
+
     /// Returns `true` if the storage contains the specified `certificate ID`.
     pub fn contains_certificate(&self, certificate_id: Field<N>) -> bool {
         // Check if the certificate ID exists in storage.
@@ -324,6 +333,21 @@ impl<N: Network> Storage<N> {
         }
     }
 
+    /// Returns the certificate of the latest round that includes the given `transaction ID`.
+    /// If the transaction ID does not exist in storage, `None` is returned.
+    pub fn get_certificate_for_transaction(&self, transaction_id: &N::TransactionID) -> Option<BatchCertificate<N>> {
+        // Retrieve the certificate IDs.
+        let transactions = self.transactions.read();
+        let certificate_ids = transactions.get(transaction_id)?;
+        // Retrieve the certificate of the latest round.
+        let certificates = self.certificates.read();
+        certificate_ids
+            .iter()
+            .flat_map(|certificate_id| certificates.get(certificate_id))
+            .max_by_key(|c| c.round())
+            .cloned()
+    }
+
     /// Returns the certificates that have not yet been included in the ledger.
     /// Note that the order of this set is by round and then insertion.
     pub(crate) fn get_pending_certificates(&self) -> IndexSet<BatchCertificate<N>> {
@@ -523,7 +547,7 @@ impl<N: Network> Storage<N> {
 
     /// Inserts the given `certificate` into storage.
     ///
-    /// This method triggers updates to the `rounds`, `certificates`, `batch_ids`, and `transmissions` maps.
+    /// This method triggers updates to the `rounds`, `certificates`, `batch_ids`, `transmissions`, and `transactions` maps.
     ///
     /// This method ensures the following invariants:
     /// - The certificate ID does not already exist in storage.
@@ -552,7 +576,7 @@ impl<N: Network> Storage<N> {
     ///
     /// This method assumes **all missing** transmissions are provided in the `missing_transmissions` map.
     ///
-    /// This method triggers updates to the `rounds`, `certificates`, `batch_ids`, and `transmissions` maps.
+    /// This method triggers updates to the `rounds`, `certificates`, `batch_ids`, `transmissions`, and `transactions` maps.
     fn insert_certificate_atomic(
         &self,
         certificate: BatchCertificate<N>,
@@ -572,6 +596,8 @@ impl<N: Network> Storage<N> {
         self.rounds.write().entry(round).or_default().insert((certificate_id, batch_id, author));
         // Obtain the certificate's transmission ids.
         let transmission_ids = certificate.transmission_ids().clone();
+        // Insert the certificate ID for each of the transactions.
+        self.insert_transactions(certificate_id, &transmission_ids);
         // Insert the certificate.
         self.certificates.write().insert(certificate_id, certificate);
         // Insert the batch ID.
@@ -587,7 +613,7 @@ impl<N: Network> Storage<N> {
 
     /// Removes the given `certificate ID` from storage.
     ///
-    /// This method triggers updates to the `rounds`, `certificates`, `batch_ids`, and `transmissions` maps.
+    /// This method triggers updates to the `rounds`, `certificates`, `batch_ids`, `transmissions`, and `transactions` maps.
     ///
     /// If the certificate was successfully removed, `true` is returned.
     /// If the certificate did not exist in storage, `false` is returned.
@@ -624,11 +650,35 @@ impl<N: Network> Storage<N> {
         self.certificates.write().swap_remove(&certificate_id);
         // Remove the batch ID.
         self.batch_ids.write().swap_remove(&batch_id);
+        // Remove the certificate ID for each of the transactions.
+        self.remove_transactions(certificate_id, certificate.transmission_ids());
         // Remove the transmission entries in the certificate from storage.
         self.transmissions.remove_transmissions(&certificate_id, certificate.transmission_ids());
         // Return successfully.
         true
     }
+
+    /// Inserts the given `certificate ID` for each of the transactions in the given transmission IDs.
+    fn insert_transactions(&self, certificate_id: Field<N>, transmission_ids: &IndexSet<TransmissionID<N>>) {
+        let mut transactions = self.transactions.write();
+        for transaction_id in transaction_ids(transmission_ids) {
+            transactions.entry(*transaction_id).or_default().insert(certificate_id);
+        }
+    }
+
+    /// Removes the given `certificate ID` for each of the transactions in the given transmission IDs.
+    /// If this is the last certificate ID for a transaction ID, the transaction ID is removed.
+    fn remove_transactions(&self, certificate_id: Field<N>, transmission_ids: &IndexSet<TransmissionID<N>>) {
+        let mut transactions = self.transactions.write();
+        for transaction_id in transaction_ids(transmission_ids) {
+            if let Entry::Occupied(mut entry) = transactions.entry(*transaction_id) {
+                entry.get_mut().swap_remove(&certificate_id);
+                if entry.get().is_empty() {
+                    entry.swap_remove();
+                }
+            }
+        }
+    }
 }
 
 impl<N: Network> Storage<N> {
@@ -764,11 +814,6 @@ impl<N: Network> Storage<N> {
         &self.ledger
     }
 
-    /// Returns an iterator over the `(round, (certificate ID, batch ID, author))` entries.
-    pub fn rounds_iter(&self) -> impl Iterator<Item = (u64, IndexSet<(Field<N>, Field<N>, Address<N>)>)> {
-        self.rounds.read().clone().into_iter()
-    }
-
     /// Returns an iterator over the `(certificate ID, certificate)` entries.
     pub fn certificates_iter(&self) -> impl Iterator<Item = (Field<N>, BatchCertificate<N>)> {
         self.certificates.read().clone().into_iter()
@@ -805,6 +850,8 @@ impl<N: Network> Storage<N> {
         self.rounds.write().entry(round).or_default().insert((certificate_id, batch_id, author));
         // Obtain the certificate's transmission ids.
         let transmission_ids = certificate.transmission_ids().clone();
+        // Insert the certificate ID for each of the transactions.
+        self.insert_transactions(certificate_id, &transmission_ids);
         // Insert the certificate.
         self.certificates.write().insert(certificate_id, certificate);
         // Insert the batch ID.
@@ -825,6 +872,16 @@ impl<N: Network> Storage<N> {
     }
 }
 
+/// Returns the IDs of the transactions among the given transmission IDs.
+fn transaction_ids<N: Network>(
+    transmission_ids: &IndexSet<TransmissionID<N>>,
+) -> impl '_ + Iterator<Item = &N::TransactionID> {
+    transmission_ids.iter().filter_map(|transmission_id| match transmission_id {
+        TransmissionID::Transaction(transaction_id, _) => Some(transaction_id),
+        _ => None,
+    })
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
     use super::*;
@@ -937,6 +994,10 @@ pub(crate) mod tests {
         assert_eq!(storage.get_certificates_for_round(round), indexset! { certificate.clone() });
         // Ensure the certificate is stored for the correct round and author.
         assert_eq!(storage.get_certificate_for_round_with_author(round, author), Some(certificate.clone()));
+        // Ensure the certificate is stored for each of its transactions.
+        for transaction_id in transaction_ids(certificate.transmission_ids()) {
+            assert_eq!(storage.get_certificate_for_transaction(transaction_id), Some(certificate.clone()));
+        }
 
         // Check that the underlying storage representation is correct.
         {
@@ -963,6 +1024,10 @@ pub(crate) mod tests {
         assert!(storage.get_certificates_for_round(round).is_empty());
         // Ensure the certificate is no longer stored for the round and author.
         assert_eq!(storage.get_certificate_for_round_with_author(round, author), None);
+        // Ensure the certificate is no longer stored for any of its transactions.
+        for transaction_id in transaction_ids(certificate.transmission_ids()) {
+            assert_eq!(storage.get_certificate_for_transaction(transaction_id), None);
+        }
         // Ensure the storage is empty.
         assert_storage(&storage, &[], &[], &[], &Default::default());
     }
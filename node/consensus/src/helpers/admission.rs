@@ -0,0 +1,126 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkos_node_bft::helpers::fmt_id;
+use snarkvm::{
+    console::program::{Argument, Literal, Plaintext},
+    ledger::block::{Output, Transaction},
+    prelude::{Address, Network},
+};
+
+use colored::Colorize;
+use std::fmt::{self, Write};
+
+/// The reason an unconfirmed transaction or solution is rejected from the memory pool.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AdmissionError<N: Network> {
+    /// The transaction is a fee transaction, which may only be created by the ledger.
+    FeeTransaction(String),
+    /// The transmission was recently received, and may still be in the memory pool.
+    Duplicate(String),
+    /// The transmission already exists in the ledger.
+    AlreadyInLedger(String),
+    /// The memory pool is full, and the transmission has a lower priority than every queued one.
+    QueueFull(String),
+    /// The fee payer already has the maximum number of transactions pending in the memory pool.
+    PayerLimitExceeded { payer: Address<N>, limit: usize },
+    /// The transaction exceeds the maximum transaction size.
+    TooLarge(String),
+    /// The transmission is not well-formed.
+    /// Note: On admission, this only covers the failures to serialize a transaction or to read its priority fee,
+    /// as the proofs are verified by the workers once the transaction leaves the queue, and a refusal is recorded
+    /// as a drop.
+    Malformed { id: String, reason: String },
+}
+
+impl<N: Network> AdmissionError<N> {
+    /// Returns the machine-readable code of the error.
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::FeeTransaction(..) => "fee_transaction",
+            Self::Duplicate(..) => "duplicate",
+            Self::AlreadyInLedger(..) => "already_in_ledger",
+            Self::QueueFull(..) => "queue_full",
+            Self::PayerLimitExceeded { .. } => "payer_limit_exceeded",
+            Self::TooLarge(..) => "too_large",
+            Self::Malformed { .. } => "malformed",
+        }
+    }
+
+    /// Returns `true` if the same transmission may be admitted if it is broadcast again later.
+    pub const fn is_retryable(&self) -> bool {
+        matches!(self, Self::QueueFull(..) | Self::PayerLimitExceeded { .. })
+    }
+
+    /// Returns the error message for the clients, with the full IDs and without styling.
+    pub fn message(&self) -> String {
+        let mut message = String::new();
+        // Note: Writing to a string does not fail.
+        let _ = self.write_message(&mut message, false);
+        message
+    }
+
+    /// Writes the error message. For the logs, the IDs are shortened, and the skipped transmissions are marked.
+    fn write_message(&self, f: &mut impl Write, is_log: bool) -> fmt::Result {
+        let id = |id: &String| match is_log {
+            true => fmt_id(id),
+            false => id.clone(),
+        };
+        let skipping = match is_log {
+            true => format!(" {}", "(skipping)".dimmed()),
+            false => String::new(),
+        };
+        match self {
+            Self::FeeTransaction(tx_id) => write!(f, "Transaction '{}' is a fee transaction{skipping}", id(tx_id)),
+            Self::Duplicate(tx_id) => write!(f, "Transmission '{}' was recently received", id(tx_id)),
+            Self::AlreadyInLedger(tx_id) => write!(f, "Transmission '{}' exists in the ledger{skipping}", id(tx_id)),
+            Self::QueueFull(tx_id) => {
+                write!(f, "Transaction '{}' has a lower priority fee per byte than the full memory pool", id(tx_id))
+            }
+            Self::PayerLimitExceeded { payer, limit } => {
+                write!(f, "Fee payer '{payer}' already has {limit} transactions pending in the memory pool")
+            }
+            Self::TooLarge(tx_id) => write!(f, "Transaction '{}' exceeds the byte limit", id(tx_id)),
+            Self::Malformed { id: tx_id, reason } => {
+                write!(f, "Transmission '{}' is not well-formed - {reason}", id(tx_id))
+            }
+        }
+    }
+}
+
+impl<N: Network> fmt::Display for AdmissionError<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_message(f, true)
+    }
+}
+
+impl<N: Network> std::error::Error for AdmissionError<N> {}
+
+/// Returns the payer of the public fee of the given transaction, or `None` if the fee is private or missing.
+/// Note: The payer of a public fee is the first argument of the future of `credits.aleo/fee_public`.
+/// A private fee doesn't reveal its payer, so the transactions paying one are not capped per fee payer.
+pub fn fee_payer<N: Network>(transaction: &Transaction<N>) -> Option<Address<N>> {
+    let fee = transaction.fee_transition()?;
+    if !fee.is_fee_public() {
+        return None;
+    }
+    fee.transition().outputs().iter().find_map(|output| match output {
+        Output::Future(_, Some(future)) => match future.arguments().first() {
+            Some(Argument::Plaintext(Plaintext::Literal(Literal::Address(payer), _))) => Some(*payer),
+            _ => None,
+        },
+        _ => None,
+    })
+}
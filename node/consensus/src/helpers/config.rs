@@ -0,0 +1,77 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkos_node_bft::{Primary, Worker};
+use snarkvm::prelude::Network;
+
+use anyhow::{Result, ensure};
+
+/// The default capacity of the queue reserved for deployments.
+const CAPACITY_FOR_DEPLOYMENTS: usize = 1 << 10;
+/// The default capacity of the queue reserved for executions.
+const CAPACITY_FOR_EXECUTIONS: usize = 1 << 10;
+/// The default capacity of the queue reserved for solutions.
+const CAPACITY_FOR_SOLUTIONS: usize = 1 << 10;
+/// The default **suggested** maximum number of deployments in each interval.
+const MAX_DEPLOYMENTS_PER_INTERVAL: usize = 1;
+/// The default maximum number of transactions that a fee payer may have pending in the inbound queue.
+const MAX_PENDING_PER_PAYER: usize = 64;
+
+/// The memory pool limits of consensus.
+/// Note: These are inbound queue limits, not Narwhal-enforced limits.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConsensusConfig {
+    /// The capacity of the queue reserved for deployments.
+    pub capacity_for_deployments: usize,
+    /// The capacity of the queue reserved for executions.
+    pub capacity_for_executions: usize,
+    /// The capacity of the queue reserved for solutions.
+    pub capacity_for_solutions: usize,
+    /// The **suggested** maximum number of deployments in each interval.
+    pub max_deployments_per_interval: usize,
+    /// The maximum number of transactions that a fee payer may have pending in the inbound queue.
+    /// Note: Only public fees reveal their payer, so the transactions paying a private fee are not capped.
+    pub max_pending_per_payer: usize,
+}
+
+impl Default for ConsensusConfig {
+    fn default() -> Self {
+        Self {
+            capacity_for_deployments: CAPACITY_FOR_DEPLOYMENTS,
+            capacity_for_executions: CAPACITY_FOR_EXECUTIONS,
+            capacity_for_solutions: CAPACITY_FOR_SOLUTIONS,
+            max_deployments_per_interval: MAX_DEPLOYMENTS_PER_INTERVAL,
+            max_pending_per_payer: MAX_PENDING_PER_PAYER,
+        }
+    }
+}
+
+impl ConsensusConfig {
+    /// Ensures the limits are consistent with each other, and with the memory pools of the primary and the workers.
+    pub fn check<N: Network>(&self) -> Result<()> {
+        ensure!(self.capacity_for_deployments > 0, "The capacity for deployments must be positive");
+        ensure!(self.capacity_for_executions > 0, "The capacity for executions must be positive");
+        ensure!(self.capacity_for_solutions > 0, "The capacity for solutions must be positive");
+        ensure!(self.max_pending_per_payer > 0, "The maximum pending transactions per fee payer must be positive");
+        // Ensure the deployments sent in each interval fit in the queue, and in the memory pools of the BFT.
+        let limit = Primary::<N>::MAX_TRANSMISSIONS_TOLERANCE.min(Worker::<N>::MAX_TRANSMISSIONS_PER_WORKER);
+        ensure!(
+            (1..=self.capacity_for_deployments.min(limit)).contains(&self.max_deployments_per_interval),
+            "The maximum deployments per interval must be between 1 and the capacity for deployments, \
+             and at most the transmissions limit of the memory pool ({limit})"
+        );
+        Ok(())
+    }
+}
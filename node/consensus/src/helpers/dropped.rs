@@ -0,0 +1,49 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+/// The number of recently-dropped transactions remembered by consensus.
+pub const DROPPED_TRANSACTIONS_CAPACITY: usize = 1 << 12;
+
+/// The reason a transaction left the memory pool without being included in a block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DropReason {
+    /// The transaction was evicted from the full inbound queue.
+    Evicted,
+    /// The transaction was refused by the workers, with the given error.
+    Refused(String),
+    /// The transaction was aborted in the block at the given height.
+    Aborted(u32),
+}
+
+impl fmt::Display for DropReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Evicted => write!(f, "evicted from the full inbound queue"),
+            Self::Refused(error) => write!(f, "refused by the workers - {error}"),
+            Self::Aborted(height) => write!(f, "aborted in block {height}"),
+        }
+    }
+}
+
+/// A record of a transaction that was dropped from the memory pool.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DroppedTransaction {
+    /// The reason the transaction was dropped.
+    pub reason: DropReason,
+    /// The UTC timestamp at which the transaction was dropped.
+    pub timestamp: i64,
+}
@@ -0,0 +1,39 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::{ledger::puzzle::SolutionID, prelude::Network};
+
+/// The capacity of the channel used to broadcast memory pool events.
+pub const MEMORY_POOL_EVENTS_CAPACITY: usize = 1 << 12;
+
+/// An event emitted by the inbound queues of the memory pool.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MemoryPoolEvent<N: Network> {
+    /// A solution was inserted into the inbound queue.
+    SolutionInserted(SolutionID<N>),
+    /// A solution was evicted from the inbound queue before reaching the workers.
+    SolutionEvicted(SolutionID<N>),
+    /// A transaction was inserted into the inbound queue.
+    TransactionInserted(N::TransactionID),
+    /// A transaction was evicted from the inbound queue before reaching the workers.
+    TransactionEvicted(N::TransactionID),
+}
+
+impl<N: Network> MemoryPoolEvent<N> {
+    /// Returns `true` if the event is an insertion.
+    pub const fn is_insertion(&self) -> bool {
+        matches!(self, Self::SolutionInserted(..) | Self::TransactionInserted(..))
+    }
+}
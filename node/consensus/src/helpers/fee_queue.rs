@@ -0,0 +1,294 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::{ledger::block::Transaction, prelude::Network};
+
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BTreeMap, HashMap},
+};
+
+/// The upper bounds of the fee buckets, in microcredits per byte, and the name of each bucket.
+/// Note: The last bucket holds every fee per byte above the previous bound.
+pub const FEE_BUCKETS: [(u64, &str); 5] = [(0, "0"), (1, "0-1"), (10, "1-10"), (100, "10-100"), (u64::MAX, "100+")];
+
+/// The priority fee of a transaction per byte of its serialized size.
+#[derive(Copy, Clone, Debug)]
+pub struct FeeRate {
+    /// The priority fee, in microcredits.
+    fee: u64,
+    /// The serialized size, in bytes.
+    size: u64,
+}
+
+impl FeeRate {
+    /// Initializes the fee per byte of the given priority fee and serialized size.
+    pub fn new(fee: u64, size: usize) -> Self {
+        // Note: The size is at least one byte, to ensure the fee per byte is well defined.
+        Self { fee, size: u64::try_from(size).unwrap_or(u64::MAX).max(1) }
+    }
+
+    /// Returns the index of the fee bucket of this fee per byte, in `FEE_BUCKETS`.
+    pub fn bucket_index(&self) -> usize {
+        // Note: The fee is compared to the bound times the size, to avoid rounding the fee per byte.
+        let is_within = |bound: u64| match bound {
+            0 => self.fee == 0,
+            u64::MAX => true,
+            bound => u128::from(self.fee) < u128::from(bound) * u128::from(self.size),
+        };
+        FEE_BUCKETS.iter().position(|(bound, _)| is_within(*bound)).unwrap_or(FEE_BUCKETS.len() - 1)
+    }
+
+    /// Returns the name of the fee bucket of this fee per byte.
+    pub fn bucket(&self) -> &'static str {
+        FEE_BUCKETS[self.bucket_index()].1
+    }
+}
+
+impl Ord for FeeRate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Compare the fractions `fee / size` exactly, by cross-multiplying.
+        (u128::from(self.fee) * u128::from(other.size)).cmp(&(u128::from(other.fee) * u128::from(self.size)))
+    }
+}
+
+impl PartialOrd for FeeRate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for FeeRate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for FeeRate {}
+
+/// The position of a transaction in the queue: the highest fee per byte first, then the oldest first.
+type Priority = (Reverse<FeeRate>, u64);
+
+/// A bounded queue of transactions, ordered by priority fee per byte, and then by age.
+pub struct FeeQueue<N: Network> {
+    /// The maximum number of transactions in the queue.
+    capacity: usize,
+    /// The sequence number of the next transaction, which orders the transactions by age.
+    next_sequence: u64,
+    /// The priority of each transaction in the queue.
+    priorities: HashMap<N::TransactionID, Priority>,
+    /// The transactions, in order of priority.
+    transactions: BTreeMap<Priority, Transaction<N>>,
+    /// The number of transactions in each fee bucket, updated as transactions are inserted and removed.
+    depths: [usize; FEE_BUCKETS.len()],
+}
+
+impl<N: Network> FeeQueue<N> {
+    /// Initializes an empty queue with the given capacity.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            next_sequence: 0,
+            priorities: Default::default(),
+            transactions: Default::default(),
+            depths: Default::default(),
+        }
+    }
+
+    /// Returns the number of transactions in the queue.
+    pub fn len(&self) -> usize {
+        self.transactions.len()
+    }
+
+    /// Returns `true` if the queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.transactions.is_empty()
+    }
+
+    /// Returns `true` if the given transaction ID is in the queue.
+    pub fn contains(&self, transaction_id: &N::TransactionID) -> bool {
+        self.priorities.contains_key(transaction_id)
+    }
+
+    /// Returns the transactions in the queue, in order of priority.
+    pub fn iter(&self) -> impl '_ + Iterator<Item = &Transaction<N>> {
+        self.transactions.values()
+    }
+
+    /// Inserts the given transaction with the given fee per byte, which must not be in the queue already.
+    /// If the queue is full, the transaction with the lowest priority is evicted and its ID returned,
+    /// which is the given transaction itself if every queued transaction has a higher priority.
+    pub fn push(&mut self, transaction: Transaction<N>, fee_rate: FeeRate) -> Option<N::TransactionID> {
+        let priority = (Reverse(fee_rate), self.next_sequence);
+        self.next_sequence = self.next_sequence.saturating_add(1);
+        self.priorities.insert(transaction.id(), priority);
+        self.transactions.insert(priority, transaction);
+        self.depths[fee_rate.bucket_index()] += 1;
+
+        // Evict the transaction with the lowest fee per byte, and the newest among those, if the queue is full.
+        match self.transactions.len() > self.capacity {
+            true => self.transactions.pop_last().map(|(priority, evicted)| self.forget(priority, &evicted)),
+            false => None,
+        }
+    }
+
+    /// Removes and returns the transaction with the highest priority.
+    pub fn pop(&mut self) -> Option<Transaction<N>> {
+        let (priority, transaction) = self.transactions.pop_first()?;
+        self.forget(priority, &transaction);
+        Some(transaction)
+    }
+
+    /// Returns the number of transactions in each fee bucket, in the order of `FEE_BUCKETS`.
+    pub fn depth_per_fee_bucket(&self) -> impl '_ + Iterator<Item = (&'static str, usize)> {
+        FEE_BUCKETS.iter().zip(self.depths.iter()).map(|((_, name), depth)| (*name, *depth))
+    }
+
+    /// Forgets the given transaction, which was removed from the queue, returning its ID.
+    fn forget(&mut self, (Reverse(fee_rate), _): Priority, transaction: &Transaction<N>) -> N::TransactionID {
+        self.priorities.remove(&transaction.id());
+        self.depths[fee_rate.bucket_index()] -= 1;
+        transaction.id()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::{
+        ledger::ledger_test_helpers::{
+            sample_execution_transaction_with_fee,
+            sample_fee_private_transaction,
+            sample_fee_public_transaction,
+            sample_large_execution_transaction,
+        },
+        prelude::{MainnetV0, TestRng},
+    };
+
+    use std::collections::HashSet;
+
+    type CurrentNetwork = MainnetV0;
+
+    /// Samples distinct transactions.
+    fn sample_transactions(rng: &mut TestRng) -> Vec<Transaction<CurrentNetwork>> {
+        let transactions = vec![
+            sample_execution_transaction_with_fee(true, rng),
+            sample_execution_transaction_with_fee(false, rng),
+            sample_fee_private_transaction(rng),
+            sample_fee_public_transaction(rng),
+            sample_large_execution_transaction(rng),
+        ];
+        // Ensure the transactions are distinct.
+        let ids = transactions.iter().map(|transaction| transaction.id()).collect::<HashSet<_>>();
+        assert_eq!(ids.len(), transactions.len());
+        transactions
+    }
+
+    /// Returns the number of transactions in each fee bucket of the given queue.
+    fn depths(queue: &FeeQueue<CurrentNetwork>) -> Vec<usize> {
+        queue.depth_per_fee_bucket().map(|(_, depth)| depth).collect()
+    }
+
+    #[test]
+    fn test_fee_rate_ordering() {
+        // A higher fee per byte is greater.
+        assert!(FeeRate::new(11, 100) > FeeRate::new(10, 100));
+        assert!(FeeRate::new(10, 99) > FeeRate::new(10, 100));
+        // The fees per byte are compared exactly, without rounding.
+        assert_eq!(FeeRate::new(1, 2), FeeRate::new(2, 4));
+        assert!(FeeRate::new(1, 3) < FeeRate::new(1, 2));
+        assert!(FeeRate::new(333_333, 1_000_000) < FeeRate::new(1, 3));
+        // The extreme fees and sizes don't overflow.
+        assert!(FeeRate::new(u64::MAX, usize::MAX) < FeeRate::new(u64::MAX, 1));
+        assert!(FeeRate::new(u64::MAX, 1) > FeeRate::new(u64::MAX - 1, 1));
+        // An empty size counts as one byte.
+        assert_eq!(FeeRate::new(5, 0), FeeRate::new(5, 1));
+    }
+
+    #[test]
+    fn test_fee_rate_bucket() {
+        assert_eq!(FeeRate::new(0, 100).bucket(), "0");
+        assert_eq!(FeeRate::new(1, 100).bucket(), "0-1");
+        assert_eq!(FeeRate::new(99, 100).bucket(), "0-1");
+        assert_eq!(FeeRate::new(100, 100).bucket(), "1-10");
+        assert_eq!(FeeRate::new(999, 100).bucket(), "1-10");
+        assert_eq!(FeeRate::new(1_000, 100).bucket(), "10-100");
+        assert_eq!(FeeRate::new(9_999, 100).bucket(), "10-100");
+        assert_eq!(FeeRate::new(10_000, 100).bucket(), "100+");
+        assert_eq!(FeeRate::new(u64::MAX, 1).bucket(), "100+");
+        for (index, (_, name)) in FEE_BUCKETS.iter().enumerate() {
+            let fee_rate = FeeRate::new([0, 50, 500, 5_000, 50_000][index], 100);
+            assert_eq!(fee_rate.bucket_index(), index);
+            assert_eq!(fee_rate.bucket(), *name);
+        }
+    }
+
+    #[test]
+    fn test_pop_order() {
+        let rng = &mut TestRng::default();
+        let transactions = sample_transactions(rng);
+
+        // Insert the transactions, with two of them sharing the highest fee per byte.
+        let mut queue = FeeQueue::new(transactions.len());
+        let fee_rates = [
+            FeeRate::new(50, 100),
+            FeeRate::new(5_000, 100),
+            FeeRate::new(0, 100),
+            FeeRate::new(5_000, 100),
+            FeeRate::new(500, 100),
+        ];
+        for (transaction, fee_rate) in transactions.iter().zip(fee_rates) {
+            assert_eq!(queue.push(transaction.clone(), fee_rate), None);
+        }
+        assert_eq!(queue.len(), transactions.len());
+        assert!(transactions.iter().all(|transaction| queue.contains(&transaction.id())));
+        assert_eq!(depths(&queue), vec![1, 1, 1, 2, 0]);
+
+        // The highest fee per byte comes first, and the oldest among equal fees per byte.
+        let expected = [1, 3, 4, 0, 2].map(|index| transactions[index].id());
+        assert_eq!(queue.iter().map(|transaction| transaction.id()).collect::<Vec<_>>(), expected);
+        let popped = std::iter::from_fn(|| queue.pop()).map(|transaction| transaction.id()).collect::<Vec<_>>();
+        assert_eq!(popped, expected);
+
+        // The queue is empty, and so are its fee buckets.
+        assert!(queue.is_empty());
+        assert!(transactions.iter().all(|transaction| !queue.contains(&transaction.id())));
+        assert_eq!(depths(&queue), vec![0; FEE_BUCKETS.len()]);
+    }
+
+    #[test]
+    fn test_eviction() {
+        let rng = &mut TestRng::default();
+        let transactions = sample_transactions(rng);
+        let ids = transactions.iter().map(|transaction| transaction.id()).collect::<Vec<_>>();
+
+        let mut queue = FeeQueue::new(2);
+        assert_eq!(queue.push(transactions[0].clone(), FeeRate::new(2, 1)), None);
+        assert_eq!(queue.push(transactions[1].clone(), FeeRate::new(2, 1)), None);
+        // The newest transaction among the lowest fees per byte is evicted, which is the new one on a tie.
+        assert_eq!(queue.push(transactions[2].clone(), FeeRate::new(2, 1)), Some(ids[2]));
+        // A transaction with a lower fee per byte than every queued transaction evicts itself.
+        assert_eq!(queue.push(transactions[3].clone(), FeeRate::new(1, 1)), Some(ids[3]));
+        assert!(!queue.contains(&ids[2]) && !queue.contains(&ids[3]));
+        assert_eq!(depths(&queue), vec![0, 0, 2, 0, 0]);
+
+        // A transaction with a higher fee per byte evicts the newest among the lowest.
+        assert_eq!(queue.push(transactions[4].clone(), FeeRate::new(3, 1)), Some(ids[1]));
+        assert_eq!(queue.len(), 2);
+        assert!(queue.contains(&ids[0]) && queue.contains(&ids[4]) && !queue.contains(&ids[1]));
+        assert_eq!(depths(&queue), vec![0, 0, 2, 0, 0]);
+        assert_eq!(queue.iter().map(|transaction| transaction.id()).collect::<Vec<_>>(), vec![ids[4], ids[0]]);
+    }
+}
@@ -0,0 +1,254 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::{
+    ledger::{block::Transaction, puzzle::Solution},
+    prelude::{FromBytes, IoResult, Network, Read, Result, ToBytes, Write, anyhow, bail, error},
+};
+
+use aleo_std::{StorageMode, aleo_ledger_dir};
+use std::{fs, path::PathBuf};
+
+/// The maximum number of solutions, and of transactions, in the memory pool journal.
+const MAX_JOURNAL_ENTRIES: u32 = 1 << 16;
+/// The interval between the writes of the memory pool journal, in seconds.
+pub(crate) const MEMORY_POOL_JOURNAL_INTERVAL_IN_SECS: u64 = 30;
+
+/// Returns the path where a memory pool journal file may be stored.
+/// Note: The journal is stored next to the proposal cache, in the folder right above the ledger.
+pub fn memory_pool_journal_path(network: u16, dev: Option<u16>) -> PathBuf {
+    const MEMORY_POOL_JOURNAL_FILE_NAME: &str = "current-memory-pool-journal";
+
+    // Obtain the path to the ledger.
+    let mut path = aleo_ledger_dir(network, StorageMode::from(dev));
+    // Go to the folder right above the ledger.
+    path.pop();
+    // Append the memory pool journal's file name.
+    match dev {
+        Some(id) => path.push(format!(".{MEMORY_POOL_JOURNAL_FILE_NAME}-{}-{}", network, id)),
+        None => path.push(format!("{MEMORY_POOL_JOURNAL_FILE_NAME}-{}", network)),
+    }
+
+    path
+}
+
+/// The state of the memory pool journal of a running node.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) enum JournalState {
+    /// The journal of the previous run is being replayed, so it is neither stored nor removed.
+    #[default]
+    Replaying,
+    /// The journal was replayed, and is stored periodically.
+    Open,
+    /// The journal was stored for the last time, on shutdown.
+    Closed,
+}
+
+/// A helper type for the unconfirmed solutions and transactions of the memory pool, kept across restarts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MemoryPoolJournal<N: Network> {
+    /// The unconfirmed solutions.
+    solutions: Vec<Solution<N>>,
+    /// The unconfirmed transactions.
+    transactions: Vec<Transaction<N>>,
+}
+
+impl<N: Network> MemoryPoolJournal<N> {
+    /// Initializes a new instance of the memory pool journal.
+    /// Note: Only the first `MAX_JOURNAL_ENTRIES` solutions, and transactions, are kept in the journal.
+    pub fn new(mut solutions: Vec<Solution<N>>, mut transactions: Vec<Transaction<N>>) -> Self {
+        solutions.truncate(MAX_JOURNAL_ENTRIES as usize);
+        transactions.truncate(MAX_JOURNAL_ENTRIES as usize);
+        Self { solutions, transactions }
+    }
+
+    /// Returns `true` if a memory pool journal exists for the given network and `dev`.
+    pub fn exists(dev: Option<u16>) -> bool {
+        memory_pool_journal_path(N::ID, dev).exists()
+    }
+
+    /// Load the memory pool journal from the file system.
+    pub fn load(dev: Option<u16>) -> Result<Self> {
+        // Construct the memory pool journal file system path.
+        let path = memory_pool_journal_path(N::ID, dev);
+
+        // Deserialize the memory pool journal from the file system.
+        let journal = match fs::read(&path) {
+            Ok(bytes) => match Self::from_bytes_le(&bytes) {
+                Ok(journal) => journal,
+                Err(_) => bail!("Couldn't deserialize the memory pool journal stored at {}", path.display()),
+            },
+            Err(_) => bail!("Couldn't read the memory pool journal stored at {}", path.display()),
+        };
+
+        info!(
+            "Loaded {} solutions and {} transactions from the memory pool journal at {}",
+            journal.solutions.len(),
+            journal.transactions.len(),
+            path.display()
+        );
+
+        Ok(journal)
+    }
+
+    /// Store the memory pool journal to the file system.
+    /// Note: The journal is written to a temporary file first, and then renamed,
+    /// so the stored journal is never left partially written.
+    pub fn store(&self, dev: Option<u16>) -> Result<()> {
+        let path = memory_pool_journal_path(N::ID, dev);
+        debug!("Storing the memory pool journal to {}...", path.display());
+
+        // Serialize the memory pool journal.
+        let bytes = self.to_bytes_le()?;
+        // Store the memory pool journal to a temporary file.
+        let mut temp_path = path.clone().into_os_string();
+        temp_path.push(".tmp");
+        let temp_path = PathBuf::from(temp_path);
+        fs::write(&temp_path, bytes)
+            .map_err(|err| anyhow!("Couldn't write the memory pool journal to {} - {err}", temp_path.display()))?;
+        // Replace the stored memory pool journal with the temporary file.
+        fs::rename(&temp_path, &path)
+            .map_err(|err| anyhow!("Couldn't move the memory pool journal to {} - {err}", path.display()))?;
+
+        Ok(())
+    }
+
+    /// Remove the memory pool journal from the file system, if it exists.
+    pub fn remove(dev: Option<u16>) -> Result<()> {
+        let path = memory_pool_journal_path(N::ID, dev);
+        match fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => bail!("Couldn't remove the memory pool journal at {} - {err}", path.display()),
+        }
+    }
+
+    /// Returns the solutions and transactions.
+    pub fn into(self) -> (Vec<Solution<N>>, Vec<Transaction<N>>) {
+        (self.solutions, self.transactions)
+    }
+}
+
+impl<N: Network> ToBytes for MemoryPoolJournal<N> {
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        // Write the number of solutions.
+        u32::try_from(self.solutions.len()).map_err(error)?.write_le(&mut writer)?;
+        // Serialize the solutions.
+        for solution in &self.solutions {
+            solution.write_le(&mut writer)?;
+        }
+        // Write the number of transactions.
+        u32::try_from(self.transactions.len()).map_err(error)?.write_le(&mut writer)?;
+        // Serialize the transactions.
+        for transaction in &self.transactions {
+            transaction.write_le(&mut writer)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<N: Network> FromBytes for MemoryPoolJournal<N> {
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        // Read the number of solutions.
+        let num_solutions = u32::read_le(&mut reader)?;
+        // Ensure the number of solutions is within bounds.
+        if num_solutions > MAX_JOURNAL_ENTRIES {
+            return Err(error(format!(
+                "Number of solutions ({num_solutions}) exceeds the maximum ({MAX_JOURNAL_ENTRIES})"
+            )));
+        }
+        // Deserialize the solutions.
+        let solutions = (0..num_solutions).map(|_| Solution::read_le(&mut reader)).collect::<IoResult<Vec<_>>>()?;
+        // Read the number of transactions.
+        let num_transactions = u32::read_le(&mut reader)?;
+        // Ensure the number of transactions is within bounds.
+        if num_transactions > MAX_JOURNAL_ENTRIES {
+            return Err(error(format!(
+                "Number of transactions ({num_transactions}) exceeds the maximum ({MAX_JOURNAL_ENTRIES})"
+            )));
+        }
+        // Deserialize the transactions.
+        let transactions =
+            (0..num_transactions).map(|_| Transaction::read_le(&mut reader)).collect::<IoResult<Vec<_>>>()?;
+
+        Ok(Self::new(solutions, transactions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::{
+        ledger::{
+            ledger_test_helpers::{
+                sample_execution_transaction_with_fee,
+                sample_fee_public_transaction,
+                sample_large_execution_transaction,
+            },
+            puzzle::PartialSolution,
+        },
+        prelude::{Address, MainnetV0, PrivateKey, Rng, TestRng},
+    };
+
+    type CurrentNetwork = MainnetV0;
+
+    const ITERATIONS: usize = 100;
+
+    /// Samples a memory pool journal with up to 3 solutions, and up to 3 of the given transactions.
+    fn sample_memory_pool_journal(
+        transactions: &[Transaction<CurrentNetwork>],
+        rng: &mut TestRng,
+    ) -> MemoryPoolJournal<CurrentNetwork> {
+        let solutions = (0..rng.gen_range(0..=3))
+            .map(|_| {
+                let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+                let address = Address::try_from(private_key).unwrap();
+                let partial_solution = PartialSolution::new(rng.gen(), address, rng.gen()).unwrap();
+                Solution::new(partial_solution, rng.gen())
+            })
+            .collect();
+        let transactions =
+            (0..rng.gen_range(0..=3)).map(|_| transactions[rng.gen_range(0..transactions.len())].clone()).collect();
+        MemoryPoolJournal::new(solutions, transactions)
+    }
+
+    #[test]
+    fn test_bytes() {
+        let rng = &mut TestRng::default();
+        let transactions = vec![
+            sample_execution_transaction_with_fee(true, rng),
+            sample_fee_public_transaction(rng),
+            sample_large_execution_transaction(rng),
+        ];
+
+        for _ in 0..ITERATIONS {
+            let expected = sample_memory_pool_journal(&transactions, rng);
+            // Check the byte representation.
+            let expected_bytes = expected.to_bytes_le().unwrap();
+            assert_eq!(expected, MemoryPoolJournal::read_le(&expected_bytes[..]).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_bytes_exceeding_entries() {
+        // Check that a journal with too many solutions is rejected.
+        let bytes = (MAX_JOURNAL_ENTRIES + 1).to_bytes_le().unwrap();
+        assert!(MemoryPoolJournal::<CurrentNetwork>::read_le(&bytes[..]).is_err());
+        // Check that a journal with too many transactions is rejected.
+        let bytes = [0u32.to_bytes_le().unwrap(), (MAX_JOURNAL_ENTRIES + 1).to_bytes_le().unwrap()].concat();
+        assert!(MemoryPoolJournal::<CurrentNetwork>::read_le(&bytes[..]).is_err());
+    }
+}
@@ -18,6 +18,9 @@
 #[macro_use]
 extern crate tracing;
 
+mod helpers;
+pub use helpers::*;
+
 use snarkos_account::Account;
 use snarkos_node_bft::{
     BFT,
@@ -38,7 +41,7 @@ use snarkos_node_bft_storage_service::BFTPersistentStorage;
 use snarkvm::{
     ledger::{
         block::Transaction,
-        narwhal::{BatchHeader, Data, Subdag, Transmission, TransmissionID},
+        narwhal::{BatchCertificate, BatchHeader, Data, Subdag, Transmission, TransmissionID},
         puzzle::{Solution, SolutionID},
     },
     prelude::*,
@@ -50,39 +53,145 @@ use colored::Colorize;
 use indexmap::IndexMap;
 use lru::LruCache;
 use parking_lot::Mutex;
-use std::{future::Future, net::SocketAddr, num::NonZeroUsize, sync::Arc, time::Duration};
+use std::{collections::HashMap, future::Future, net::SocketAddr, num::NonZeroUsize, sync::Arc, time::Duration};
 use tokio::{
-    sync::{OnceCell, oneshot},
+    sync::{OnceCell, broadcast, oneshot},
     task::JoinHandle,
 };
 
-#[cfg(feature = "metrics")]
-use std::collections::HashMap;
-
-/// The capacity of the queue reserved for deployments.
-/// Note: This is an inbound queue capacity, not a Narwhal-enforced capacity.
-const CAPACITY_FOR_DEPLOYMENTS: usize = 1 << 10;
-/// The capacity of the queue reserved for executions.
-/// Note: This is an inbound queue capacity, not a Narwhal-enforced capacity.
-const CAPACITY_FOR_EXECUTIONS: usize = 1 << 10;
-/// The capacity of the queue reserved for solutions.
-/// Note: This is an inbound queue capacity, not a Narwhal-enforced capacity.
-const CAPACITY_FOR_SOLUTIONS: usize = 1 << 10;
-/// The **suggested** maximum number of deployments in each interval.
-/// Note: This is an inbound queue limit, not a Narwhal-enforced limit.
-const MAX_DEPLOYMENTS_PER_INTERVAL: usize = 1;
-
-/// Helper struct to track incoming transactions.
+/// The number of seconds after which an unconfirmed transmission is stale, and its first-seen timestamp forgotten.
+/// Note: This matches the threshold of the transmission latency metric.
+#[cfg(not(feature = "metrics"))]
+const STALE_TRANSMISSION_SECS: i64 = 30 * 60;
+
+/// Helper struct to track incoming transactions, ordered by priority fee per byte.
 struct TransactionsQueue<N: Network> {
-    pub deployments: LruCache<N::TransactionID, Transaction<N>>,
-    pub executions: LruCache<N::TransactionID, Transaction<N>>,
+    pub deployments: FeeQueue<N>,
+    pub executions: FeeQueue<N>,
+    /// The public fee payer of each queued transaction.
+    payers: HashMap<N::TransactionID, Address<N>>,
+    /// The number of queued transactions of each public fee payer.
+    pending_per_payer: HashMap<Address<N>, usize>,
 }
 
-impl<N: Network> Default for TransactionsQueue<N> {
-    fn default() -> Self {
+impl<N: Network> TransactionsQueue<N> {
+    /// Initializes empty queues with the capacities of the given configuration.
+    fn new(config: &ConsensusConfig) -> Self {
         Self {
-            deployments: LruCache::new(NonZeroUsize::new(CAPACITY_FOR_DEPLOYMENTS).unwrap()),
-            executions: LruCache::new(NonZeroUsize::new(CAPACITY_FOR_EXECUTIONS).unwrap()),
+            deployments: FeeQueue::new(config.capacity_for_deployments),
+            executions: FeeQueue::new(config.capacity_for_executions),
+            payers: Default::default(),
+            pending_per_payer: Default::default(),
+        }
+    }
+
+    /// Returns the number of queued transactions of the given fee payer.
+    fn num_pending(&self, payer: &Address<N>) -> usize {
+        self.pending_per_payer.get(payer).copied().unwrap_or_default()
+    }
+
+    /// Records the fee payer of the given queued transaction.
+    fn track_payer(&mut self, transaction_id: N::TransactionID, payer: Address<N>) {
+        self.payers.insert(transaction_id, payer);
+        *self.pending_per_payer.entry(payer).or_default() += 1;
+    }
+
+    /// Forgets the fee payer of the given transaction, which left the queue.
+    fn untrack_payer(&mut self, transaction_id: &N::TransactionID) {
+        let Some(payer) = self.payers.remove(transaction_id) else {
+            return;
+        };
+        if let Some(pending) = self.pending_per_payer.get_mut(&payer) {
+            *pending = pending.saturating_sub(1);
+            if *pending == 0 {
+                self.pending_per_payer.remove(&payer);
+            }
+        }
+    }
+
+    /// Queues the given transaction, returning the ID of the transaction evicted to make room, if any.
+    /// The transaction is rejected if it is queued already, or if its fee payer has `max_pending` queued transactions.
+    /// Note: A transaction with a private fee has no known fee payer, so it is not capped.
+    fn push(
+        &mut self,
+        transaction: Transaction<N>,
+        fee_rate: FeeRate,
+        payer: Option<Address<N>>,
+        max_pending: usize,
+    ) -> Result<Option<N::TransactionID>, AdmissionError<N>> {
+        let transaction_id = transaction.id();
+        if self.deployments.contains(&transaction_id) || self.executions.contains(&transaction_id) {
+            return Err(AdmissionError::Duplicate(transaction_id.to_string()));
+        }
+        // Ensure the fee payer does not exceed its share of the inbound queue.
+        if let Some(payer) = payer.filter(|payer| self.num_pending(payer) >= max_pending) {
+            return Err(AdmissionError::PayerLimitExceeded { payer, limit: max_pending });
+        }
+        let evicted = match transaction.is_deploy() {
+            true => self.deployments.push(transaction, fee_rate),
+            false => self.executions.push(transaction, fee_rate),
+        };
+        // Account for the fee payer, unless the transaction was evicted right away.
+        if let Some(payer) = payer.filter(|_| evicted != Some(transaction_id)) {
+            self.track_payer(transaction_id, payer);
+        }
+        if let Some(evicted_id) = &evicted {
+            self.untrack_payer(evicted_id);
+        }
+        Ok(evicted)
+    }
+
+    /// Queues the given transaction as `push` does, rejecting it as `QueueFull` if it is evicted right away.
+    /// Note: The rejected or evicted transactions are forgotten as seen, so they may be resubmitted once there is room.
+    fn admit(
+        &mut self,
+        seen_transactions: &mut LruCache<N::TransactionID, ()>,
+        transaction: Transaction<N>,
+        fee_rate: FeeRate,
+        payer: Option<Address<N>>,
+        max_pending: usize,
+    ) -> Result<Option<N::TransactionID>, AdmissionError<N>> {
+        let transaction_id = transaction.id();
+        match self.push(transaction, fee_rate, payer, max_pending) {
+            Ok(Some(evicted_id)) => {
+                seen_transactions.pop(&evicted_id);
+                match evicted_id == transaction_id {
+                    true => Err(AdmissionError::QueueFull(transaction_id.to_string())),
+                    false => Ok(Some(evicted_id)),
+                }
+            }
+            Ok(None) => Ok(None),
+            Err(error) => {
+                if error.is_retryable() {
+                    seen_transactions.pop(&transaction_id);
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// Removes and returns the deployment, or the execution, with the highest priority.
+    fn pop(&mut self, is_deployment: bool) -> Option<Transaction<N>> {
+        let transaction = match is_deployment {
+            true => self.deployments.pop(),
+            false => self.executions.pop(),
+        }?;
+        self.untrack_payer(&transaction.id());
+        Some(transaction)
+    }
+
+    /// Updates the queue depth of each fee bucket.
+    #[cfg(feature = "metrics")]
+    fn update_metrics(&self) {
+        let depths = self.deployments.depth_per_fee_bucket().zip(self.executions.depth_per_fee_bucket());
+        for ((bucket, deployments), (_, executions)) in depths {
+            let depth = deployments + executions;
+            metrics::gauge_label(
+                metrics::consensus::TRANSACTION_QUEUE_DEPTH,
+                "fee_bucket",
+                bucket.to_string(),
+                depth as f64,
+            );
         }
     }
 }
@@ -103,7 +212,17 @@ pub struct Consensus<N: Network> {
     seen_solutions: Arc<Mutex<LruCache<SolutionID<N>, ()>>>,
     /// The recently-seen unconfirmed transactions.
     seen_transactions: Arc<Mutex<LruCache<N::TransactionID, ()>>>,
-    #[cfg(feature = "metrics")]
+    /// The recently-dropped unconfirmed transactions.
+    dropped_transactions: Arc<Mutex<LruCache<N::TransactionID, DroppedTransaction>>>,
+    /// The sender for the memory pool events.
+    memory_pool_events: broadcast::Sender<MemoryPoolEvent<N>>,
+    /// The memory pool limits.
+    config: ConsensusConfig,
+    /// The development ID, if the node is in development mode.
+    dev: Option<u16>,
+    /// The state of the memory pool journal.
+    journal_state: Arc<Mutex<JournalState>>,
+    /// The UNIX timestamp at which each unconfirmed transmission was first seen.
     transmissions_queue_timestamps: Arc<Mutex<HashMap<TransmissionID<N>, i64>>>,
     /// The spawned handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
@@ -117,7 +236,10 @@ impl<N: Network> Consensus<N> {
         ip: Option<SocketAddr>,
         trusted_validators: &[SocketAddr],
         storage_mode: StorageMode,
+        config: ConsensusConfig,
     ) -> Result<Self> {
+        // Ensure the memory pool limits are valid.
+        config.check::<N>()?;
         // Recover the development ID, if it is present.
         let dev = match storage_mode {
             StorageMode::Development(id) => Some(id),
@@ -134,11 +256,19 @@ impl<N: Network> Consensus<N> {
             ledger,
             bft,
             primary_sender: Default::default(),
-            solutions_queue: Arc::new(Mutex::new(LruCache::new(NonZeroUsize::new(CAPACITY_FOR_SOLUTIONS).unwrap()))),
-            transactions_queue: Default::default(),
+            solutions_queue: Arc::new(Mutex::new(LruCache::new(
+                NonZeroUsize::new(config.capacity_for_solutions).unwrap(),
+            ))),
+            transactions_queue: Arc::new(Mutex::new(TransactionsQueue::new(&config))),
             seen_solutions: Arc::new(Mutex::new(LruCache::new(NonZeroUsize::new(1 << 16).unwrap()))),
             seen_transactions: Arc::new(Mutex::new(LruCache::new(NonZeroUsize::new(1 << 16).unwrap()))),
-            #[cfg(feature = "metrics")]
+            dropped_transactions: Arc::new(Mutex::new(LruCache::new(
+                NonZeroUsize::new(DROPPED_TRANSACTIONS_CAPACITY).unwrap(),
+            ))),
+            memory_pool_events: broadcast::channel(MEMORY_POOL_EVENTS_CAPACITY).0,
+            config,
+            dev,
+            journal_state: Default::default(),
             transmissions_queue_timestamps: Default::default(),
             handles: Default::default(),
         })
@@ -154,8 +284,22 @@ impl<N: Network> Consensus<N> {
         let (consensus_sender, consensus_receiver) = init_consensus_channels();
         // Then, start the consensus handlers.
         self.start_handlers(consensus_receiver);
-        // Lastly, the consensus.
+        // Next, start the BFT.
         self.bft.run(Some(consensus_sender), primary_sender, primary_receiver).await?;
+        // Lastly, replay the memory pool journal from the previous run, and then store the journal periodically.
+        let self_ = self.clone();
+        self.spawn(async move {
+            self_.replay_memory_pool_journal().await;
+            loop {
+                // Sleep briefly.
+                tokio::time::sleep(Duration::from_secs(MEMORY_POOL_JOURNAL_INTERVAL_IN_SECS)).await;
+                // Store the unconfirmed solutions and transactions to disk.
+                let self__ = self_.clone();
+                if let Err(err) = spawn_blocking!(self__.store_memory_pool_journal(false)) {
+                    warn!("Failed to store the memory pool journal - {err}");
+                }
+            }
+        });
         Ok(())
     }
 
@@ -164,6 +308,11 @@ impl<N: Network> Consensus<N> {
         &self.ledger
     }
 
+    /// Returns the memory pool limits.
+    pub const fn config(&self) -> &ConsensusConfig {
+        &self.config
+    }
+
     /// Returns the BFT.
     pub const fn bft(&self) -> &BFT<N> {
         &self.bft
@@ -173,6 +322,11 @@ impl<N: Network> Consensus<N> {
     pub fn primary_sender(&self) -> &PrimarySender<N> {
         self.primary_sender.get().expect("Primary sender not set")
     }
+
+    /// Returns a receiver for the insertions into and evictions from the inbound queues.
+    pub fn subscribe_to_memory_pool(&self) -> broadcast::Receiver<MemoryPoolEvent<N>> {
+        self.memory_pool_events.subscribe()
+    }
 }
 
 impl<N: Network> Consensus<N> {
@@ -217,6 +371,28 @@ impl<N: Network> Consensus<N> {
     pub fn unconfirmed_transactions(&self) -> impl '_ + Iterator<Item = (N::TransactionID, Data<Transaction<N>>)> {
         self.worker_transactions().chain(self.inbound_transactions())
     }
+
+    /// Returns `true` if the given transaction ID is in the inbound queue.
+    pub fn contains_inbound_transaction(&self, transaction_id: &N::TransactionID) -> bool {
+        let tx_queue = self.transactions_queue.lock();
+        tx_queue.deployments.contains(transaction_id) || tx_queue.executions.contains(transaction_id)
+    }
+
+    /// Returns the uncommitted certificate that includes the given transaction ID, if one exists in storage.
+    pub fn find_certificate_for_transaction(&self, transaction_id: &N::TransactionID) -> Option<BatchCertificate<N>> {
+        self.bft.storage().get_certificate_for_transaction(transaction_id)
+    }
+
+    /// Returns the record of the given transaction ID, if it was recently dropped from the memory pool.
+    pub fn get_dropped_transaction(&self, transaction_id: &N::TransactionID) -> Option<DroppedTransaction> {
+        self.dropped_transactions.lock().peek(transaction_id).cloned()
+    }
+
+    /// Returns the UNIX timestamp at which the given transmission was first seen, if it is still tracked.
+    /// Note: The timestamps are forgotten once the transmission is in a block, or after `STALE_TRANSMISSION_SECS`.
+    pub fn transmission_first_seen(&self, transmission_id: &TransmissionID<N>) -> Option<i64> {
+        self.transmissions_queue_timestamps.lock().get(transmission_id).copied()
+    }
 }
 
 impl<N: Network> Consensus<N> {
@@ -277,10 +453,11 @@ impl<N: Network> Consensus<N> {
         // Return an iterator over the deployment and execution transactions in the inbound queue.
         tx_queue
             .deployments
-            .clone()
+            .iter()
+            .chain(tx_queue.executions.iter())
+            .map(|tx| (tx.id(), Data::Object(tx.clone())))
+            .collect::<Vec<_>>()
             .into_iter()
-            .chain(tx_queue.executions.clone())
-            .map(|(id, tx)| (id, Data::Object(tx)))
     }
 }
 
@@ -290,31 +467,35 @@ impl<N: Network> Consensus<N> {
         // Calculate the transmission checksum.
         let checksum = Data::<Solution<N>>::Buffer(solution.to_bytes_le()?.into()).to_checksum::<N>()?;
         #[cfg(feature = "metrics")]
-        {
-            metrics::increment_gauge(metrics::consensus::UNCONFIRMED_SOLUTIONS, 1f64);
-            let timestamp = snarkos_node_bft::helpers::now();
-            self.transmissions_queue_timestamps
-                .lock()
-                .insert(TransmissionID::Solution(solution.id(), checksum), timestamp);
-        }
+        metrics::increment_gauge(metrics::consensus::UNCONFIRMED_SOLUTIONS, 1f64);
+        let timestamp = snarkos_node_bft::helpers::now();
+        self.transmissions_queue_timestamps.lock().insert(TransmissionID::Solution(solution.id(), checksum), timestamp);
         // Queue the unconfirmed solution.
         {
             let solution_id = solution.id();
 
-            // Check if the transaction was recently seen.
+            // Check if the solution was recently seen.
             if self.seen_solutions.lock().put(solution_id, ()).is_some() {
-                // If the transaction was recently seen, return early.
-                return Ok(());
+                // If the solution was recently seen, reject it as a duplicate.
+                bail!(AdmissionError::<N>::Duplicate(solution_id.to_string()));
             }
             // Check if the solution already exists in the ledger.
             if self.ledger.contains_transmission(&TransmissionID::Solution(solution_id, checksum))? {
-                bail!("Solution '{}' exists in the ledger {}", fmt_id(solution_id), "(skipping)".dimmed());
+                bail!(AdmissionError::<N>::AlreadyInLedger(solution_id.to_string()));
             }
             // Add the solution to the memory pool.
             trace!("Received unconfirmed solution '{}' in the queue", fmt_id(solution_id));
-            if self.solutions_queue.lock().put(solution_id, solution).is_some() {
-                bail!("Solution '{}' exists in the memory pool", fmt_id(solution_id));
+            let evicted = self.solutions_queue.lock().push(solution_id, solution);
+            match evicted {
+                Some((evicted_id, _)) if evicted_id == solution_id => {
+                    bail!(AdmissionError::<N>::Duplicate(solution_id.to_string()));
+                }
+                Some((evicted_id, _)) => {
+                    self.notify_memory_pool(MemoryPoolEvent::SolutionEvicted(evicted_id));
+                }
+                None => {}
             }
+            self.notify_memory_pool(MemoryPoolEvent::SolutionInserted(solution_id));
         }
 
         // Try to process the unconfirmed solutions in the memory pool.
@@ -362,44 +543,73 @@ impl<N: Network> Consensus<N> {
 
     /// Adds the given unconfirmed transaction to the memory pool.
     pub async fn add_unconfirmed_transaction(&self, transaction: Transaction<N>) -> Result<()> {
-        // Calculate the transmission checksum.
-        let checksum = Data::<Transaction<N>>::Buffer(transaction.to_bytes_le()?.into()).to_checksum::<N>()?;
-        #[cfg(feature = "metrics")]
-        {
-            metrics::increment_gauge(metrics::consensus::UNCONFIRMED_TRANSACTIONS, 1f64);
-            let timestamp = snarkos_node_bft::helpers::now();
-            self.transmissions_queue_timestamps
-                .lock()
-                .insert(TransmissionID::Transaction(transaction.id(), checksum), timestamp);
+        let malformed = |error: anyhow::Error| AdmissionError::<N>::Malformed {
+            id: transaction.id().to_string(),
+            reason: error.to_string(),
+        };
+        // Calculate the transmission checksum, and the priority fee per byte.
+        let bytes = transaction.to_bytes_le().map_err(malformed)?;
+        // Ensure the transaction is within the transaction size limit.
+        if bytes.len() > N::MAX_TRANSACTION_SIZE {
+            bail!(AdmissionError::<N>::TooLarge(transaction.id().to_string()));
         }
+        let fee_rate = FeeRate::new(*transaction.priority_fee_amount().map_err(malformed)?, bytes.len());
+        let payer = fee_payer(&transaction);
+        let checksum = Data::<Transaction<N>>::Buffer(bytes.into()).to_checksum::<N>()?;
+        #[cfg(feature = "metrics")]
+        metrics::increment_gauge(metrics::consensus::UNCONFIRMED_TRANSACTIONS, 1f64);
+        let timestamp = snarkos_node_bft::helpers::now();
+        self.transmissions_queue_timestamps
+            .lock()
+            .insert(TransmissionID::Transaction(transaction.id(), checksum), timestamp);
         // Queue the unconfirmed transaction.
         {
             let transaction_id = transaction.id();
 
             // Check that the transaction is not a fee transaction.
             if transaction.is_fee() {
-                bail!("Transaction '{}' is a fee transaction {}", fmt_id(transaction_id), "(skipping)".dimmed());
+                bail!(AdmissionError::<N>::FeeTransaction(transaction_id.to_string()));
             }
             // Check if the transaction was recently seen.
             if self.seen_transactions.lock().put(transaction_id, ()).is_some() {
-                // If the transaction was recently seen, return early.
-                return Ok(());
+                // If the transaction was recently seen, reject it as a duplicate.
+                bail!(AdmissionError::<N>::Duplicate(transaction_id.to_string()));
             }
             // Check if the transaction already exists in the ledger.
             if self.ledger.contains_transmission(&TransmissionID::Transaction(transaction_id, checksum))? {
-                bail!("Transaction '{}' exists in the ledger {}", fmt_id(transaction_id), "(skipping)".dimmed());
+                bail!(AdmissionError::<N>::AlreadyInLedger(transaction_id.to_string()));
             }
             // Add the transaction to the memory pool.
             trace!("Received unconfirmed transaction '{}' in the queue", fmt_id(transaction_id));
-            if transaction.is_deploy() {
-                if self.transactions_queue.lock().deployments.put(transaction_id, transaction).is_some() {
-                    bail!("Transaction '{}' exists in the memory pool", fmt_id(transaction_id));
+            let admitted = {
+                let mut tx_queue = self.transactions_queue.lock();
+                let max_pending = self.config.max_pending_per_payer;
+                let admitted =
+                    tx_queue.admit(&mut self.seen_transactions.lock(), transaction, fee_rate, payer, max_pending);
+                #[cfg(feature = "metrics")]
+                tx_queue.update_metrics();
+                admitted
+            };
+            let evicted = match admitted {
+                Ok(evicted) => evicted,
+                Err(error) => {
+                    if matches!(error, AdmissionError::QueueFull(..)) {
+                        self.record_dropped_transaction(transaction_id, DropReason::Evicted);
+                    }
+                    bail!(error);
                 }
-            } else if self.transactions_queue.lock().executions.put(transaction_id, transaction).is_some() {
-                bail!("Transaction '{}' exists in the memory pool", fmt_id(transaction_id));
+            };
+            if let Some(evicted_id) = evicted {
+                self.record_dropped_transaction(evicted_id, DropReason::Evicted);
+                self.notify_memory_pool(MemoryPoolEvent::TransactionEvicted(evicted_id));
             }
+            // Forget any earlier drop of this transaction, as it is back in the memory pool.
+            self.dropped_transactions.lock().pop(&transaction_id);
+            self.notify_memory_pool(MemoryPoolEvent::TransactionInserted(transaction_id));
 
             // Try to process the unconfirmed transactions in the memory pool.
+            // Note: The transaction is verified by the workers once it leaves the queue, and a refusal
+            // is recorded as a drop, as it may happen after this call returns.
             self.process_unconfirmed_transactions().await
         }
     }
@@ -418,22 +628,20 @@ impl<N: Network> Consensus<N> {
             // Acquire the lock on the transactions queue.
             let mut tx_queue = self.transactions_queue.lock();
             // Determine the number of deployments to send.
-            let num_deployments = tx_queue.deployments.len().min(capacity).min(MAX_DEPLOYMENTS_PER_INTERVAL);
+            let num_deployments =
+                tx_queue.deployments.len().min(capacity).min(self.config.max_deployments_per_interval);
             // Determine the number of executions to send.
             let num_executions = tx_queue.executions.len().min(capacity.saturating_sub(num_deployments));
             // Create an iterator which will select interleaved deployments and executions within the capacity.
             // Note: interleaving ensures we will never have consecutive invalid deployments blocking the queue.
             let selector_iter = (0..num_deployments).map(|_| true).interleave((0..num_executions).map(|_| false));
-            // Drain the transactions from the queue, interleaving deployments and executions.
-            selector_iter
-                .filter_map(|select_deployment| {
-                    if select_deployment {
-                        tx_queue.deployments.pop_lru().map(|(_, tx)| tx)
-                    } else {
-                        tx_queue.executions.pop_lru().map(|(_, tx)| tx)
-                    }
-                })
-                .collect_vec()
+            // Drain the transactions with the highest priority fee per byte from the queue,
+            // interleaving deployments and executions.
+            let transactions =
+                selector_iter.filter_map(|select_deployment| tx_queue.pop(select_deployment)).collect_vec();
+            #[cfg(feature = "metrics")]
+            tx_queue.update_metrics();
+            transactions
         };
         // Iterate over the transactions.
         for transaction in transactions.into_iter() {
@@ -450,6 +658,7 @@ impl<N: Network> Consensus<N> {
                         fmt_id(transaction_id)
                     );
                 }
+                self.record_dropped_transaction(transaction_id, DropReason::Refused(e.to_string()));
             }
         }
         Ok(())
@@ -530,10 +739,23 @@ impl<N: Network> Consensus<N> {
         // Advance to the next block.
         self.ledger.advance_to_next_block(&next_block)?;
 
+        // Record the transactions that were aborted in the block.
+        for transaction_id in next_block.aborted_transaction_ids() {
+            self.record_dropped_transaction(*transaction_id, DropReason::Aborted(next_block.height()));
+        }
+
         // If the next block starts a new epoch, clear the existing solutions.
         if next_block.height() % N::NUM_BLOCKS_PER_EPOCH == 0 {
             // Clear the solutions queue.
-            self.solutions_queue.lock().clear();
+            let evicted_ids = {
+                let mut solutions_queue = self.solutions_queue.lock();
+                let evicted_ids = solutions_queue.iter().map(|(id, _)| *id).collect_vec();
+                solutions_queue.clear();
+                evicted_ids
+            };
+            for solution_id in evicted_ids {
+                self.notify_memory_pool(MemoryPoolEvent::SolutionEvicted(solution_id));
+            }
             // Clear the worker solutions.
             self.bft.primary().clear_worker_solutions();
         }
@@ -556,15 +778,39 @@ impl<N: Network> Consensus<N> {
             metrics::gauge(metrics::blocks::COINBASE_TARGET, coinbase_target as f64);
             metrics::gauge(metrics::blocks::CUMULATIVE_PROOF_TARGET, cumulative_proof_target as f64);
         }
+        // Forget the first-seen timestamps of the transmissions in the block, as the metrics do when enabled.
+        #[cfg(not(feature = "metrics"))]
+        self.forget_first_seen(&next_block);
         Ok(())
     }
 
+    /// Forgets the first-seen timestamps of the transmissions in the given block, and of the stale transmissions.
+    #[cfg(not(feature = "metrics"))]
+    fn forget_first_seen(&self, block: &snarkvm::ledger::block::Block<N>) {
+        use std::collections::HashSet;
+
+        let solution_ids = block.solutions().solution_ids().chain(block.aborted_solution_ids()).collect::<HashSet<_>>();
+        let transaction_ids = block.transaction_ids().chain(block.aborted_transaction_ids()).collect::<HashSet<_>>();
+        let stale_timestamp = snarkos_node_bft::helpers::now().saturating_sub(STALE_TRANSMISSION_SECS);
+        self.transmissions_queue_timestamps.lock().retain(|transmission_id, timestamp| {
+            *timestamp > stale_timestamp
+                && match transmission_id {
+                    TransmissionID::Solution(solution_id, _) => !solution_ids.contains(solution_id),
+                    TransmissionID::Transaction(transaction_id, _) => !transaction_ids.contains(transaction_id),
+                    TransmissionID::Ratification => false,
+                }
+        });
+    }
+
     /// Reinserts the given transmissions into the memory pool.
     async fn reinsert_transmissions(&self, transmissions: IndexMap<TransmissionID<N>, Transmission<N>>) {
         // Iterate over the transmissions.
         for (transmission_id, transmission) in transmissions.into_iter() {
             // Reinsert the transmission into the memory pool.
             if let Err(e) = self.reinsert_transmission(transmission_id, transmission).await {
+                if let TransmissionID::Transaction(transaction_id, _) = transmission_id {
+                    self.record_dropped_transaction(transaction_id, DropReason::Refused(e.to_string()));
+                }
                 warn!(
                     "Unable to reinsert transmission {}.{} into the memory pool - {e}",
                     fmt_id(transmission_id),
@@ -599,6 +845,103 @@ impl<N: Network> Consensus<N> {
         callback_receiver.await?
     }
 
+    /// Returns the memory pool journal of the unconfirmed solutions and transactions, in the workers and the queues.
+    fn memory_pool_journal(&self) -> MemoryPoolJournal<N> {
+        // Note: The worker transmissions come first, as they were received before the queued ones,
+        // followed by the queued ones in order of priority, so the journal keeps the first ones if it is full.
+        let mut solutions = self
+            .worker_solutions()
+            .filter_map(|(_, solution)| solution.deserialize_blocking().ok())
+            .collect::<Vec<_>>();
+        solutions.extend(self.solutions_queue.lock().iter().rev().map(|(_, solution)| *solution));
+        let mut transactions = self
+            .worker_transactions()
+            .filter_map(|(_, transaction)| transaction.deserialize_blocking().ok())
+            .collect::<Vec<_>>();
+        {
+            let tx_queue = self.transactions_queue.lock();
+            transactions.extend(tx_queue.deployments.iter().chain(tx_queue.executions.iter()).cloned());
+        }
+        MemoryPoolJournal::new(solutions, transactions)
+    }
+
+    /// Stores the memory pool journal to disk, unless it was stored for the last time already.
+    /// If `close` is `true`, the journal is not stored again, so the final journal is not overwritten on shutdown.
+    /// Note: While the journal of the previous run is replayed, it is kept, as it holds the entries yet to replay.
+    fn store_memory_pool_journal(&self, close: bool) -> Result<()> {
+        // Note: The lock is held while storing, so the journal is stored by one task at a time.
+        let mut journal_state = self.journal_state.lock();
+        let state = *journal_state;
+        if close {
+            *journal_state = JournalState::Closed;
+        }
+        match state {
+            JournalState::Open => self.memory_pool_journal().store(self.dev),
+            JournalState::Replaying | JournalState::Closed => Ok(()),
+        }
+    }
+
+    /// Replays the unconfirmed solutions and transactions of the memory pool journal, if it exists.
+    async fn replay_memory_pool_journal(&self) {
+        // If the memory pool journal does not exist, then return early.
+        if !MemoryPoolJournal::<N>::exists(self.dev) {
+            self.finish_memory_pool_journal_replay(false);
+            return;
+        }
+        let dev = self.dev;
+        let (solutions, transactions) = match spawn_blocking!(MemoryPoolJournal::<N>::load(dev)) {
+            Ok(journal) => journal.into(),
+            Err(err) => {
+                warn!("Failed to load the memory pool journal - {err}");
+                self.finish_memory_pool_journal_replay(false);
+                return;
+            }
+        };
+        // Add the solutions and transactions back to the memory pool.
+        // Note: The solutions and transactions that exist in the ledger are skipped.
+        for solution in solutions {
+            let solution_id = solution.id();
+            if let Err(err) = self.add_unconfirmed_solution(solution).await {
+                trace!("Skipped solution '{}' from the memory pool journal - {err}", fmt_id(solution_id));
+            }
+        }
+        for transaction in transactions {
+            let transaction_id = transaction.id();
+            if let Err(err) = self.add_unconfirmed_transaction(transaction).await {
+                trace!("Skipped transaction '{}' from the memory pool journal - {err}", fmt_id(transaction_id));
+            }
+        }
+        // Remove the replayed memory pool journal, as its transmissions are back in the memory pool.
+        self.finish_memory_pool_journal_replay(true);
+    }
+
+    /// Ends the replay of the memory pool journal, so it is stored periodically, removing the journal if requested.
+    /// Note: If the journal was closed during the replay, it is kept as is, to be replayed in full on the next run.
+    fn finish_memory_pool_journal_replay(&self, remove: bool) {
+        let mut journal_state = self.journal_state.lock();
+        if *journal_state == JournalState::Closed {
+            return;
+        }
+        *journal_state = JournalState::Open;
+        if remove {
+            if let Err(err) = MemoryPoolJournal::<N>::remove(self.dev) {
+                warn!("Failed to remove the memory pool journal - {err}");
+            }
+        }
+    }
+
+    /// Records the given transaction ID as dropped from the memory pool.
+    fn record_dropped_transaction(&self, transaction_id: N::TransactionID, reason: DropReason) {
+        let dropped = DroppedTransaction { reason, timestamp: snarkos_node_bft::helpers::now() };
+        self.dropped_transactions.lock().put(transaction_id, dropped);
+    }
+
+    /// Notifies the memory pool subscribers of the given event.
+    fn notify_memory_pool(&self, event: MemoryPoolEvent<N>) {
+        // Note: Sending only fails if there are no subscribers, which is not an error.
+        let _ = self.memory_pool_events.send(event);
+    }
+
     /// Spawns a task with the given future; it should only be used for long-running tasks.
     fn spawn<T: Future<Output = ()> + Send + 'static>(&self, future: T) {
         self.handles.lock().push(tokio::spawn(future));
@@ -607,9 +950,156 @@ impl<N: Network> Consensus<N> {
     /// Shuts down the BFT.
     pub async fn shut_down(&self) {
         info!("Shutting down consensus...");
+        // Save the unconfirmed solutions and transactions to disk, before the workers are shut down.
+        let self_ = self.clone();
+        if let Err(err) = spawn_blocking!(self_.store_memory_pool_journal(true)) {
+            error!("Failed to store the memory pool journal: {err}");
+        }
         // Shut down the BFT.
         self.bft.shut_down().await;
         // Abort the tasks.
         self.handles.lock().iter().for_each(|handle| handle.abort());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::{
+        ledger::ledger_test_helpers::{
+            sample_execution_transaction_with_fee,
+            sample_fee_private_transaction,
+            sample_fee_public_transaction,
+            sample_large_execution_transaction,
+        },
+        prelude::{MainnetV0, TestRng},
+    };
+
+    type CurrentNetwork = MainnetV0;
+
+    /// Samples distinct execution transactions.
+    fn sample_executions(rng: &mut TestRng) -> Vec<Transaction<CurrentNetwork>> {
+        let transactions = vec![
+            sample_execution_transaction_with_fee(true, rng),
+            sample_execution_transaction_with_fee(false, rng),
+            sample_large_execution_transaction(rng),
+        ];
+        // Ensure the transactions are distinct.
+        assert_eq!(transactions.iter().map(|transaction| transaction.id()).unique().count(), transactions.len());
+        transactions
+    }
+
+    /// Initializes empty queues, with the given capacity for executions.
+    fn sample_queue(capacity_for_executions: usize) -> TransactionsQueue<CurrentNetwork> {
+        TransactionsQueue::new(&ConsensusConfig { capacity_for_executions, ..Default::default() })
+    }
+
+    #[test]
+    fn test_payer_limit() {
+        let rng = &mut TestRng::default();
+        let transactions = sample_executions(rng);
+        let payer = Address::rand(rng);
+        let fee_rate = FeeRate::new(1, 1);
+
+        let mut queue = sample_queue(transactions.len());
+        assert_eq!(queue.push(transactions[0].clone(), fee_rate, Some(payer), 2), Ok(None));
+        assert_eq!(queue.push(transactions[1].clone(), fee_rate, Some(payer), 2), Ok(None));
+        assert_eq!(queue.num_pending(&payer), 2);
+        // The fee payer is at its limit, so its next transaction is rejected, and not accounted for.
+        assert_eq!(
+            queue.push(transactions[2].clone(), fee_rate, Some(payer), 2),
+            Err(AdmissionError::PayerLimitExceeded { payer, limit: 2 })
+        );
+        assert_eq!(queue.num_pending(&payer), 2);
+        assert!(!queue.executions.contains(&transactions[2].id()));
+        // A duplicate transaction is rejected, and not accounted for.
+        assert_eq!(
+            queue.push(transactions[0].clone(), fee_rate, Some(payer), 3),
+            Err(AdmissionError::Duplicate(transactions[0].id().to_string()))
+        );
+        assert_eq!(queue.num_pending(&payer), 2);
+        // Another fee payer is not affected.
+        let other_payer = Address::rand(rng);
+        assert_eq!(queue.push(transactions[2].clone(), fee_rate, Some(other_payer), 2), Ok(None));
+        assert_eq!(queue.num_pending(&other_payer), 1);
+
+        // Once the transactions are drained, the fee payers have no pending transactions.
+        assert_eq!(std::iter::from_fn(|| queue.pop(false)).count(), transactions.len());
+        assert_eq!(queue.num_pending(&payer), 0);
+        assert_eq!(queue.num_pending(&other_payer), 0);
+        assert!(queue.payers.is_empty() && queue.pending_per_payer.is_empty());
+        // The fee payer may queue transactions again.
+        assert_eq!(queue.push(transactions[2].clone(), fee_rate, Some(payer), 2), Ok(None));
+        assert_eq!(queue.num_pending(&payer), 1);
+    }
+
+    #[test]
+    fn test_payer_eviction() {
+        let rng = &mut TestRng::default();
+        let transactions = sample_executions(rng);
+        let ids = transactions.iter().map(|transaction| transaction.id()).collect::<Vec<_>>();
+        let payer = Address::rand(rng);
+
+        let mut queue = sample_queue(1);
+        assert_eq!(queue.push(transactions[0].clone(), FeeRate::new(1, 1), Some(payer), 2), Ok(None));
+        assert_eq!(queue.num_pending(&payer), 1);
+        // A transaction with a higher fee per byte evicts the queued one, which no longer counts for its fee payer.
+        assert_eq!(queue.push(transactions[1].clone(), FeeRate::new(2, 1), None, 2), Ok(Some(ids[0])));
+        assert_eq!(queue.num_pending(&payer), 0);
+        // A transaction evicted right away is not accounted for.
+        assert_eq!(queue.push(transactions[2].clone(), FeeRate::new(1, 1), Some(payer), 2), Ok(Some(ids[2])));
+        assert_eq!(queue.num_pending(&payer), 0);
+        assert!(queue.payers.is_empty() && queue.pending_per_payer.is_empty());
+    }
+
+    #[test]
+    fn test_resubmit_evicted() {
+        let rng = &mut TestRng::default();
+        let transactions = sample_executions(rng);
+        let ids = transactions.iter().map(|transaction| transaction.id()).collect::<Vec<_>>();
+        let mut seen_transactions = LruCache::new(NonZeroUsize::new(16).unwrap());
+        let mut queue = sample_queue(1);
+
+        // Submits the transaction as `add_unconfirmed_transaction` does, rejecting the recently seen ones.
+        let mut submit = |queue: &mut TransactionsQueue<CurrentNetwork>, index: usize, fee: u64| {
+            if seen_transactions.put(ids[index], ()).is_some() {
+                return Err(AdmissionError::Duplicate(ids[index].to_string()));
+            }
+            queue.admit(&mut seen_transactions, transactions[index].clone(), FeeRate::new(fee, 1), None, 1)
+        };
+
+        assert_eq!(submit(&mut queue, 0, 2), Ok(None));
+        // A transaction with a lower fee per byte is rejected, and may be resubmitted.
+        assert_eq!(submit(&mut queue, 1, 1), Err(AdmissionError::QueueFull(ids[1].to_string())));
+        assert_eq!(submit(&mut queue, 1, 1), Err(AdmissionError::QueueFull(ids[1].to_string())));
+        // A transaction with a higher fee per byte evicts the queued one.
+        assert_eq!(submit(&mut queue, 2, 3), Ok(Some(ids[0])));
+        // The queued transaction is still rejected as a duplicate.
+        assert_eq!(submit(&mut queue, 2, 3), Err(AdmissionError::Duplicate(ids[2].to_string())));
+
+        // Once the queue is drained, the evicted transaction is admitted again.
+        assert_eq!(std::iter::from_fn(|| queue.pop(false)).count(), 1);
+        assert_eq!(submit(&mut queue, 0, 2), Ok(None));
+        assert!(queue.executions.contains(&ids[0]));
+    }
+
+    #[test]
+    fn test_private_fee_is_not_capped() {
+        let rng = &mut TestRng::default();
+        let transactions = sample_executions(rng);
+
+        // The fee payer is only known for a public fee.
+        assert!(fee_payer(&sample_execution_transaction_with_fee(true, rng)).is_none());
+        assert!(fee_payer(&sample_fee_private_transaction(rng)).is_none());
+        assert!(fee_payer(&sample_execution_transaction_with_fee(false, rng)).is_some());
+        assert!(fee_payer(&sample_fee_public_transaction(rng)).is_some());
+
+        // The transactions without a known fee payer are not capped.
+        let mut queue = sample_queue(transactions.len());
+        for transaction in &transactions {
+            assert_eq!(queue.push(transaction.clone(), FeeRate::new(1, 1), None, 1), Ok(None));
+        }
+        assert_eq!(queue.executions.len(), transactions.len());
+        assert!(queue.payers.is_empty() && queue.pending_per_payer.is_empty());
+    }
+}
@@ -62,6 +62,11 @@ pub fn initialize_metrics(ip: Option<SocketAddr>) {
     }
 }
 
+/// Sets the gauge with the given name and label to the given value.
+pub fn gauge_label(name: &'static str, label_key: &'static str, label_value: String, value: f64) {
+    ::metrics::gauge!(name, label_key => label_value).set(value);
+}
+
 pub fn update_block_metrics<N: Network>(block: &Block<N>) {
     use snarkvm::ledger::ConfirmedTransaction;
 
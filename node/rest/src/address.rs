@@ -0,0 +1,507 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use snarkvm::{
+    console::program::{Argument, Future},
+    prelude::{
+        Address,
+        FromBytes,
+        Identifier,
+        Literal,
+        Plaintext,
+        ToBytes,
+        Value,
+        block::{Block, Input, Output},
+    },
+};
+
+use aleo_std::StorageMode;
+use anyhow::bail;
+use indexmap::{IndexMap, IndexSet};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{collections::HashMap, marker::PhantomData};
+
+/// The number of transitions returned per page when the caller does not specify a limit.
+const DEFAULT_ADDRESS_PAGE_SIZE: usize = 50;
+/// The maximum number of transitions returned per page.
+const MAX_ADDRESS_PAGE_SIZE: usize = 500;
+
+/// The prefix of the keys of the transitions, followed by the address, the height and the transition ID.
+const TRANSITION_PREFIX: u8 = 0;
+/// The prefix of the keys of the balances, followed by the address and the height.
+const BALANCE_PREFIX: u8 = 1;
+/// The prefix of the keys of the addresses whose balance is recorded, followed by the key ID of their account.
+const ACCOUNT_PREFIX: u8 = 2;
+
+/// The query object for `get_address_transitions`.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct AddressTransitionsQuery {
+    /// The cursor returned by the previous page, if any.
+    cursor: Option<String>,
+    /// The maximum number of transitions to return.
+    limit: Option<usize>,
+}
+
+impl AddressTransitionsQuery {
+    /// The query parameters, as documented in the OpenAPI document.
+    pub(crate) const PARAMS: &'static [QueryParam] = &[
+        QueryParam::new("cursor", "string", false, "The cursor returned by the previous page"),
+        QueryParam::new("limit", "integer", false, "The maximum number of transitions to return"),
+    ];
+}
+
+/// A transition in which an address appears as a public input or output.
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AddressActivity<N: Network> {
+    /// The height of the block containing the transition.
+    height: u32,
+    /// The ID of the transaction containing the transition.
+    transaction_id: N::TransactionID,
+    /// The ID of the transition.
+    transition_id: N::TransitionID,
+    /// The program of the transition.
+    program_id: ProgramID<N>,
+    /// The function of the transition.
+    function_name: Identifier<N>,
+}
+
+/// The index from addresses to the transitions in which they appear as public inputs or outputs.
+/// The index is persisted next to the ledger, and catches up with the ledger from where it left off.
+/// The transitions of an address are ordered by height, then by transition ID, which is the cursor of a page.
+///
+/// The balance of an address is recorded by each block writing to its account, if the address appears in the block,
+/// or its balance was recorded before, as the finalize operations only commit to the accounts. The balances of the
+/// addresses rewarded by the ratifications of a block are recorded as well, as the ratifications are not in the
+/// finalize operations. Note: The block rewards are bonded, so they leave the accounts of the stakers unchanged.
+///
+/// The blocks are indexed as they are committed, off the commit path, so the balances a block wrote are read from the
+/// finalize store as of the block only if it is still the latest block once they are read. Otherwise, a balance is
+/// known only if no later block overwrote it, as the finalize operations only commit to the balances.
+pub(crate) struct AddressIndex<N: Network> {
+    /// The persisted index.
+    store: IndexStore,
+    /// PhantomData.
+    _phantom: PhantomData<N>,
+}
+
+impl<N: Network> AddressIndex<N> {
+    /// Opens the address index of the ledger with the given storage mode.
+    pub(crate) fn open(storage_mode: StorageMode) -> Result<Self> {
+        let store = IndexStore::open(&index_store_path(ADDRESS_INDEX_NAME, N::ID, storage_mode))?;
+        Ok(Self { store, _phantom: PhantomData })
+    }
+
+    /// Indexes the blocks committed since the last call, starting from the genesis block.
+    fn index_new_blocks<C: ConsensusStorage<N>>(&self, ledger: &Ledger<N, C>) -> Result<()> {
+        loop {
+            let height = self.store.next_height()?;
+            if height > ledger.latest_height() {
+                return Ok(());
+            }
+            self.index_block(ledger, &ledger.get_block(height)?)?;
+        }
+    }
+
+    /// Indexes the transitions of the given block, along with the balances it wrote.
+    fn index_block<C: ConsensusStorage<N>>(&self, ledger: &Ledger<N, C>, block: &Block<N>) -> Result<()> {
+        let height = block.height();
+
+        // Collect the addresses appearing in each transition.
+        let mut entries = Vec::new();
+        let mut touched = IndexSet::new();
+        for confirmed in block.transactions().iter() {
+            let transaction_id = confirmed.to_unconfirmed_transaction_id()?;
+            for transition in confirmed.transaction().transitions() {
+                let mut addresses = IndexSet::new();
+                for input in transition.inputs() {
+                    if let Input::Public(_, Some(plaintext)) = input {
+                        collect_plaintext_addresses(plaintext, &mut addresses);
+                    }
+                }
+                for output in transition.outputs() {
+                    match output {
+                        Output::Public(_, Some(plaintext)) => collect_plaintext_addresses(plaintext, &mut addresses),
+                        Output::Future(_, Some(future)) => collect_future_addresses(future, &mut addresses),
+                        _ => {}
+                    }
+                }
+                let mut value = transaction_id.to_bytes_le()?;
+                transition.program_id().write_le(&mut value)?;
+                transition.function_name().write_le(&mut value)?;
+                for address in addresses {
+                    entries.push((transition_key(&address, height, transition.id())?, value.clone()));
+                    touched.insert(address);
+                }
+            }
+        }
+
+        // Collect the writes to the `credits.aleo/account` mapping, keeping the last write to each entry.
+        let (program_id, mapping_name) = account_mapping::<N>()?;
+        let account_id = to_mapping_id(&program_id, &mapping_name)?;
+        let writes = mapping_writes(block)
+            .into_iter()
+            .filter(|write| write.mapping_id == account_id)
+            .map(|write| (write.key_id, write.value_id))
+            .collect::<HashMap<_, _>>();
+
+        // Find the addresses whose account the block wrote to, among the addresses appearing in the block,
+        // and the addresses whose balance was recorded before.
+        let mut accounts = IndexMap::new();
+        for address in touched {
+            let key_id = to_key_id(&program_id, &mapping_name, &Plaintext::from(Literal::Address(address)))?;
+            if writes.contains_key(&key_id) {
+                accounts.insert(key_id, address);
+            }
+        }
+        for key_id in writes.keys() {
+            if !accounts.contains_key(key_id) {
+                if let Some(address) = self.store.get(&account_key(key_id)?)? {
+                    accounts.insert(*key_id, Address::from_bytes_le(&address)?);
+                }
+            }
+        }
+
+        // Read the current value of each account the block wrote to, and of each address rewarded by the ratifications.
+        let mut values = IndexMap::new();
+        for address in accounts.values().copied().chain(ratified_addresses(block)) {
+            let key = Plaintext::from(Literal::Address(address));
+            values.insert(address, ledger.vm().finalize_store().get_value_confirmed(program_id, mapping_name, &key)?);
+        }
+        // Check whether the values were read as of the block.
+        // Note: A block is inserted along with its finalize operations, so no later block wrote to them if none exists.
+        let is_latest = !ledger.vm().block_store().contains_block_height(height.saturating_add(1))?;
+
+        // Record the balance of each address whose account the block wrote to.
+        let mut balances = IndexMap::new();
+        for (key_id, address) in &accounts {
+            let balance = match (writes[key_id], &values[address]) {
+                (None, _) => Some(0),
+                (Some(_), Some(value)) if is_latest => Some(to_balance(address, value.clone())?),
+                // Note: Otherwise, the balance is kept only if it is the one the block wrote.
+                (Some(value_id), Some(value)) if to_value_id(key_id, value)? == value_id => {
+                    Some(to_balance(address, value.clone())?)
+                }
+                _ => None,
+            };
+            balances.insert(*address, (*key_id, balance));
+        }
+        // Record the balance of each address rewarded by the ratifications.
+        for address in ratified_addresses(block) {
+            // Note: The genesis ratifications run before the transactions, and the rewards after them.
+            if height == 0 && balances.contains_key(&address) {
+                continue;
+            }
+            let key_id = to_key_id(&program_id, &mapping_name, &Plaintext::from(Literal::Address(address)))?;
+            let balance = match (is_latest, &values[&address]) {
+                (true, Some(value)) => Some(to_balance(&address, value.clone())?),
+                // Note: An address without an account, such as a validator bonding its whole balance, is skipped.
+                (true, None) if !accounts.contains_key(&key_id) => continue,
+                (true, None) => Some(0),
+                // Note: The ratifications don't commit to the balances, so the balance is unknown.
+                (false, _) => None,
+            };
+            balances.insert(address, (key_id, balance));
+        }
+
+        // Record the balances, along with the addresses, so that the next blocks writing to their accounts are found.
+        for (address, (key_id, balance)) in balances {
+            // Note: An unknown balance is stored as an empty value.
+            let balance = balance.map(|balance| balance.to_le_bytes().to_vec()).unwrap_or_default();
+            entries.push((balance_key(&address, height)?, balance));
+            entries.push((account_key(&key_id)?, address.to_bytes_le()?));
+        }
+
+        // Store the entries of the block.
+        self.store.insert_block(height, entries)
+    }
+
+    /// Returns the page of transitions of the given address following the cursor, and the cursor of the next page.
+    #[allow(clippy::type_complexity)]
+    fn transitions(
+        &self,
+        address: &Address<N>,
+        cursor: Option<(u32, N::TransitionID)>,
+        limit: usize,
+    ) -> Result<(Vec<AddressActivity<N>>, Option<(u32, N::TransitionID)>)> {
+        let prefix = address_prefix(TRANSITION_PREFIX, address)?;
+        let from = match cursor {
+            Some((height, transition_id)) => transition_key(address, height, &transition_id)?,
+            None => prefix.clone(),
+        };
+
+        // Read one transition past the page, to tell whether there is a next page.
+        let mut transitions = Vec::with_capacity(limit + 1);
+        for entry in self.store.iter_from(&prefix, &from) {
+            let (key, value) = entry?;
+            // Skip the last transition of the previous page.
+            if *key == *from && cursor.is_some() {
+                continue;
+            }
+            let (height, transition_id) = key[prefix.len()..].split_at(4);
+            let height = u32::from_be_bytes(height.try_into()?);
+            let transition_id = N::TransitionID::from_bytes_le(transition_id)?;
+            let mut value = &value[..];
+            let transaction_id = N::TransactionID::read_le(&mut value)?;
+            let program_id = ProgramID::read_le(&mut value)?;
+            let function_name = Identifier::read_le(&mut value)?;
+            transitions.push(AddressActivity { height, transaction_id, transition_id, program_id, function_name });
+            if transitions.len() > limit {
+                break;
+            }
+        }
+
+        let next = match transitions.len() > limit {
+            true => transitions.get(limit - 1).map(|activity| (activity.height, activity.transition_id)),
+            false => None,
+        };
+        transitions.truncate(limit);
+        Ok((transitions, next))
+    }
+
+    /// Returns the balance of the given address after each block in the given range that wrote to it.
+    fn balances(&self, address: &Address<N>, start: u32, end: u32) -> Result<Vec<(u32, Option<u64>)>> {
+        let prefix = address_prefix(BALANCE_PREFIX, address)?;
+        let mut balances = Vec::new();
+        for entry in self.store.iter_from(&prefix, &balance_key(address, start)?) {
+            let (key, value) = entry?;
+            let height = u32::from_be_bytes(key[prefix.len()..].try_into()?);
+            if height > end {
+                break;
+            }
+            let balance = match value.is_empty() {
+                true => None,
+                false => Some(u64::from_le_bytes(value[..].try_into()?)),
+            };
+            balances.push((height, balance));
+        }
+        Ok(balances)
+    }
+}
+
+/// Returns the prefix of the keys of the given kind for the given address.
+fn address_prefix<N: Network>(kind: u8, address: &Address<N>) -> Result<Vec<u8>> {
+    let mut key = vec![kind];
+    address.write_le(&mut key)?;
+    Ok(key)
+}
+
+/// Returns the key of the given transition of the given address.
+/// Note: The height is big-endian, so that the keys are ordered by height.
+fn transition_key<N: Network>(address: &Address<N>, height: u32, transition_id: &N::TransitionID) -> Result<Vec<u8>> {
+    let mut key = address_prefix(TRANSITION_PREFIX, address)?;
+    key.extend_from_slice(&height.to_be_bytes());
+    transition_id.write_le(&mut key)?;
+    Ok(key)
+}
+
+/// Returns the key holding the address whose account has the given key ID.
+fn account_key<N: Network>(key_id: &Field<N>) -> Result<Vec<u8>> {
+    let mut key = vec![ACCOUNT_PREFIX];
+    key_id.write_le(&mut key)?;
+    Ok(key)
+}
+
+/// Returns the key of the balance of the given address at the given height.
+fn balance_key<N: Network>(address: &Address<N>, height: u32) -> Result<Vec<u8>> {
+    let mut key = address_prefix(BALANCE_PREFIX, address)?;
+    key.extend_from_slice(&height.to_be_bytes());
+    Ok(key)
+}
+
+/// Returns the cursor of the given transition.
+fn encode_cursor<N: Network>((height, transition_id): (u32, N::TransitionID)) -> String {
+    format!("{height}-{transition_id}")
+}
+
+/// Returns the transition encoded in the given cursor.
+fn decode_cursor<N: Network>(cursor: &str) -> Result<(u32, N::TransitionID), RestError> {
+    let invalid = || RestError(format!("Invalid cursor '{cursor}'"));
+    let (height, transition_id) = cursor.split_once('-').ok_or_else(invalid)?;
+    Ok((height.parse().map_err(|_| invalid())?, transition_id.parse().map_err(|_| invalid())?))
+}
+
+/// Appends the addresses contained in the given plaintext.
+fn collect_plaintext_addresses<N: Network>(plaintext: &Plaintext<N>, addresses: &mut IndexSet<Address<N>>) {
+    match plaintext {
+        Plaintext::Literal(Literal::Address(address), _) => {
+            addresses.insert(*address);
+        }
+        Plaintext::Literal(..) => {}
+        Plaintext::Struct(members, _) => {
+            for member in members.values() {
+                collect_plaintext_addresses(member, addresses);
+            }
+        }
+        Plaintext::Array(elements, _) => {
+            for element in elements {
+                collect_plaintext_addresses(element, addresses);
+            }
+        }
+    }
+}
+
+/// Appends the addresses contained in the arguments of the given future.
+fn collect_future_addresses<N: Network>(future: &Future<N>, addresses: &mut IndexSet<Address<N>>) {
+    for argument in future.arguments() {
+        match argument {
+            Argument::Plaintext(plaintext) => collect_plaintext_addresses(plaintext, addresses),
+            Argument::Future(future) => collect_future_addresses(future, addresses),
+        }
+    }
+}
+
+/// Returns the balance held in the given `credits.aleo/account` value of the given address.
+fn to_balance<N: Network>(address: &Address<N>, value: Value<N>) -> Result<u64> {
+    match value {
+        Value::Plaintext(Plaintext::Literal(Literal::U64(balance), _)) => Ok(*balance),
+        value => bail!("Unexpected balance '{value}' for '{address}'"),
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    /// Keeps the address index up to date, indexing the blocks as they are committed.
+    pub(crate) async fn maintain_address_index(self, index: Arc<AddressIndex<N>>) {
+        let mut block_height = self.block_height.clone();
+        loop {
+            // Index the blocks committed since the last iteration, starting from the genesis block.
+            let (ledger, index_) = (self.ledger.clone(), index.clone());
+            match tokio::task::spawn_blocking(move || index_.index_new_blocks(&ledger)).await {
+                Ok(Ok(())) => {}
+                Ok(Err(error)) => warn!("Failed to update the address index - {error}"),
+                Err(error) => warn!("Failed to update the address index - {error}"),
+            }
+            // Wait for the next block. If the sender was dropped, no more blocks will be committed.
+            if block_height.changed().await.is_err() {
+                return;
+            }
+        }
+    }
+
+    // GET /<network>/address/{address}/transitions?cursor={cursor}&limit={limit}
+    pub(crate) async fn get_address_transitions(
+        State(rest): State<Self>,
+        Path(address): Path<Address<N>>,
+        Query(query): Query<AddressTransitionsQuery>,
+    ) -> Result<ErasedJson, RestError> {
+        let Some(index) = &rest.address_index else {
+            return Err(RestError("The address index is not enabled on this node".to_string()));
+        };
+        let limit = query.limit.unwrap_or(DEFAULT_ADDRESS_PAGE_SIZE);
+        // Ensure the page size is bounded.
+        if limit == 0 || limit > MAX_ADDRESS_PAGE_SIZE {
+            return Err(RestError(format!(
+                "The limit must be between 1 and {MAX_ADDRESS_PAGE_SIZE} (requested {limit})"
+            )));
+        }
+
+        // Decode the cursor into the last transition of the previous page.
+        let cursor = match &query.cursor {
+            Some(cursor) => Some(decode_cursor::<N>(cursor)?),
+            None => None,
+        };
+
+        let index = index.clone();
+        let get_page = move || -> Result<_> {
+            let (transitions, next) = index.transitions(&address, cursor, limit)?;
+            // Report the balance after each block touching the address on this page.
+            let balances = match (transitions.first(), transitions.last()) {
+                (Some(first), Some(last)) => index.balances(&address, first.height, last.height)?,
+                _ => Vec::new(),
+            };
+            Ok((transitions, next, balances, index.store.next_height()?))
+        };
+        let (transitions, next, balances, next_height) = match tokio::task::spawn_blocking(get_page).await {
+            Ok(Ok(page)) => page,
+            Ok(Err(err)) => return Err(RestError(format!("Unable to read the address index - {err}"))),
+            Err(err) => return Err(RestError(format!("Unable to read the address index - {err}"))),
+        };
+        let balances = balances
+            .into_iter()
+            .map(|(height, balance)| json!({ "height": height, "balance": balance }))
+            .collect::<Vec<_>>();
+
+        Ok(ErasedJson::pretty(json!({
+            "address": address,
+            "transitions": transitions,
+            "balances": balances,
+            "cursor": next.map(encode_cursor::<N>),
+            "indexedHeight": next_height.checked_sub(1),
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::{
+        ledger::store::{ConsensusStore, helpers::memory::ConsensusMemory},
+        prelude::{MainnetV0, PrivateKey, U64, VM},
+        utilities::TestRng,
+    };
+    use std::str::FromStr;
+
+    type CurrentNetwork = MainnetV0;
+    type CurrentLedger = Ledger<CurrentNetwork, ConsensusMemory<CurrentNetwork>>;
+
+    #[test]
+    fn test_balances_of_consecutive_blocks() {
+        let rng = &mut TestRng::default();
+
+        // Initialize a ledger, whose genesis block gives a balance to the given private key.
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let store = ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(None).unwrap();
+        let genesis = VM::from(store).unwrap().genesis_beacon(&private_key, rng).unwrap();
+        let ledger = CurrentLedger::load(genesis, StorageMode::Production).unwrap();
+
+        // Open an index, and index the genesis block.
+        let directory = tempfile::tempdir().unwrap();
+        let index = AddressIndex::<CurrentNetwork>::open(StorageMode::Custom(directory.path().join("a"))).unwrap();
+        index.index_new_blocks(&ledger).unwrap();
+
+        // Ensure the balance given by the genesis ratifications is recorded.
+        let beacon = Address::try_from(&private_key).unwrap();
+        assert_eq!(index.balances(&beacon, 0, 0).unwrap().len(), 1);
+
+        // Transfer to the same address in two consecutive blocks, indexing each block as it is committed.
+        let address = Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+        for amount in [1_000, 2_000] {
+            let inputs = [Value::from(Literal::Address(address)), Value::from(Literal::U64(U64::new(amount)))];
+            let locator =
+                (ProgramID::from_str("credits.aleo").unwrap(), Identifier::from_str("transfer_public").unwrap());
+            let transaction =
+                ledger.vm().execute(&private_key, locator, inputs.into_iter(), None, 0, None, rng).unwrap();
+            let block = ledger
+                .prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![transaction], rng)
+                .unwrap();
+            ledger.advance_to_next_block(&block).unwrap();
+            index.index_new_blocks(&ledger).unwrap();
+        }
+
+        // Ensure both balances are known, even though the second block overwrote the first one.
+        assert_eq!(index.balances(&address, 0, u32::MAX).unwrap(), vec![(1, Some(1_000)), (2, Some(3_000))]);
+        // Ensure both transitions are indexed.
+        let (transitions, next) = index.transitions(&address, None, DEFAULT_ADDRESS_PAGE_SIZE).unwrap();
+        assert_eq!(transitions.iter().map(|activity| activity.height).collect::<Vec<_>>(), vec![1, 2]);
+        assert!(next.is_none());
+
+        // Ensure an index catching up with the ledger only knows the balance that was not overwritten.
+        let index = AddressIndex::<CurrentNetwork>::open(StorageMode::Custom(directory.path().join("b"))).unwrap();
+        index.index_new_blocks(&ledger).unwrap();
+        assert_eq!(index.balances(&address, 0, u32::MAX).unwrap(), vec![(1, None), (2, Some(3_000))]);
+    }
+}
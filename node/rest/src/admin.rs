@@ -0,0 +1,136 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use serde::Deserialize;
+use serde_json::json;
+use std::{net::IpAddr, sync::atomic::Ordering};
+
+/// The request body for the admin routes acting on a peer.
+#[derive(Deserialize)]
+pub(crate) struct PeerRequest {
+    /// The listening address of the peer.
+    ip: SocketAddr,
+}
+
+/// The request body for the admin route revoking a JWT.
+#[derive(Deserialize)]
+pub(crate) struct RevokeRequest {
+    /// The ID of the token, found in its `jti` claim.
+    id: String,
+}
+
+/// The request body for the admin routes acting on an IP ban.
+#[derive(Deserialize)]
+pub(crate) struct BanRequest {
+    /// The banned IP address.
+    ip: IpAddr,
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // POST /<network>/admin/peers/connect
+    pub(crate) async fn admin_connect_peer(
+        State(rest): State<Self>,
+        Json(request): Json<PeerRequest>,
+    ) -> Result<ErasedJson, RestError> {
+        // Note: `connect` returns `None` if the peer is connected, connecting, restricted, or invalid.
+        let Some(handle) = rest.routing.router().connect(request.ip) else {
+            return Err(RestError(format!("Unable to connect to '{}'", request.ip)));
+        };
+        match handle.await {
+            Ok(connected) => Ok(ErasedJson::pretty(json!({ "ip": request.ip, "connected": connected }))),
+            Err(err) => Err(RestError(format!("Unable to connect to '{}' - {err}", request.ip))),
+        }
+    }
+
+    // POST /<network>/admin/peers/disconnect
+    pub(crate) async fn admin_disconnect_peer(
+        State(rest): State<Self>,
+        Json(request): Json<PeerRequest>,
+    ) -> Result<ErasedJson, RestError> {
+        match rest.routing.router().disconnect(request.ip).await {
+            Ok(disconnected) => Ok(ErasedJson::pretty(json!({ "ip": request.ip, "disconnected": disconnected }))),
+            Err(err) => Err(RestError(format!("Unable to disconnect from '{}' - {err}", request.ip))),
+        }
+    }
+
+    // GET /<network>/admin/peers/trusted
+    pub(crate) async fn admin_get_trusted_peers(State(rest): State<Self>) -> ErasedJson {
+        ErasedJson::pretty(rest.routing.router().trusted_peers())
+    }
+
+    // POST /<network>/admin/peers/trusted/add
+    pub(crate) async fn admin_add_trusted_peer(
+        State(rest): State<Self>,
+        Json(request): Json<PeerRequest>,
+    ) -> Result<ErasedJson, RestError> {
+        // Ensure the peer is not the node itself or an invalid address.
+        if !rest.routing.router().is_valid_peer_ip(&request.ip) {
+            return Err(RestError(format!("Unable to trust '{}' (invalid peer address)", request.ip)));
+        }
+        // Note: The heartbeat connects to any disconnected trusted peers.
+        let inserted = rest.routing.router().insert_trusted_peer(request.ip);
+        Ok(ErasedJson::pretty(json!({ "ip": request.ip, "inserted": inserted })))
+    }
+
+    // POST /<network>/admin/peers/trusted/remove
+    pub(crate) async fn admin_remove_trusted_peer(
+        State(rest): State<Self>,
+        Json(request): Json<PeerRequest>,
+    ) -> ErasedJson {
+        // Note: The peer stays connected until it is disconnected explicitly or rotated out.
+        let removed = rest.routing.router().remove_trusted_peer(request.ip);
+        ErasedJson::pretty(json!({ "ip": request.ip, "removed": removed }))
+    }
+
+    // GET /<network>/admin/bans
+    pub(crate) async fn admin_get_banned_ips(State(rest): State<Self>) -> ErasedJson {
+        let banned_peers = rest.routing.tcp().banned_peers();
+        let bans = banned_peers
+            .get_banned_ips()
+            .into_iter()
+            .filter_map(|ip| banned_peers.get_ban_config(ip).map(|ban| (ip, ban.banned_at().elapsed().as_secs())))
+            .map(|(ip, elapsed)| json!({ "ip": ip, "bannedForSecs": elapsed }))
+            .collect::<Vec<_>>();
+        ErasedJson::pretty(bans)
+    }
+
+    // POST /<network>/admin/bans/lift
+    pub(crate) async fn admin_lift_ban(State(rest): State<Self>, Json(request): Json<BanRequest>) -> ErasedJson {
+        let lifted = rest.routing.tcp().banned_peers().remove_ip_ban(&request.ip);
+        if lifted {
+            info!("Lifted the ban on '{}' through the admin API", request.ip);
+        }
+        ErasedJson::pretty(json!({ "ip": request.ip, "lifted": lifted }))
+    }
+
+    // POST /<network>/admin/tokens/revoke
+    pub(crate) async fn admin_revoke_token(
+        State(rest): State<Self>,
+        Json(request): Json<RevokeRequest>,
+    ) -> Result<ErasedJson, RestError> {
+        let revoked = rest.jwt.revoke(&request.id)?;
+        Ok(ErasedJson::pretty(json!({ "id": request.id, "revoked": revoked })))
+    }
+
+    // POST /<network>/admin/shutdown
+    pub(crate) async fn admin_shutdown(State(rest): State<Self>) -> ErasedJson {
+        warn!("Received a shutdown request through the admin API");
+        // Note: The node's signal handler observes the flag and performs the graceful shutdown.
+        rest.shutdown.store(true, Ordering::Release);
+        ErasedJson::pretty(json!({ "shuttingDown": true }))
+    }
+}
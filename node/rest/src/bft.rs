@@ -0,0 +1,123 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use serde_json::json;
+use std::collections::BTreeMap;
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    /// Returns the consensus module, or an error if this node does not run consensus.
+    fn consensus_or_err(&self) -> Result<&Consensus<N>, RestError> {
+        self.consensus.as_ref().ok_or_else(|| RestError("Route isn't available for this node type".to_string()))
+    }
+
+    // GET /<network>/bft/rounds
+    pub(crate) async fn get_bft_rounds(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        let bft = rest.consensus_or_err()?.bft();
+        Ok(ErasedJson::pretty(json!({
+            "currentRound": bft.storage().current_round(),
+            "gcRound": bft.storage().gc_round(),
+            "maxGcRounds": bft.storage().max_gc_rounds(),
+            "isSynced": bft.is_synced(),
+        })))
+    }
+
+    // GET /<network>/bft/dag
+    pub(crate) async fn get_bft_dag(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        let storage = rest.consensus_or_err()?.bft().storage();
+        // Order the rounds, listing the certificates of each round in storage.
+        let dag = storage
+            .rounds_iter()
+            .map(|(round, certificates)| {
+                let certificates = certificates
+                    .into_iter()
+                    .map(|(certificate_id, batch_id, author)| {
+                        json!({ "certificateId": certificate_id, "batchId": batch_id, "author": author })
+                    })
+                    .collect::<Vec<_>>();
+                (round, certificates)
+            })
+            .collect::<BTreeMap<_, _>>();
+        Ok(ErasedJson::pretty(dag))
+    }
+
+    // GET /<network>/bft/certificates/{round}
+    pub(crate) async fn get_bft_certificates_for_round(
+        State(rest): State<Self>,
+        headers: HeaderMap,
+        Path(round): Path<u64>,
+    ) -> Result<Response, RestError> {
+        let storage = rest.consensus_or_err()?.bft().storage();
+        negotiate_all(&headers, storage.get_certificates_for_round(round).into_iter().collect())
+    }
+
+    // GET /<network>/bft/certificate/{certificateID}
+    pub(crate) async fn get_bft_certificate(
+        State(rest): State<Self>,
+        headers: HeaderMap,
+        Path(certificate_id): Path<Field<N>>,
+    ) -> Result<Response, RestError> {
+        let storage = rest.consensus_or_err()?.bft().storage();
+        match storage.get_certificate(certificate_id) {
+            Some(certificate) => negotiate(&headers, certificate),
+            None => Err(RestError(format!("Missing certificate '{certificate_id}' in storage"))),
+        }
+    }
+
+    // GET /<network>/bft/leader
+    pub(crate) async fn get_bft_leader(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        let consensus = rest.consensus_or_err()?;
+        let latest_leader =
+            consensus.ledger().latest_leader().map(|(round, leader)| json!({ "round": round, "leader": leader }));
+        let leader_certificate =
+            consensus.bft().leader_certificate().read().as_ref().map(|certificate| certificate.id());
+        Ok(ErasedJson::pretty(json!({
+            "latestLeader": latest_leader,
+            "leader": consensus.bft().leader(),
+            "leaderCertificateId": leader_certificate,
+        })))
+    }
+
+    // GET /<network>/bft/committee/{round}
+    pub(crate) async fn get_bft_committee_lookback(
+        State(rest): State<Self>,
+        Path(round): Path<u64>,
+    ) -> Result<ErasedJson, RestError> {
+        let consensus = rest.consensus_or_err()?;
+        Ok(ErasedJson::pretty(consensus.ledger().get_committee_lookback_for_round(round)?))
+    }
+
+    // GET /<network>/bft/proposal
+    pub(crate) async fn get_bft_proposal(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        let consensus = rest.consensus_or_err()?;
+        let proposed_batch = consensus.bft().primary().proposed_batch().read();
+        let Some(proposal) = proposed_batch.as_ref() else {
+            return Ok(ErasedJson::pretty(None::<()>));
+        };
+        // Note: The committee lookback is typically served from the committee cache of the ledger service.
+        let committee = consensus.ledger().get_committee_lookback_for_round(proposal.round())?;
+        Ok(ErasedJson::pretty(json!({
+            "batchId": proposal.batch_id(),
+            "round": proposal.round(),
+            "timestamp": proposal.timestamp(),
+            "author": proposal.batch_header().author(),
+            "transmissionIds": proposal.transmissions().keys().collect::<Vec<_>>(),
+            "signers": proposal.signers(),
+            "nonsigners": proposal.nonsigners(&committee),
+            "isQuorumThresholdReached": proposal.is_quorum_threshold_reached(&committee),
+        })))
+    }
+}
@@ -0,0 +1,249 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use snarkos_node_consensus::MemoryPoolEvent;
+use snarkvm::ledger::ConfirmedTransaction;
+
+use anyhow::{anyhow, bail};
+use axum::{
+    http::HeaderMap,
+    response::sse::{Event, KeepAlive, Sse},
+};
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{collections::VecDeque, convert::Infallible, str::FromStr};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+/// The query object for `get_events`.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct EventsQuery {
+    /// The comma-separated topics to subscribe to (default: all available topics).
+    topics: Option<String>,
+    /// The block height to start streaming from (inclusive).
+    start: Option<u32>,
+}
+
+impl EventsQuery {
+    /// The query parameters, as documented in the OpenAPI document.
+    pub(crate) const PARAMS: &'static [QueryParam] = &[
+        QueryParam::new("topics", "string", false, "The comma-separated topics to subscribe to"),
+        QueryParam::new("start", "integer", false, "The block height to start streaming from (inclusive)"),
+    ];
+}
+
+/// The topics a client may subscribe to.
+#[derive(Copy, Clone, Default)]
+struct Topics {
+    /// The committed blocks.
+    blocks: bool,
+    /// The accepted, rejected, and aborted transactions of each committed block.
+    transactions: bool,
+    /// The insertions into and evictions from the inbound queues of the memory pool.
+    memory_pool: bool,
+}
+
+impl FromStr for Topics {
+    type Err = RestError;
+
+    fn from_str(topics: &str) -> Result<Self, Self::Err> {
+        let mut result = Self::default();
+        for topic in topics.split(',').map(str::trim) {
+            match topic {
+                "blocks" => result.blocks = true,
+                "transactions" => result.transactions = true,
+                "memoryPool" => result.memory_pool = true,
+                _ => return Err(RestError(format!("Unknown event topic '{topic}'"))),
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl Topics {
+    /// Returns `true` if the subscribed topics require streaming the committed blocks.
+    const fn requires_blocks(&self) -> bool {
+        self.blocks || self.transactions
+    }
+}
+
+/// The state of a single event stream.
+struct EventStream<N: Network, C: ConsensusStorage<N>, R: Routing<N>> {
+    /// The REST server.
+    rest: Rest<N, C, R>,
+    /// The subscribed topics.
+    topics: Topics,
+    /// The height of the next block to stream.
+    next_height: u32,
+    /// The receiver for the latest block height.
+    block_height: watch::Receiver<u32>,
+    /// The receiver for the memory pool events, if subscribed.
+    memory_pool: Option<broadcast::Receiver<MemoryPoolEvent<N>>>,
+    /// The events waiting to be sent.
+    pending: VecDeque<Event>,
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> EventStream<N, C, R> {
+    /// Returns the next event, waiting for one if necessary.
+    /// Returns `None` once the stream has ended.
+    async fn next_event(&mut self) -> Option<Event> {
+        loop {
+            // Send the pending events first.
+            if let Some(event) = self.pending.pop_front() {
+                return Some(event);
+            }
+            // Catch up on the blocks the client has not seen yet, if the topics require them.
+            if self.topics.requires_blocks() && self.next_height <= self.rest.ledger.latest_height() {
+                if let Err(error) = self.queue_block_events().await {
+                    warn!("Failed to stream the events for block {} - {error}", self.next_height);
+                    return None;
+                }
+                continue;
+            }
+            // Wait for the next block or memory pool event.
+            tokio::select! {
+                result = self.block_height.changed() => {
+                    // If the sender was dropped, no more blocks will be committed.
+                    if result.is_err() {
+                        return None;
+                    }
+                }
+                result = recv_memory_pool_event(&mut self.memory_pool) => match result {
+                    Ok(event) => self.queue_memory_pool_event(event),
+                    Err(RecvError::Lagged(num_skipped)) => {
+                        let comment = format!("skipped {num_skipped} memory pool events");
+                        self.pending.push_back(Event::default().comment(comment));
+                    }
+                    Err(RecvError::Closed) => self.memory_pool = None,
+                },
+            }
+        }
+    }
+
+    /// Queues the events for the next block.
+    ///
+    /// The last event of each block carries the block height as its ID, so that
+    /// a reconnecting client resumes right after the last block it fully received.
+    async fn queue_block_events(&mut self) -> Result<()> {
+        let height = self.next_height;
+        // Retrieve the block in a blocking task.
+        let ledger = self.rest.ledger.clone();
+        let block = match tokio::task::spawn_blocking(move || ledger.get_block(height)).await {
+            Ok(block) => block?,
+            Err(error) => bail!("{error}"),
+        };
+
+        if self.topics.transactions {
+            for confirmed in block.transactions().iter() {
+                let (status, kind) = match confirmed {
+                    ConfirmedTransaction::AcceptedDeploy(..) => ("accepted", "deploy"),
+                    ConfirmedTransaction::AcceptedExecute(..) => ("accepted", "execute"),
+                    ConfirmedTransaction::RejectedDeploy(..) => ("rejected", "deploy"),
+                    ConfirmedTransaction::RejectedExecute(..) => ("rejected", "execute"),
+                };
+                let data = json!({
+                    "height": height,
+                    "id": confirmed.to_unconfirmed_transaction_id()?,
+                    "status": status,
+                    "type": kind,
+                });
+                self.pending.push_back(Event::default().event("transaction").data(data.to_string()));
+            }
+            for transaction_id in block.aborted_transaction_ids() {
+                let data = json!({ "height": height, "id": transaction_id, "status": "aborted" });
+                self.pending.push_back(Event::default().event("transaction").data(data.to_string()));
+            }
+        }
+
+        let event = match self.topics.blocks {
+            true => Event::default().event("block").json_data(&block).map_err(|error| anyhow!("{error}"))?,
+            false => Event::default().comment(format!("block {height}")),
+        };
+        self.pending.push_back(event.id(height.to_string()));
+
+        self.next_height = height.saturating_add(1);
+        Ok(())
+    }
+
+    /// Queues the given memory pool event.
+    fn queue_memory_pool_event(&mut self, event: MemoryPoolEvent<N>) {
+        let data = match event {
+            MemoryPoolEvent::SolutionInserted(id) => json!({ "action": "inserted", "type": "solution", "id": id }),
+            MemoryPoolEvent::SolutionEvicted(id) => json!({ "action": "evicted", "type": "solution", "id": id }),
+            MemoryPoolEvent::TransactionInserted(id) => {
+                json!({ "action": "inserted", "type": "transaction", "id": id })
+            }
+            MemoryPoolEvent::TransactionEvicted(id) => json!({ "action": "evicted", "type": "transaction", "id": id }),
+        };
+        self.pending.push_back(Event::default().event("memoryPool").data(data.to_string()));
+    }
+}
+
+/// Receives the next memory pool event, or waits forever if there is no subscription.
+async fn recv_memory_pool_event<N: Network>(
+    receiver: &mut Option<broadcast::Receiver<MemoryPoolEvent<N>>>,
+) -> Result<MemoryPoolEvent<N>, RecvError> {
+    match receiver {
+        Some(receiver) => receiver.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // GET /<network>/events?topics={topics}&start={height}
+    pub(crate) async fn get_events(
+        State(rest): State<Self>,
+        headers: HeaderMap,
+        Query(query): Query<EventsQuery>,
+    ) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, RestError> {
+        // Parse the topics, defaulting to every topic available on this node.
+        let topics = match &query.topics {
+            Some(topics) => topics.parse::<Topics>()?,
+            None => Topics { blocks: true, transactions: true, memory_pool: rest.consensus.is_some() },
+        };
+        // Subscribe to the memory pool, if requested.
+        let memory_pool = match (topics.memory_pool, &rest.consensus) {
+            (true, Some(consensus)) => Some(consensus.subscribe_to_memory_pool()),
+            (true, None) => {
+                return Err(RestError("The 'memoryPool' topic isn't available for this node type".to_string()));
+            }
+            (false, _) => None,
+        };
+
+        // Determine the first block to stream. A reconnecting client resumes after the last block it received.
+        let last_event_id =
+            headers.get("last-event-id").and_then(|id| id.to_str().ok()).and_then(|id| id.parse::<u32>().ok());
+        let next_height = match (last_event_id, query.start) {
+            (Some(height), _) => height.saturating_add(1),
+            (None, Some(start)) => start,
+            (None, None) => rest.ledger.latest_height().saturating_add(1),
+        };
+
+        let stream = EventStream {
+            block_height: rest.block_height.clone(),
+            rest,
+            topics,
+            next_height,
+            memory_pool,
+            pending: Default::default(),
+        };
+        let stream = futures::stream::unfold(stream, |mut stream| async move {
+            stream.next_event().await.map(|event| (Ok::<_, Infallible>(event), stream))
+        });
+
+        Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+    }
+}
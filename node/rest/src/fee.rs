@@ -0,0 +1,204 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use snarkvm::prelude::{
+    block::{Deployment, Execution},
+    deployment_cost,
+    execution_cost_v1,
+    execution_cost_v2,
+};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// The number of blocks sampled when the caller does not specify one.
+const DEFAULT_FEE_ESTIMATE_BLOCKS: u32 = 10;
+/// The maximum number of blocks that can be sampled per call.
+const MAX_FEE_ESTIMATE_BLOCKS: u32 = 50;
+/// The upper bound on the factor by which the memory pool pressure scales the estimates.
+const MAX_PRESSURE_MULTIPLIER: f64 = 4.0;
+/// The percentiles of the sampled priority fees that are reported.
+const PERCENTILES: [u8; 5] = [10, 25, 50, 75, 90];
+
+/// The query object for `get_fee_estimate`.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct FeeEstimateQuery {
+    /// The number of most recent blocks to sample.
+    blocks: Option<u32>,
+}
+
+impl FeeEstimateQuery {
+    /// The query parameters, as documented in the OpenAPI document.
+    pub(crate) const PARAMS: &'static [QueryParam] =
+        &[QueryParam::new("blocks", "integer", false, "The number of most recent blocks to sample")];
+}
+
+/// The deployment or execution whose base fee is estimated by `post_fee_estimate`.
+#[derive(Deserialize)]
+#[serde(untagged, bound = "")]
+pub(crate) enum FeeSubject<N: Network> {
+    Deployment(Box<Deployment<N>>),
+    Execution(Box<Execution<N>>),
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // GET /<network>/fee/estimate?blocks={num_blocks}
+    pub(crate) async fn get_fee_estimate(
+        State(rest): State<Self>,
+        Query(query): Query<FeeEstimateQuery>,
+    ) -> Result<ErasedJson, RestError> {
+        let num_blocks = query.blocks.unwrap_or(DEFAULT_FEE_ESTIMATE_BLOCKS);
+
+        // Ensure the number of sampled blocks is bounded.
+        if num_blocks == 0 || num_blocks > MAX_FEE_ESTIMATE_BLOCKS {
+            return Err(RestError(format!(
+                "The number of sampled blocks must be between 1 and {MAX_FEE_ESTIMATE_BLOCKS} (requested {num_blocks})"
+            )));
+        }
+
+        // Determine the sampled block range, ending at the latest block.
+        let end_height = rest.ledger.latest_height().saturating_add(1);
+        let start_height = end_height.saturating_sub(num_blocks);
+
+        // Retrieve the number of transactions waiting in the memory pool.
+        let num_pending = rest.consensus.as_ref().map(|consensus| consensus.num_unconfirmed_transactions());
+
+        // Prepare a closure for the blocking work.
+        let estimate = move || -> Result<ErasedJson, RestError> {
+            // Collect the priority fees paid by every transaction in the sampled blocks.
+            let mut priority_fees = Vec::new();
+            for height in start_height..end_height {
+                let block = rest.ledger.get_block(height)?;
+                for confirmed in block.transactions().iter() {
+                    priority_fees.push(*confirmed.transaction().priority_fee_amount()?);
+                }
+            }
+            priority_fees.sort_unstable();
+
+            // Compare the memory pool against the average number of transactions per block.
+            let sampled_blocks = end_height.saturating_sub(start_height).max(1);
+            let per_block = (priority_fees.len() as f64 / sampled_blocks as f64).max(1.0);
+            let pressure = num_pending.map(|num_pending| num_pending as f64 / per_block);
+            let multiplier = pressure.unwrap_or(1.0).clamp(1.0, MAX_PRESSURE_MULTIPLIER);
+
+            // Compute the percentiles, scaled up when the memory pool holds more than a block's worth.
+            let mut percentiles = IndexMap::new();
+            let mut estimates = IndexMap::new();
+            for percentile in PERCENTILES {
+                let fee = percentile_of(&priority_fees, percentile);
+                percentiles.insert(format!("p{percentile}"), fee);
+                estimates.insert(format!("p{percentile}"), (fee as f64 * multiplier).ceil() as u64);
+            }
+
+            Ok(ErasedJson::pretty(json!({
+                "startHeight": start_height,
+                "endHeight": end_height,
+                "numTransactions": priority_fees.len(),
+                "memoryPoolTransactions": num_pending,
+                "pressure": pressure,
+                "percentiles": percentiles,
+                "priorityFee": estimates,
+            })))
+        };
+
+        match tokio::task::spawn_blocking(estimate).await {
+            Ok(json) => json,
+            Err(err) => Err(RestError(format!("Unable to estimate fees - {err}"))),
+        }
+    }
+
+    // POST /<network>/fee/estimate
+    pub(crate) async fn post_fee_estimate(
+        State(rest): State<Self>,
+        Json(subject): Json<FeeSubject<N>>,
+    ) -> Result<ErasedJson, RestError> {
+        // Prepare a closure for the blocking work.
+        let estimate = move || -> Result<ErasedJson, RestError> {
+            match subject {
+                FeeSubject::Deployment(deployment) => {
+                    let (base_fee, (storage_cost, synthesis_cost, namespace_cost)) = deployment_cost(&deployment)?;
+                    Ok(ErasedJson::pretty(json!({
+                        "type": "deploy",
+                        "baseFee": base_fee,
+                        "storageCost": storage_cost,
+                        "synthesisCost": synthesis_cost,
+                        "namespaceCost": namespace_cost,
+                    })))
+                }
+                FeeSubject::Execution(execution) => {
+                    // Note: The execution cost is priced as of the latest block, as the VM does when verifying the fee.
+                    let process = rest.ledger.vm().process();
+                    let (base_fee, (storage_cost, finalize_cost)) =
+                        match rest.ledger.latest_height() < N::CONSENSUS_V2_HEIGHT {
+                            true => execution_cost_v1(&process.read(), &execution)?,
+                            false => execution_cost_v2(&process.read(), &execution)?,
+                        };
+                    Ok(ErasedJson::pretty(json!({
+                        "type": "execute",
+                        "baseFee": base_fee,
+                        "storageCost": storage_cost,
+                        "finalizeCost": finalize_cost,
+                    })))
+                }
+            }
+        };
+
+        match tokio::task::spawn_blocking(estimate).await {
+            Ok(json) => json,
+            Err(err) => Err(RestError(format!("Unable to estimate the base fee - {err}"))),
+        }
+    }
+}
+
+/// Returns the nearest-rank percentile of the given sorted values, or zero if there are none.
+fn percentile_of(sorted: &[u64], percentile: u8) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = (percentile as usize * sorted.len()).div_ceil(100).max(1);
+    sorted[rank.min(sorted.len()) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile_of() {
+        // An empty sample has a percentile of zero.
+        assert_eq!(percentile_of(&[], 0), 0);
+        assert_eq!(percentile_of(&[], 50), 0);
+        assert_eq!(percentile_of(&[], 100), 0);
+
+        // A single value is every percentile.
+        for percentile in [0, 1, 50, 99, 100] {
+            assert_eq!(percentile_of(&[7], percentile), 7);
+        }
+
+        let sorted = (1..=10).collect::<Vec<u64>>();
+        // The 0th percentile is the smallest value, and the 100th percentile is the largest value.
+        assert_eq!(percentile_of(&sorted, 0), 1);
+        assert_eq!(percentile_of(&sorted, 100), 10);
+        // The other percentiles use the nearest rank.
+        assert_eq!(percentile_of(&sorted, 10), 1);
+        assert_eq!(percentile_of(&sorted, 11), 2);
+        assert_eq!(percentile_of(&sorted, 50), 5);
+        assert_eq!(percentile_of(&sorted, 51), 6);
+        assert_eq!(percentile_of(&sorted, 90), 9);
+        assert_eq!(percentile_of(&sorted, 91), 10);
+    }
+}
@@ -0,0 +1,118 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use snarkos_node_router::SYNC_LENIENCY;
+
+use axum::response::IntoResponse;
+use indexmap::IndexMap;
+use serde_json::{Value, json};
+use std::sync::atomic::Ordering;
+use time::OffsetDateTime;
+
+/// The minimum number of connected peers for the node to be ready.
+const MIN_READY_PEERS: usize = 1;
+/// The maximum number of seconds since the latest block for the node to be ready.
+const MAX_READY_BLOCK_AGE_SECS: i64 = 300;
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // GET /health/live
+    pub(crate) async fn get_health_live(State(rest): State<Self>) -> Response {
+        // The node is live as long as it serves requests and is not shutting down.
+        let shutting_down = rest.shutdown.load(Ordering::Acquire);
+        let status = match shutting_down {
+            true => StatusCode::SERVICE_UNAVAILABLE,
+            false => StatusCode::OK,
+        };
+        let failing = match shutting_down {
+            true => vec!["shutdown"],
+            false => vec![],
+        };
+        let body = json!({
+            "live": !shutting_down,
+            "height": rest.ledger.latest_height(),
+            "failing": failing,
+        });
+        (status, ErasedJson::pretty(body)).into_response()
+    }
+
+    // GET /health/ready
+    pub(crate) async fn get_health_ready(State(rest): State<Self>) -> Response {
+        let mut checks = IndexMap::new();
+
+        // Ensure the node is not shutting down.
+        let shutting_down = rest.shutdown.load(Ordering::Acquire);
+        checks.insert("shutdown", check(!shutting_down, json!({ "shuttingDown": shutting_down })));
+
+        // Ensure the node is synced up to the greatest peer height.
+        let is_block_synced = rest.routing.is_block_synced();
+        let num_blocks_behind = rest.routing.num_blocks_behind();
+        checks.insert(
+            "blockSync",
+            check(
+                is_block_synced && num_blocks_behind <= SYNC_LENIENCY,
+                json!({
+                    "isBlockSynced": is_block_synced,
+                    "blocksBehind": num_blocks_behind,
+                    "leniency": SYNC_LENIENCY,
+                }),
+            ),
+        );
+
+        // Ensure the node is connected to the network.
+        let num_peers = rest.routing.router().number_of_connected_peers();
+        checks.insert(
+            "peers",
+            check(num_peers >= MIN_READY_PEERS, json!({ "connected": num_peers, "minimum": MIN_READY_PEERS })),
+        );
+
+        // Ensure the BFT is synced, if this node is a validator.
+        if let Some(consensus) = &rest.consensus {
+            let is_synced = consensus.bft().is_synced();
+            checks.insert("bft", check(is_synced, json!({ "isSynced": is_synced })));
+        }
+
+        // Ensure the ledger has advanced recently.
+        let latest_block = rest.ledger.latest_block();
+        let block_age = OffsetDateTime::now_utc().unix_timestamp().saturating_sub(latest_block.timestamp());
+        checks.insert(
+            "latestBlock",
+            check(
+                block_age <= MAX_READY_BLOCK_AGE_SECS,
+                json!({
+                    "height": latest_block.height(),
+                    "ageSecs": block_age,
+                    "maximumAgeSecs": MAX_READY_BLOCK_AGE_SECS,
+                }),
+            ),
+        );
+
+        // Report the failing checks, if any.
+        let failing =
+            checks.iter().filter(|(_, check)| check["ok"] == false).map(|(name, _)| *name).collect::<Vec<_>>();
+        let status = match failing.is_empty() {
+            true => StatusCode::OK,
+            false => StatusCode::SERVICE_UNAVAILABLE,
+        };
+        let body = json!({ "ready": failing.is_empty(), "failing": failing, "checks": checks });
+        (status, ErasedJson::pretty(body)).into_response()
+    }
+}
+
+/// Returns the outcome of a health check, along with the details it was based on.
+fn check(ok: bool, mut details: Value) -> Value {
+    details["ok"] = json!(ok);
+    details
+}
@@ -16,32 +16,222 @@
 use snarkvm::prelude::*;
 
 use ::time::OffsetDateTime;
-use anyhow::{Result, anyhow};
+use anyhow::{Result, anyhow, bail, ensure};
 use axum::{
-    RequestPartsExt,
     body::Body,
-    http::{Request, StatusCode},
+    extract::State,
+    http::{HeaderMap, Request, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
 };
-use axum_extra::{
-    TypedHeader,
-    headers::authorization::{Authorization, Bearer},
+use axum_extra::headers::{
+    HeaderMapExt,
+    authorization::{Authorization, Bearer},
 };
 use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, encode};
-use once_cell::sync::OnceCell;
+use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    fmt,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+/// The time a jwt token is valid for, by default.
+pub const EXPIRATION: i64 = 24 * 60 * 60; // 1 day.
+/// The environment variable that may hold the JWT secret.
+pub const JWT_SECRET_ENV: &str = "SNARKOS_REST_JWT_SECRET";
+/// The minimum length of a JWT secret, in bytes.
+pub const MIN_JWT_SECRET_LENGTH: usize = 16;
+/// The interval at which the JWT revocation file is reloaded, if it changed.
+const REVOCATION_RELOAD_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The JWT settings of the REST server.
+pub struct JwtConfig {
+    /// The secret used to sign and verify tokens.
+    secret: Vec<u8>,
+    /// The path to the file listing the revoked token IDs, one per line.
+    revocation_file: Option<PathBuf>,
+    /// The revoked token IDs, along with the modification time of the revocation file they were read at.
+    revoked: RwLock<(Option<SystemTime>, HashSet<String>)>,
+    /// If `true`, the broadcast routes require a token with the `broadcast` scope.
+    protect_broadcast: bool,
+}
+
+impl Default for JwtConfig {
+    /// Returns the settings signing the tokens with a random secret, so that they do not survive a restart.
+    fn default() -> Self {
+        Self {
+            secret: random_jwt_secret(),
+            revocation_file: None,
+            revoked: Default::default(),
+            protect_broadcast: false,
+        }
+    }
+}
+
+impl fmt::Debug for JwtConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Note: The secret is omitted, so that it is never logged.
+        f.debug_struct("JwtConfig")
+            .field("revocation_file", &self.revocation_file)
+            .field("protect_broadcast", &self.protect_broadcast)
+            .finish_non_exhaustive()
+    }
+}
+
+impl JwtConfig {
+    /// Initializes the JWT settings, reading the secret from the given file or else from the
+    /// `SNARKOS_REST_JWT_SECRET` environment variable. If neither is set, a random secret is generated,
+    /// and the tokens will not survive a restart.
+    pub fn new(secret_file: Option<&Path>, revocation_file: Option<PathBuf>, protect_broadcast: bool) -> Result<Self> {
+        let secret = load_jwt_secret(secret_file)?.unwrap_or_else(random_jwt_secret);
+        let revoked = match &revocation_file {
+            Some(path) if path.exists() => (modified_time(path), read_revocation_file(path)?),
+            _ => (None, HashSet::new()),
+        };
+        Ok(Self { secret, revocation_file, revoked: RwLock::new(revoked), protect_broadcast })
+    }
+
+    /// Returns `true` if the broadcast routes require a token with the `broadcast` scope.
+    pub fn is_broadcast_protected(&self) -> bool {
+        self.protect_broadcast
+    }
+
+    /// Returns the JSON web token string for the given claims, signed with the secret.
+    pub fn sign(&self, claims: &Claims) -> Result<String> {
+        claims.to_jwt_string_with_secret(&self.secret)
+    }
+
+    /// Returns `true` if the given token ID was revoked.
+    fn is_revoked(&self, token_id: &str) -> bool {
+        self.revoked.read().1.contains(token_id)
+    }
+
+    /// Reloads the revocation file, if it changed since it was last read.
+    fn reload_revocations(&self) {
+        if let Some(path) = &self.revocation_file {
+            let modified = modified_time(path);
+            if modified != self.revoked.read().0 {
+                match read_revocation_file(path) {
+                    Ok(revoked) => *self.revoked.write() = (modified, revoked),
+                    Err(error) => warn!("Unable to reload the JWT revocation file - {error}"),
+                }
+            }
+        }
+    }
+
+    /// Revokes the given token ID, returning `false` if it was already revoked.
+    pub(crate) fn revoke(&self, token_id: &str) -> Result<bool> {
+        ensure!(!token_id.is_empty() && !token_id.contains(char::is_whitespace), "Invalid token ID '{token_id}'");
+        if self.is_revoked(token_id) {
+            return Ok(false);
+        }
+        // Persist the revocation, so that it survives a restart.
+        if let Some(path) = &self.revocation_file {
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            writeln!(file, "{token_id}")?;
+        }
+        self.revoked.write().1.insert(token_id.to_string());
+        Ok(true)
+    }
+}
+
+/// Returns the modification time of the given file, if it exists.
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+/// Returns the token IDs listed in the given revocation file, skipping blank lines and `#` comments.
+fn read_revocation_file(path: &Path) -> Result<HashSet<String>> {
+    Ok(fs::read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(ToString::to_string)
+        .collect())
+}
+
+/// Returns the configured JWT secret, read from the given file or else from the `SNARKOS_REST_JWT_SECRET`
+/// environment variable. Returns `None` if neither is set.
+pub fn load_jwt_secret(secret_file: Option<&Path>) -> Result<Option<Vec<u8>>> {
+    let secret = match secret_file {
+        Some(path) => fs::read_to_string(path).map_err(|e| anyhow!("Unable to read the JWT secret file - {e}"))?,
+        None => match std::env::var(JWT_SECRET_ENV) {
+            Ok(secret) => secret,
+            Err(_) => return Ok(None),
+        },
+    };
+    let secret = secret.trim().as_bytes().to_vec();
+    ensure!(secret.len() >= MIN_JWT_SECRET_LENGTH, "The JWT secret must be at least {MIN_JWT_SECRET_LENGTH} bytes");
+    Ok(Some(secret))
+}
+
+/// Periodically reloads the JWT revocation file, so that the tokens listed in it by hand are rejected.
+pub(crate) async fn reload_jwt_revocations(config: Arc<JwtConfig>) {
+    if config.revocation_file.is_none() {
+        return;
+    }
+    let mut interval = tokio::time::interval(REVOCATION_RELOAD_INTERVAL);
+    loop {
+        interval.tick().await;
+        // Note: The file is read in a blocking task, to avoid stalling the requests.
+        let config = config.clone();
+        if let Err(error) = tokio::task::spawn_blocking(move || config.reload_revocations()).await {
+            warn!("Unable to reload the JWT revocation file - {error}");
+        }
+    }
+}
+
+/// Returns a random JWT secret.
+fn random_jwt_secret() -> Vec<u8> {
+    let seed: [u8; 16] = ::rand::thread_rng().gen();
+    seed.to_vec()
+}
+
+/// The permission granted by a token to a group of routes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Scope {
+    /// Read the protected mappings and node details.
+    ReadMapping,
+    /// Manage the peers and the lifecycle of the node.
+    Admin,
+    /// Broadcast transactions and solutions, if the node requires it.
+    Broadcast,
+}
+
+impl Scope {
+    /// The scopes that only read the node data, as unlocked by the tokens before the scopes were introduced.
+    pub const READ: [Scope; 1] = [Scope::ReadMapping];
+}
 
-/// The time a jwt token is valid for.
-pub const EXPIRATION: i64 = 10 * 365 * 24 * 60 * 60; // 10 years.
+impl FromStr for Scope {
+    type Err = anyhow::Error;
 
-/// Returns the JWT secret for the node instance.
-fn jwt_secret() -> &'static Vec<u8> {
-    static SECRET: OnceCell<Vec<u8>> = OnceCell::new();
-    SECRET.get_or_init(|| {
-        let seed: [u8; 16] = ::rand::thread_rng().gen();
-        seed.to_vec()
-    })
+    fn from_str(scope: &str) -> Result<Self> {
+        match scope {
+            "read-mapping" => Ok(Self::ReadMapping),
+            "admin" => Ok(Self::Admin),
+            "broadcast" => Ok(Self::Broadcast),
+            _ => bail!("Unknown scope '{scope}' (expected 'read-mapping', 'admin' or 'broadcast')"),
+        }
+    }
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::ReadMapping => write!(f, "read-mapping"),
+            Self::Admin => write!(f, "admin"),
+            Self::Broadcast => write!(f, "broadcast"),
+        }
+    }
 }
 
 /// The Json web token claims.
@@ -53,14 +243,43 @@ pub struct Claims {
     iat: i64,
     /// Expiration time (as UTC timestamp).
     exp: i64,
+    /// The token ID, used for revocation.
+    #[serde(default)]
+    jti: String,
+    /// The scopes granted by the token.
+    #[serde(default)]
+    scopes: Vec<Scope>,
 }
 
 impl Claims {
+    /// Initializes claims with the read scopes, valid for `EXPIRATION` seconds.
+    /// Note: The tokens with the `admin` or `broadcast` scope are only minted with `with_scopes`.
     pub fn new<N: Network>(address: Address<N>) -> Self {
+        Self::with_scopes(address, &Scope::READ, EXPIRATION)
+    }
+
+    /// Initializes claims with the given scopes, valid for the given number of seconds.
+    pub fn with_scopes<N: Network>(address: Address<N>, scopes: &[Scope], expiration: i64) -> Self {
         let issued_at = OffsetDateTime::now_utc().unix_timestamp();
-        let expiration = issued_at.saturating_add(EXPIRATION);
+        let expiration = issued_at.saturating_add(expiration);
+        let token_id = format!("{:032x}", ::rand::thread_rng().gen::<u128>());
 
-        Self { sub: address.to_string(), iat: issued_at, exp: expiration }
+        Self { sub: address.to_string(), iat: issued_at, exp: expiration, jti: token_id, scopes: scopes.to_vec() }
+    }
+
+    /// Returns the token ID.
+    pub fn id(&self) -> &str {
+        &self.jti
+    }
+
+    /// Returns the scopes granted by the token.
+    pub fn scopes(&self) -> &[Scope] {
+        &self.scopes
+    }
+
+    /// Returns the UTC timestamp at which the token expires.
+    pub fn expiration(&self) -> i64 {
+        self.exp
     }
 
     /// Returns true if the token is expired.
@@ -68,33 +287,47 @@ impl Claims {
         OffsetDateTime::now_utc().unix_timestamp() >= self.exp
     }
 
-    /// Returns the json web token string.
-    pub fn to_jwt_string(&self) -> Result<String> {
-        encode(&Header::default(), &self, &EncodingKey::from_secret(jwt_secret())).map_err(|e| anyhow!(e))
+    /// Returns the json web token string, signed with the given secret.
+    pub fn to_jwt_string_with_secret(&self, secret: &[u8]) -> Result<String> {
+        encode(&Header::default(), &self, &EncodingKey::from_secret(secret)).map_err(|e| anyhow!(e))
     }
 }
 
-pub async fn auth_middleware(request: Request<Body>, next: Next) -> Result<Response, Response> {
-    // Deconstruct the request to extract the auth token.
-    let (mut parts, body) = request.into_parts();
-    let auth: TypedHeader<Authorization<Bearer>> =
-        parts.extract().await.map_err(|_| StatusCode::UNAUTHORIZED.into_response())?;
+/// Ensures the request carries a valid, unrevoked token that grants the given scope.
+pub async fn auth_middleware(
+    State((config, scope)): State<(Arc<JwtConfig>, Scope)>,
+    request: Request<Body>,
+    next: Next,
+) -> Result<Response, Response> {
+    authorize(&config, request.headers(), scope).map_err(IntoResponse::into_response)?;
+
+    Ok(next.run(request).await)
+}
 
-    match decode::<Claims>(auth.token(), &DecodingKey::from_secret(jwt_secret()), &Validation::new(Algorithm::HS256)) {
+/// Ensures the given headers carry a valid, unrevoked token that grants the given scope.
+/// Returns the status and the message of the rejection otherwise.
+pub(crate) fn authorize(config: &JwtConfig, headers: &HeaderMap, scope: Scope) -> Result<(), (StatusCode, String)> {
+    // Extract the auth token.
+    let Some(auth) = headers.typed_get::<Authorization<Bearer>>() else {
+        return Err((StatusCode::UNAUTHORIZED, String::new()));
+    };
+
+    match decode::<Claims>(auth.token(), &DecodingKey::from_secret(&config.secret), &Validation::new(Algorithm::HS256))
+    {
         Ok(decoded) => {
             let claims = decoded.claims;
             if claims.is_expired() {
-                return Err((StatusCode::UNAUTHORIZED, "Expired JSON Web Token".to_owned()).into_response());
+                return Err((StatusCode::UNAUTHORIZED, "Expired JSON Web Token".to_owned()));
             }
+            if config.is_revoked(claims.id()) {
+                return Err((StatusCode::UNAUTHORIZED, "Revoked JSON Web Token".to_owned()));
+            }
+            if !claims.scopes().contains(&scope) {
+                let message = format!("JSON Web Token lacks the '{scope}' scope");
+                return Err((StatusCode::FORBIDDEN, message));
+            }
+            Ok(())
         }
-
-        Err(_) => {
-            return Err(StatusCode::UNAUTHORIZED.into_response());
-        }
+        Err(_) => Err((StatusCode::UNAUTHORIZED, String::new())),
     }
-
-    // Reconstruct the request.
-    let request = Request::from_parts(parts, body);
-
-    Ok(next.run(request).await)
 }
@@ -0,0 +1,120 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{RestError, accepts_binary, encode};
+use snarkvm::prelude::{Network, block::Block};
+
+use axum::{
+    body::Bytes,
+    http::{
+        HeaderMap,
+        HeaderValue,
+        StatusCode,
+        header::{ACCEPT, CACHE_CONTROL, CONTENT_TYPE, ETAG, IF_NONE_MATCH, VARY},
+    },
+    response::{IntoResponse, Response},
+};
+use lru::LruCache;
+use parking_lot::Mutex;
+use std::{fmt::Display, num::NonZeroUsize};
+
+/// The `Cache-Control` of the objects that never change.
+const CACHE_IMMUTABLE: &str = "public, max-age=31536000, immutable";
+/// The `Cache-Control` of the objects that may still change, which clients must revalidate before use.
+const CACHE_REVALIDATE: &str = "no-cache";
+/// The number of serialized blocks kept in memory.
+const BLOCK_CACHE_CAPACITY: usize = 64;
+
+/// Returns the entity tag of the object with the given ID, in the JSON or the binary encoding.
+pub(crate) fn entity_tag(id: impl Display, is_binary: bool) -> Result<HeaderValue, RestError> {
+    let encoding = match is_binary {
+        true => "bin",
+        false => "json",
+    };
+    HeaderValue::from_str(&format!("\"{id}-{encoding}\"")).map_err(|e| RestError(format!("Invalid entity tag - {e}")))
+}
+
+/// Returns `true` if the `If-None-Match` header of the request matches the given entity tag.
+fn is_not_modified(headers: &HeaderMap, etag: &HeaderValue) -> bool {
+    headers
+        .get_all(IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|tag| tag.trim().trim_start_matches("W/"))
+        .any(|tag| tag == "*" || tag.as_bytes() == etag.as_bytes())
+}
+
+/// Returns `304 Not Modified` if the request already holds the object with the given entity tag,
+/// and the response produced by `respond` otherwise, along with the caching headers of the object.
+pub(crate) fn conditional(
+    headers: &HeaderMap,
+    etag: HeaderValue,
+    is_immutable: bool,
+    respond: impl FnOnce() -> Result<Response, RestError>,
+) -> Result<Response, RestError> {
+    let mut response = match is_not_modified(headers, &etag) {
+        true => StatusCode::NOT_MODIFIED.into_response(),
+        false => respond()?,
+    };
+    let cache_control = match is_immutable {
+        true => CACHE_IMMUTABLE,
+        false => CACHE_REVALIDATE,
+    };
+    response.headers_mut().insert(ETAG, etag);
+    response.headers_mut().insert(CACHE_CONTROL, HeaderValue::from_static(cache_control));
+    response.headers_mut().insert(VARY, HeaderValue::from(ACCEPT));
+    Ok(response)
+}
+
+/// The serialized responses of the most recently requested blocks, in each encoding.
+/// Note: The blocks are keyed by hash, so an entry never goes stale.
+pub(crate) struct BlockCache<N: Network> {
+    /// The media type and the body of each block response.
+    #[allow(clippy::type_complexity)]
+    responses: Mutex<LruCache<(N::BlockHash, bool), (&'static str, Bytes)>>,
+}
+
+impl<N: Network> Default for BlockCache<N> {
+    /// Initializes an empty block cache.
+    fn default() -> Self {
+        // Note: The capacity is a nonzero constant.
+        let capacity = NonZeroUsize::new(BLOCK_CACHE_CAPACITY).unwrap();
+        Self { responses: Mutex::new(LruCache::new(capacity)) }
+    }
+}
+
+impl<N: Network> BlockCache<N> {
+    /// Returns the response for the block with the given hash, serializing the block loaded by `load` on a miss.
+    pub(crate) fn respond(
+        &self,
+        headers: &HeaderMap,
+        hash: N::BlockHash,
+        load: impl FnOnce() -> anyhow::Result<Block<N>>,
+    ) -> Result<Response, RestError> {
+        let key = (hash, accepts_binary(headers));
+        let cached = self.responses.lock().get(&key).cloned();
+        let (media_type, body) = match cached {
+            Some(cached) => cached,
+            None => {
+                let (media_type, body) = encode(headers, &load()?)?;
+                let entry = (media_type, Bytes::from(body));
+                self.responses.lock().put(key, entry.clone());
+                entry
+            }
+        };
+        Ok(([(CONTENT_TYPE, media_type)], body).into_response())
+    }
+}
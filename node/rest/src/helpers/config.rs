@@ -0,0 +1,180 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{API_KEY_HEADER, JwtConfig, RateLimits};
+
+use anyhow::{Result, anyhow};
+use axum::http::{
+    HeaderName,
+    HeaderValue,
+    Method,
+    header::{AUTHORIZATION, CONTENT_TYPE, IF_NONE_MATCH},
+};
+use std::{fs, path::PathBuf, str::FromStr, sync::Arc, time::SystemTime};
+use tower_http::cors::{AllowHeaders, AllowOrigin, Any, CorsLayer};
+
+/// The wildcard that allows any origin or header.
+const CORS_WILDCARD: &str = "*";
+
+/// The settings of the REST server.
+#[derive(Clone, Debug)]
+pub struct RestConfig {
+    /// The rate limits.
+    pub rate_limits: RateLimits,
+    /// The TLS settings, if the server serves HTTPS.
+    pub tls: Option<TlsConfig>,
+    /// The CORS settings.
+    pub cors: CorsConfig,
+    /// The JWT settings, which sign and verify the tokens granting access to the protected routes.
+    pub jwt: Arc<JwtConfig>,
+    /// If `true`, the server indexes the transitions of each address, as blocks are committed.
+    pub address_index: bool,
+    /// If `true`, the server indexes the keys of each mapping, as blocks are committed, to page through its entries.
+    pub mapping_index: bool,
+    /// If `true`, the server records the changes to each mapping entry, as blocks are committed.
+    /// Note: This requires the `history` feature.
+    pub mapping_history: bool,
+}
+
+impl RestConfig {
+    /// Initializes the settings of a plain HTTP server, applying the given limit per IP to every route group.
+    pub fn new(rps: u32) -> Self {
+        Self {
+            rate_limits: RateLimits::new(rps),
+            tls: None,
+            cors: Default::default(),
+            jwt: Default::default(),
+            address_index: false,
+            mapping_index: false,
+            mapping_history: false,
+        }
+    }
+}
+
+/// The TLS settings of the REST server.
+/// The certificate and key are reloaded whenever either file is replaced on disk.
+#[derive(Clone, Debug)]
+pub struct TlsConfig {
+    /// The path to the PEM-encoded certificate chain.
+    pub cert_path: PathBuf,
+    /// The path to the PEM-encoded private key.
+    pub key_path: PathBuf,
+}
+
+impl TlsConfig {
+    /// Returns the modification times of the certificate and key files.
+    pub(crate) fn modified(&self) -> (Option<SystemTime>, Option<SystemTime>) {
+        let modified = |path: &PathBuf| fs::metadata(path).and_then(|metadata| metadata.modified()).ok();
+        (modified(&self.cert_path), modified(&self.key_path))
+    }
+}
+
+/// The CORS settings of the REST server.
+#[derive(Clone, Debug)]
+pub struct CorsConfig {
+    /// The allowed origins, or `*` for any origin.
+    pub origins: Vec<String>,
+    /// The allowed methods.
+    pub methods: Vec<String>,
+    /// The allowed request headers, or `*` for any header.
+    pub headers: Vec<String>,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            origins: vec![CORS_WILDCARD.to_string()],
+            methods: vec![Method::GET.to_string(), Method::POST.to_string(), Method::OPTIONS.to_string()],
+            headers: vec![
+                CONTENT_TYPE.to_string(),
+                API_KEY_HEADER.to_string(),
+                AUTHORIZATION.to_string(),
+                IF_NONE_MATCH.to_string(),
+            ],
+        }
+    }
+}
+
+impl CorsConfig {
+    /// Returns the CORS layer enforcing these settings.
+    pub(crate) fn to_layer(&self) -> Result<CorsLayer> {
+        let is_wildcard = |values: &[String]| values.iter().any(|value| value == CORS_WILDCARD);
+        let origins = match is_wildcard(&self.origins) {
+            true => AllowOrigin::from(Any),
+            false => {
+                AllowOrigin::list(parse_all(&self.origins, "origin", |origin| HeaderValue::from_str(origin).ok())?)
+            }
+        };
+        let methods = parse_all(&self.methods, "method", |method| Method::from_str(&method.to_ascii_uppercase()).ok())?;
+        let headers = match is_wildcard(&self.headers) {
+            true => AllowHeaders::from(Any),
+            false => {
+                AllowHeaders::list(parse_all(&self.headers, "header", |header| HeaderName::from_str(header).ok())?)
+            }
+        };
+        Ok(CorsLayer::new().allow_origin(origins).allow_methods(methods).allow_headers(headers))
+    }
+}
+
+/// Parses each of the given CORS settings, returning an error naming the first invalid one.
+fn parse_all<T>(values: &[String], kind: &str, parse: impl Fn(&str) -> Option<T>) -> Result<Vec<T>> {
+    values.iter().map(|value| parse(value).ok_or_else(|| anyhow!("Invalid CORS {kind} '{value}'"))).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{Request, Response, header},
+    };
+    use std::convert::Infallible;
+    use tower::{Layer, ServiceExt, service_fn};
+
+    /// Returns the response of the given CORS layer to a preflight request from an arbitrary origin.
+    fn preflight(layer: CorsLayer, request_headers: &str) -> Response<Body> {
+        let service =
+            layer.layer(service_fn(|_: Request<Body>| async { Ok::<_, Infallible>(Response::new(Body::empty())) }));
+        let request = Request::builder()
+            .method(Method::OPTIONS)
+            .uri("/mainnet/block/latest")
+            .header(header::ORIGIN, "https://explorer.example")
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, "GET")
+            .header(header::ACCESS_CONTROL_REQUEST_HEADERS, request_headers)
+            .body(Body::empty())
+            .unwrap();
+        futures::executor::block_on(service.oneshot(request)).unwrap()
+    }
+
+    #[test]
+    fn test_default_layer() {
+        let response = preflight(CorsConfig::default().to_layer().unwrap(), "authorization,x-api-key,if-none-match");
+        // Ensure any origin is allowed, as before the CORS settings were configurable.
+        assert_eq!(response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+        // Ensure the headers of the browser-facing features are allowed.
+        let allowed = response.headers()[header::ACCESS_CONTROL_ALLOW_HEADERS].to_str().unwrap().to_string();
+        for name in [CONTENT_TYPE.as_str(), API_KEY_HEADER, AUTHORIZATION.as_str(), IF_NONE_MATCH.as_str()] {
+            assert!(allowed.split(',').any(|header| header.trim() == name), "'{name}' is not in '{allowed}'");
+        }
+    }
+
+    #[test]
+    fn test_origin_list() {
+        let config = CorsConfig { origins: vec!["https://wallet.example".to_string()], ..Default::default() };
+        let response = preflight(config.to_layer().unwrap(), "content-type");
+        // Ensure an origin outside of the list is not allowed.
+        assert!(response.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN).is_none());
+    }
+}
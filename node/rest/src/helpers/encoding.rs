@@ -0,0 +1,196 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::RestError;
+use snarkvm::prelude::{FromBytes, ToBytes};
+
+use axum::{
+    Json,
+    async_trait,
+    body::Bytes,
+    extract::{FromRequest, Request},
+    http::{
+        HeaderMap,
+        StatusCode,
+        header::{ACCEPT, CONTENT_TYPE, HeaderValue},
+    },
+    response::{IntoResponse, Response},
+};
+use axum_extra::response::ErasedJson;
+use serde::{Serialize, de::DeserializeOwned};
+
+/// The media type of the snarkVM binary encoding.
+pub const OCTET_STREAM: &str = "application/octet-stream";
+/// The media type of the JSON encoding.
+pub const APPLICATION_JSON: &str = "application/json";
+
+/// Returns `true` if the given `Content-Type` header value is the binary media type.
+fn is_octet_stream(value: Option<&HeaderValue>) -> bool {
+    value
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .is_some_and(|media_type| media_type.trim().eq_ignore_ascii_case(OCTET_STREAM))
+}
+
+/// Returns the quality the given `Accept` header value gives to the given media type, from its most specific range,
+/// along with whether the range names the media type itself rather than a wildcard.
+fn quality(accept: &str, media_type: &str) -> Option<(f32, bool)> {
+    let (kind, _) = media_type.split_once('/')?;
+    let mut best: Option<(u8, f32)> = None;
+    for range in accept.split(',') {
+        let mut params = range.split(';');
+        let range = params.next().unwrap_or_default().trim();
+        let specificity = match range {
+            _ if range.eq_ignore_ascii_case(media_type) => 2,
+            _ if range.split_once('/').is_some_and(|(k, s)| k.eq_ignore_ascii_case(kind) && s == "*") => 1,
+            "*/*" => 0,
+            _ => continue,
+        };
+        // Note: A malformed quality is read as the default quality.
+        let q = params
+            .filter_map(|param| param.trim().strip_prefix("q=").or_else(|| param.trim().strip_prefix("Q=")))
+            .find_map(|q| q.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+        match best {
+            Some((best, _)) if best >= specificity => {}
+            _ => best = Some((specificity, q)),
+        }
+    }
+    best.map(|(specificity, q)| (q, specificity == 2))
+}
+
+/// Returns `true` if the given `Accept` header value names the binary media type, with a non-zero quality
+/// that is at least the quality of JSON. The binary encoding is never chosen through a wildcard.
+fn accepts_octet_stream(value: Option<&HeaderValue>) -> bool {
+    let Some(accept) = value.and_then(|value| value.to_str().ok()) else {
+        return false;
+    };
+    match quality(accept, OCTET_STREAM) {
+        Some((q, true)) if q > 0.0 => q >= quality(accept, APPLICATION_JSON).map_or(0.0, |(q, _)| q),
+        _ => false,
+    }
+}
+
+/// Returns `true` if the request asks for the binary encoding through its `Accept` header.
+pub fn accepts_binary(headers: &HeaderMap) -> bool {
+    accepts_octet_stream(headers.get(ACCEPT))
+}
+
+/// Returns the given object in the binary encoding if the request accepts it, and as JSON otherwise.
+pub fn negotiate<T: Serialize + ToBytes>(headers: &HeaderMap, object: T) -> Result<Response, RestError> {
+    match accepts_binary(headers) {
+        true => Ok(binary_response(object.to_bytes_le()?)),
+        false => Ok(ErasedJson::pretty(object).into_response()),
+    }
+}
+
+/// Returns the given objects in the binary encoding if the request accepts it, and as a JSON array otherwise.
+/// The binary encoding is the number of objects as a little-endian `u32`, followed by each object in turn.
+pub fn negotiate_all<T: Serialize + ToBytes>(headers: &HeaderMap, objects: Vec<T>) -> Result<Response, RestError> {
+    match accepts_binary(headers) {
+        true => {
+            let encode = || -> anyhow::Result<Vec<u8>> {
+                let mut bytes = Vec::new();
+                u32::try_from(objects.len())?.write_le(&mut bytes)?;
+                for object in &objects {
+                    object.write_le(&mut bytes)?;
+                }
+                Ok(bytes)
+            };
+            Ok(binary_response(encode()?))
+        }
+        false => Ok(ErasedJson::pretty(objects).into_response()),
+    }
+}
+
+/// Returns the given object in the binary encoding if the request accepts it, and as pretty JSON otherwise,
+/// along with the media type of the encoding.
+pub fn encode<T: Serialize + ToBytes>(headers: &HeaderMap, object: &T) -> anyhow::Result<(&'static str, Vec<u8>)> {
+    match accepts_binary(headers) {
+        true => Ok((OCTET_STREAM, object.to_bytes_le()?)),
+        false => Ok((APPLICATION_JSON, serde_json::to_vec_pretty(object)?)),
+    }
+}
+
+/// Returns a response with the given bytes and the binary media type.
+fn binary_response(bytes: Vec<u8>) -> Response {
+    ([(CONTENT_TYPE, OCTET_STREAM)], bytes).into_response()
+}
+
+/// An extractor for a request body in the binary encoding, if its `Content-Type` is `application/octet-stream`,
+/// and in JSON otherwise.
+pub struct BinaryOrJson<T>(pub T);
+
+#[async_trait]
+impl<S: Send + Sync, T: DeserializeOwned + FromBytes> FromRequest<S> for BinaryOrJson<T> {
+    type Rejection = Response;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        match is_octet_stream(request.headers().get(CONTENT_TYPE)) {
+            true => {
+                let bytes = Bytes::from_request(request, state).await.map_err(IntoResponse::into_response)?;
+                match T::from_bytes_le(&bytes) {
+                    Ok(object) => Ok(Self(object)),
+                    Err(error) => {
+                        let message = format!("Invalid binary body - {error}");
+                        Err((StatusCode::UNPROCESSABLE_ENTITY, message).into_response())
+                    }
+                }
+            }
+            false => {
+                let json = Json::<T>::from_request(request, state).await.map_err(IntoResponse::into_response)?;
+                Ok(Self(json.0))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accepts(accept: &str) -> bool {
+        accepts_octet_stream(Some(&HeaderValue::from_str(accept).unwrap()))
+    }
+
+    #[test]
+    fn test_accepts_octet_stream() {
+        assert!(accepts("application/octet-stream"));
+        assert!(accepts("application/json, application/octet-stream"));
+        assert!(accepts("application/octet-stream;q=0.9, application/json;q=0.5"));
+        assert!(accepts("application/json;q=0, application/octet-stream"));
+        // The binary encoding is refused with a zero quality.
+        assert!(!accepts("application/octet-stream;q=0"));
+        assert!(!accepts("application/octet-stream; q=0.0, */*"));
+        // JSON is preferred with a higher quality.
+        assert!(!accepts("application/octet-stream;q=0.5, application/json"));
+        assert!(!accepts("application/octet-stream;q=0.5, */*"));
+        // The binary encoding is never chosen through a wildcard.
+        assert!(!accepts("*/*"));
+        assert!(!accepts("application/*"));
+        assert!(!accepts("application/octet-streams"));
+        assert!(!accepts_octet_stream(None));
+    }
+
+    #[test]
+    fn test_is_octet_stream() {
+        let is = |content_type: &str| is_octet_stream(Some(&HeaderValue::from_str(content_type).unwrap()));
+        assert!(is("application/octet-stream"));
+        assert!(is("Application/Octet-Stream; charset=binary"));
+        assert!(!is("application/json"));
+        assert!(!is("application/octet-streams"));
+        assert!(!is_octet_stream(None));
+    }
+}
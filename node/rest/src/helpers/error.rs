@@ -13,10 +13,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use snarkos_node_consensus::AdmissionError;
+use snarkvm::prelude::Network;
+
 use axum::{
+    Json,
     http::StatusCode,
     response::{IntoResponse, Response},
 };
+use serde_json::json;
 
 /// An enum of error handlers for the REST API server.
 pub struct RestError(pub String);
@@ -32,3 +37,76 @@ impl From<anyhow::Error> for RestError {
         Self(err.to_string())
     }
 }
+
+/// An error returned by the broadcast routes, with a status code and a machine-readable error code.
+pub struct BroadcastError {
+    /// The status code of the response.
+    status: StatusCode,
+    /// The machine-readable error code.
+    code: &'static str,
+    /// Whether the same transaction or solution may be accepted if it is broadcast again later.
+    retryable: bool,
+    /// The error message.
+    message: String,
+}
+
+impl BroadcastError {
+    /// Returns the machine-readable error code.
+    pub const fn code(&self) -> &'static str {
+        self.code
+    }
+
+    /// Returns `true` if the same transaction or solution may be accepted if it is broadcast again later.
+    pub const fn is_retryable(&self) -> bool {
+        self.retryable
+    }
+
+    /// Returns the error message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Initializes a new broadcast error for the given transmission ID, rejected as the node is syncing.
+    pub fn syncing(id: impl ToString) -> Self {
+        Self {
+            status: StatusCode::SERVICE_UNAVAILABLE,
+            code: "node_syncing",
+            retryable: true,
+            message: format!("Unable to admit transmission '{}' (node is syncing)", id.to_string()),
+        }
+    }
+
+    /// Initializes a new broadcast error from the given error, which is an admission error if it can be downcast to one.
+    pub fn new<N: Network>(error: anyhow::Error) -> Self {
+        match error.downcast::<AdmissionError<N>>() {
+            Ok(error) => Self::from(error),
+            Err(error) => Self {
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                code: "internal",
+                retryable: false,
+                message: error.to_string(),
+            },
+        }
+    }
+}
+
+impl<N: Network> From<AdmissionError<N>> for BroadcastError {
+    fn from(error: AdmissionError<N>) -> Self {
+        let status = match &error {
+            AdmissionError::FeeTransaction(..) => StatusCode::BAD_REQUEST,
+            AdmissionError::Duplicate(..) | AdmissionError::AlreadyInLedger(..) => StatusCode::CONFLICT,
+            AdmissionError::QueueFull(..) => StatusCode::SERVICE_UNAVAILABLE,
+            AdmissionError::PayerLimitExceeded { .. } => StatusCode::TOO_MANY_REQUESTS,
+            AdmissionError::TooLarge(..) => StatusCode::PAYLOAD_TOO_LARGE,
+            AdmissionError::Malformed { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+        };
+        Self { status, code: error.code(), retryable: error.is_retryable(), message: error.message() }
+    }
+}
+
+impl IntoResponse for BroadcastError {
+    fn into_response(self) -> Response {
+        let body = json!({ "code": self.code, "retryable": self.retryable, "message": self.message });
+        (self.status, Json(body)).into_response()
+    }
+}
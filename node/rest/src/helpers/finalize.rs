@@ -0,0 +1,238 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::{
+    console::program::{Argument, Future},
+    ledger::puzzle::PuzzleSolutions,
+    prelude::{
+        Address,
+        Field,
+        Identifier,
+        Network,
+        Plaintext,
+        ProgramID,
+        Result,
+        ToBits,
+        Value,
+        block::{Block, Output, Ratify, Transaction},
+    },
+    synthesizer::program::FinalizeOperation,
+};
+
+use indexmap::{IndexMap, IndexSet};
+use std::str::FromStr;
+
+/// A write to a mapping entry, as recorded by the finalize operations of a block.
+/// Note: The finalize operations only commit to the keys and values, through their IDs.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct MappingWrite<N: Network> {
+    /// The ID of the mapping.
+    pub(crate) mapping_id: Field<N>,
+    /// The ID of the key.
+    pub(crate) key_id: Field<N>,
+    /// The ID of the value written, or `None` if the entry was removed.
+    pub(crate) value_id: Option<Field<N>>,
+}
+
+impl<N: Network> MappingWrite<N> {
+    /// Returns the write to a mapping entry performed by the given finalize operation, if it writes to one.
+    pub(crate) fn from_operation(operation: &FinalizeOperation<N>) -> Option<Self> {
+        match *operation {
+            FinalizeOperation::InsertKeyValue(mapping_id, key_id, value_id)
+            | FinalizeOperation::UpdateKeyValue(mapping_id, key_id, value_id) => {
+                Some(Self { mapping_id, key_id, value_id: Some(value_id) })
+            }
+            FinalizeOperation::RemoveKeyValue(mapping_id, key_id) => Some(Self { mapping_id, key_id, value_id: None }),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the writes to the mapping entries of the given block, in the order of the confirmed transactions.
+pub(crate) fn mapping_writes<N: Network>(block: &Block<N>) -> Vec<MappingWrite<N>> {
+    block
+        .transactions()
+        .iter()
+        .flat_map(|confirmed| confirmed.finalize_operations().iter())
+        .filter_map(MappingWrite::from_operation)
+        .collect()
+}
+
+// Note: The IDs below mirror the private helpers of the snarkVM finalize store, and are pinned by the tests.
+
+/// Returns the mapping ID for the given program ID and mapping name, as committed to by the finalize operations.
+pub(crate) fn to_mapping_id<N: Network>(program_id: &ProgramID<N>, mapping_name: &Identifier<N>) -> Result<Field<N>> {
+    // Construct the preimage.
+    let mut preimage = Vec::new();
+    program_id.write_bits_le(&mut preimage);
+    false.write_bits_le(&mut preimage); // Separator.
+    mapping_name.write_bits_le(&mut preimage);
+    // Compute the mapping ID.
+    N::hash_bhp1024(&preimage)
+}
+
+/// Returns the key ID for the given program ID, mapping name and key, as committed to by the finalize operations.
+pub(crate) fn to_key_id<N: Network>(
+    program_id: &ProgramID<N>,
+    mapping_name: &Identifier<N>,
+    key: &Plaintext<N>,
+) -> Result<Field<N>> {
+    // Construct the preimage.
+    let mut preimage = Vec::new();
+    program_id.write_bits_le(&mut preimage);
+    false.write_bits_le(&mut preimage); // Separator.
+    mapping_name.write_bits_le(&mut preimage);
+    false.write_bits_le(&mut preimage); // Separator.
+    key.write_bits_le(&mut preimage);
+    // Compute the key ID.
+    N::hash_bhp1024(&preimage)
+}
+
+/// Returns the value ID for the given key ID and value, as committed to by the finalize operations.
+pub(crate) fn to_value_id<N: Network>(key_id: &Field<N>, value: &Value<N>) -> Result<Field<N>> {
+    // Compute the value ID, from the key ID and the hash of the value.
+    N::hash_bhp1024(&(*key_id, N::hash_bhp1024(&value.to_bits_le())?).to_bits_le())
+}
+
+/// The plaintexts passed to the finalize logic of a program, by their bits.
+pub(crate) type Plaintexts<N> = IndexMap<Vec<bool>, Plaintext<N>>;
+
+/// Returns the plaintext arguments passed to the finalize logic of each program run by the given block.
+pub(crate) fn collect_finalize_arguments<N: Network>(block: &Block<N>) -> IndexMap<ProgramID<N>, Plaintexts<N>> {
+    let mut arguments = IndexMap::new();
+    for confirmed in block.transactions().iter() {
+        collect_transaction_arguments(confirmed.transaction(), &mut arguments);
+    }
+    arguments
+}
+
+/// Appends the plaintext arguments passed to the finalize logic of each program run by the given transaction.
+pub(crate) fn collect_transaction_arguments<N: Network>(
+    transaction: &Transaction<N>,
+    arguments: &mut IndexMap<ProgramID<N>, Plaintexts<N>>,
+) {
+    for transition in transaction.transitions() {
+        for output in transition.outputs() {
+            if let Output::Future(_, Some(future)) = output {
+                collect_future_arguments(future, arguments);
+            }
+        }
+    }
+}
+
+/// Appends the plaintext arguments of the given future, and of the futures it awaits.
+fn collect_future_arguments<N: Network>(future: &Future<N>, arguments: &mut IndexMap<ProgramID<N>, Plaintexts<N>>) {
+    arguments.entry(*future.program_id()).or_default();
+    for argument in future.arguments() {
+        match argument {
+            Argument::Plaintext(plaintext) => {
+                collect_plaintexts(plaintext, arguments.entry(*future.program_id()).or_default())
+            }
+            Argument::Future(future) => collect_future_arguments(future, arguments),
+        }
+    }
+}
+
+/// Appends the given plaintext, along with the members and elements it contains.
+fn collect_plaintexts<N: Network>(plaintext: &Plaintext<N>, plaintexts: &mut Plaintexts<N>) {
+    match plaintext {
+        Plaintext::Literal(..) => {}
+        Plaintext::Struct(members, _) => {
+            for member in members.values() {
+                collect_plaintexts(member, plaintexts);
+            }
+        }
+        Plaintext::Array(elements, _) => {
+            for element in elements {
+                collect_plaintexts(element, plaintexts);
+            }
+        }
+    }
+    plaintexts.entry(plaintext.to_bits_le()).or_insert_with(|| plaintext.clone());
+}
+
+/// Returns the program ID and mapping name of the `credits.aleo/account` mapping.
+pub(crate) fn account_mapping<N: Network>() -> Result<(ProgramID<N>, Identifier<N>)> {
+    Ok((ProgramID::from_str("credits.aleo")?, Identifier::from_str("account")?))
+}
+
+/// Returns the addresses whose `credits.aleo` entries are written by the ratifications of the given block,
+/// namely the genesis committee, balances and bonds, and the provers rewarded for their solutions.
+/// Note: The ratifications are not in the finalize operations of the block.
+pub(crate) fn ratified_addresses<N: Network>(block: &Block<N>) -> IndexSet<Address<N>> {
+    let mut addresses = IndexSet::new();
+    for ratify in block.ratifications().iter() {
+        if let Ratify::Genesis(committee, public_balances, bonded_balances) = ratify {
+            addresses.extend(committee.members().keys().copied());
+            addresses.extend(public_balances.keys().copied());
+            for (staker, (validator, withdrawal, _)) in bonded_balances.iter() {
+                addresses.extend([*staker, *validator, *withdrawal]);
+            }
+        }
+    }
+    let solutions: &Option<PuzzleSolutions<N>> = block.solutions();
+    for solution in solutions.iter().flat_map(|solutions| solutions.values()) {
+        addresses.insert(solution.address());
+    }
+    addresses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::{
+        ledger::store::{FinalizeStorage, FinalizeStore, helpers::memory::FinalizeMemory},
+        prelude::MainnetV0,
+        synthesizer::program::FinalizeStoreTrait,
+    };
+
+    type CurrentNetwork = MainnetV0;
+
+    #[test]
+    fn test_ids_match_the_finalize_store() {
+        let program_id = ProgramID::<CurrentNetwork>::from_str("credits.aleo").unwrap();
+        let name = Identifier::from_str("account").unwrap();
+        let key = Plaintext::from_str("aleo1rhgdu77hgyqd3xjj8ucu3jj9r2krwz6mnzyd80gncr5fxcwlh5rsvzp9px").unwrap();
+        let value = Value::from_str("5u64").unwrap();
+        let update = Value::from_str("7u64").unwrap();
+
+        // Record the finalize operations of the snarkVM finalize store.
+        let store = FinalizeStore::from(FinalizeMemory::<CurrentNetwork>::open(None::<u16>).unwrap()).unwrap();
+        let initialize = store.initialize_mapping(program_id, name).unwrap();
+        let insert = store.insert_key_value(program_id, name, key.clone(), value.clone()).unwrap();
+        let updated = store.update_key_value(program_id, name, key.clone(), update.clone()).unwrap();
+        let remove = store.remove_key_value(program_id, name, &key).unwrap().unwrap();
+
+        // Ensure the IDs are derived as the finalize store does.
+        let mapping_id = to_mapping_id(&program_id, &name).unwrap();
+        let key_id = to_key_id(&program_id, &name, &key).unwrap();
+        assert_eq!(initialize, FinalizeOperation::InitializeMapping(mapping_id));
+        assert_eq!(
+            insert,
+            FinalizeOperation::InsertKeyValue(mapping_id, key_id, to_value_id(&key_id, &value).unwrap())
+        );
+        assert_eq!(
+            updated,
+            FinalizeOperation::UpdateKeyValue(mapping_id, key_id, to_value_id(&key_id, &update).unwrap())
+        );
+        assert_eq!(remove, FinalizeOperation::RemoveKeyValue(mapping_id, key_id));
+        // Ensure the writes are read back from the operations.
+        assert_eq!(
+            MappingWrite::from_operation(&insert),
+            Some(MappingWrite { mapping_id, key_id, value_id: Some(to_value_id(&key_id, &value).unwrap()) })
+        );
+        assert_eq!(MappingWrite::from_operation(&remove), Some(MappingWrite { mapping_id, key_id, value_id: None }));
+    }
+}
@@ -0,0 +1,116 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use aleo_std::{StorageMode, aleo_ledger_dir};
+use anyhow::{Result, anyhow};
+use rocksdb::{DB, Direction, IteratorMode, Options, WriteBatch};
+use std::path::{Path, PathBuf};
+
+/// The name of the index from addresses to their transitions.
+pub const ADDRESS_INDEX_NAME: &str = "address-index";
+/// The name of the index of the changes to the program mappings.
+pub const MAPPING_HISTORY_NAME: &str = "mapping-history";
+/// The name of the index of the keys of the program mappings.
+pub const MAPPING_KEYS_NAME: &str = "mapping-keys";
+/// The names of all the indexes of the REST server, which are removed along with the ledger.
+pub const INDEX_NAMES: [&str; 3] = [ADDRESS_INDEX_NAME, MAPPING_HISTORY_NAME, MAPPING_KEYS_NAME];
+
+/// The key holding the height of the next block to index.
+/// Note: The entries of the indexes are keyed by a prefix byte, so they never collide with this key.
+const NEXT_HEIGHT_KEY: &[u8] = b"next-height";
+
+/// Returns the path where the index with the given name may be stored.
+/// Note: The index is stored next to the ledger, in a folder named after the ledger's.
+pub fn index_store_path(name: &str, network: u16, storage_mode: StorageMode) -> PathBuf {
+    let mut path = aleo_ledger_dir(network, storage_mode);
+    let ledger_name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+    path.set_file_name(format!("{ledger_name}-{name}"));
+    path
+}
+
+/// An index of the REST server, persisted in its own database and updated one block at a time.
+/// The keys are ordered bytewise, so the entries sharing a prefix are read in order, starting from any key.
+pub(crate) struct IndexStore {
+    /// The database.
+    db: DB,
+}
+
+impl IndexStore {
+    /// Opens the index stored at the given path, creating it if it doesn't exist.
+    pub(crate) fn open(path: &Path) -> Result<Self> {
+        let mut options = Options::default();
+        options.create_if_missing(true);
+        let db =
+            DB::open(&options, path).map_err(|e| anyhow!("Couldn't open the index at {} - {e}", path.display()))?;
+        Ok(Self { db })
+    }
+
+    /// Returns the height of the next block to index.
+    pub(crate) fn next_height(&self) -> Result<u32> {
+        match self.db.get(NEXT_HEIGHT_KEY)? {
+            Some(bytes) => Ok(u32::from_le_bytes(bytes.as_slice().try_into()?)),
+            None => Ok(0),
+        }
+    }
+
+    /// Stores the entries of the block at the given height, along with the height of the next block to index.
+    /// Note: The entries and the height are written atomically, so a block is either fully indexed or not at all.
+    pub(crate) fn insert_block(&self, height: u32, entries: Vec<(Vec<u8>, Vec<u8>)>) -> Result<()> {
+        let mut batch = WriteBatch::default();
+        for (key, value) in entries {
+            batch.put(key, value);
+        }
+        self.write_block(height, batch)
+    }
+
+    /// Writes the given batch for the block at the given height, along with the height of the next block to index.
+    pub(crate) fn write_block(&self, height: u32, mut batch: WriteBatch) -> Result<()> {
+        batch.put(NEXT_HEIGHT_KEY, height.saturating_add(1).to_le_bytes());
+        self.write(batch)
+    }
+
+    /// Writes the given batch atomically.
+    pub(crate) fn write(&self, batch: WriteBatch) -> Result<()> {
+        Ok(self.db.write(batch)?)
+    }
+
+    /// Returns the value of the entry with the given key, if it exists.
+    pub(crate) fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        Ok(self.db.get(key)?)
+    }
+
+    /// Returns the entries whose key starts with the given prefix, from the given key onwards, in key order.
+    pub(crate) fn iter_from<'a>(
+        &'a self,
+        prefix: &'a [u8],
+        from: &[u8],
+    ) -> impl Iterator<Item = Result<(Box<[u8]>, Box<[u8]>)>> + 'a {
+        self.db
+            .iterator(IteratorMode::From(from, Direction::Forward))
+            .map(|entry| entry.map_err(|e| anyhow!("Couldn't read the index - {e}")))
+            .take_while(move |entry| entry.as_ref().map_or(true, |(key, _)| key.starts_with(prefix)))
+    }
+
+    /// Returns the last entry whose key starts with the given prefix, up to the given key (inclusive), if any.
+    #[cfg(feature = "history")]
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn last_until(&self, prefix: &[u8], until: &[u8]) -> Result<Option<(Box<[u8]>, Box<[u8]>)>> {
+        match self.db.iterator(IteratorMode::From(until, Direction::Reverse)).next() {
+            Some(Ok((key, value))) if key.starts_with(prefix) => Ok(Some((key, value))),
+            Some(Err(e)) => Err(anyhow!("Couldn't read the index - {e}")),
+            _ => Ok(None),
+        }
+    }
+}
@@ -16,5 +16,23 @@
 mod auth;
 pub use auth::*;
 
+mod cache;
+pub(crate) use cache::*;
+
+mod config;
+pub use config::*;
+
+mod encoding;
+pub use encoding::*;
+
 mod error;
 pub use error::*;
+
+mod finalize;
+pub(crate) use finalize::*;
+
+mod index_store;
+pub use index_store::*;
+
+mod rate_limit;
+pub use rate_limit::*;
@@ -0,0 +1,282 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{Result, anyhow};
+use axum::{
+    body::Body,
+    extract::{ConnectInfo, MatchedPath, State},
+    http::{HeaderValue, Method, Request, StatusCode, header::RETRY_AFTER},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use governor::{
+    DefaultDirectRateLimiter,
+    DefaultKeyedRateLimiter,
+    Quota,
+    RateLimiter,
+    clock::{Clock, DefaultClock},
+};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    fs,
+    net::{IpAddr, SocketAddr},
+    num::NonZeroU32,
+    path::Path,
+    sync::Arc,
+    time::Duration,
+};
+
+/// The header carrying the API key of a request.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// The groups of routes that are rate limited independently.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RouteGroup {
+    /// The routes reading a single item.
+    Point,
+    /// The routes reading many items, or performing expensive work.
+    Bulk,
+    /// The routes submitting transmissions.
+    Broadcast,
+}
+
+impl RouteGroup {
+    /// Returns the name of the group, as described in the OpenAPI document.
+    pub(crate) const fn name(self) -> &'static str {
+        match self {
+            Self::Point => "point",
+            Self::Bulk => "bulk",
+            Self::Broadcast => "broadcast",
+        }
+    }
+}
+
+/// The client charged for a request, by its API key or else by its IP.
+#[derive(Clone, Debug)]
+enum Client {
+    /// A client with a configured API key.
+    ApiKey(String),
+    /// A client without an API key.
+    Ip(IpAddr),
+}
+
+/// The rate limit of a request whose calls are charged one at a time, by the route dispatching them.
+#[derive(Clone)]
+pub(crate) struct CallRateLimit {
+    /// The rate limiters.
+    limiters: Arc<RateLimiters>,
+    /// The client charged for the calls.
+    client: Client,
+}
+
+impl CallRateLimit {
+    /// Charges a call in the given route group, returning the time to wait if the limit is reached.
+    pub(crate) fn check(&self, group: RouteGroup) -> Result<(), Duration> {
+        self.limiters.check(&self.client, group)
+    }
+}
+
+/// The rate limits of the REST server, in requests per second.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RateLimits {
+    /// The limit per IP for the routes reading a single item.
+    pub point: u32,
+    /// The limit per IP for the routes reading many items, or performing expensive work.
+    pub bulk: u32,
+    /// The limit per IP for the routes submitting transmissions.
+    pub broadcast: u32,
+    /// The limit per API key, across all routes. Requests with an API key bypass the limits per IP.
+    pub api_keys: HashMap<String, u32>,
+}
+
+/// The rate limits file, which overrides the rate limits it specifies.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct RateLimitsFile {
+    point: Option<u32>,
+    bulk: Option<u32>,
+    broadcast: Option<u32>,
+    #[serde(default)]
+    api_keys: HashMap<String, u32>,
+}
+
+impl RateLimits {
+    /// Initializes the rate limits, applying the given limit per IP to every route group.
+    pub fn new(rps: u32) -> Self {
+        Self { point: rps, bulk: rps, broadcast: rps, api_keys: Default::default() }
+    }
+
+    /// Overrides the rate limits with the ones specified in the given JSON file, and adds its API keys.
+    pub fn with_file(mut self, path: &Path) -> Result<Self> {
+        let file = fs::read_to_string(path).map_err(|e| anyhow!("Unable to read the rate limits file - {e}"))?;
+        let file: RateLimitsFile =
+            serde_json::from_str(&file).map_err(|e| anyhow!("Unable to parse the rate limits file - {e}"))?;
+        self.point = file.point.unwrap_or(self.point);
+        self.bulk = file.bulk.unwrap_or(self.bulk);
+        self.broadcast = file.broadcast.unwrap_or(self.broadcast);
+        self.api_keys.extend(file.api_keys);
+        Ok(self)
+    }
+}
+
+/// The rate limiters of the REST server.
+pub(crate) struct RateLimiters {
+    /// The limiter per IP for the point routes.
+    point: DefaultKeyedRateLimiter<IpAddr>,
+    /// The limiter per IP for the bulk routes.
+    bulk: DefaultKeyedRateLimiter<IpAddr>,
+    /// The limiter per IP for the broadcast routes.
+    broadcast: DefaultKeyedRateLimiter<IpAddr>,
+    /// The limiter of each API key.
+    api_keys: HashMap<String, DefaultDirectRateLimiter>,
+}
+
+impl RateLimiters {
+    /// Initializes the rate limiters, ensuring every limit is nonzero.
+    pub(crate) fn new(limits: &RateLimits) -> Result<Self> {
+        let quota = |name: &str, rps: u32| {
+            NonZeroU32::new(rps).map(Quota::per_second).ok_or_else(|| anyhow!("The {name} rate limit must be nonzero"))
+        };
+        let api_keys = limits
+            .api_keys
+            .iter()
+            .map(|(key, rps)| Ok((key.clone(), RateLimiter::direct(quota("API key", *rps)?))))
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            point: RateLimiter::keyed(quota("point", limits.point)?),
+            bulk: RateLimiter::keyed(quota("bulk", limits.bulk)?),
+            broadcast: RateLimiter::keyed(quota("broadcast", limits.broadcast)?),
+            api_keys,
+        })
+    }
+
+    /// Returns the limiter per IP for the given route group.
+    fn group(&self, group: RouteGroup) -> &DefaultKeyedRateLimiter<IpAddr> {
+        match group {
+            RouteGroup::Point => &self.point,
+            RouteGroup::Bulk => &self.bulk,
+            RouteGroup::Broadcast => &self.broadcast,
+        }
+    }
+
+    /// Charges a request of the given client in the given route group, returning the time to wait if the limit is reached.
+    /// Note: A client with an API key is charged by the limit of its key, across all route groups.
+    fn check(&self, client: &Client, group: RouteGroup) -> Result<(), Duration> {
+        let outcome = match client {
+            Client::ApiKey(key) => match self.api_keys.get(key) {
+                Some(limiter) => limiter.check(),
+                None => Ok(()),
+            },
+            Client::Ip(ip) => self.group(group).check_key(ip),
+        };
+        outcome.map_err(|not_until| not_until.wait_time_from(DefaultClock::default().now()))
+    }
+
+    /// Removes the state of the IPs whose limits were fully replenished.
+    pub(crate) fn retain_recent(&self) {
+        for limiter in [&self.point, &self.bulk, &self.broadcast] {
+            limiter.retain_recent();
+            limiter.shrink_to_fit();
+        }
+    }
+}
+
+/// The route group of each route, by its method and path, or `None` if the handler charges each of its calls.
+pub(crate) type RouteGroups = HashMap<(Method, String), Option<RouteGroup>>;
+
+/// The middleware enforcing the rate limit of the API key of the request if any, or else of its IP and route group,
+/// given the rate limiters and the route groups. A request to an unknown route is charged as a point request.
+/// If the group of the route is `None`, the request carries its `CallRateLimit` instead, for the handler to charge
+/// each of its calls.
+pub(crate) async fn rate_limit_middleware(
+    State((limiters, groups)): State<(Arc<RateLimiters>, Arc<RouteGroups>)>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    mut request: Request<Body>,
+    next: Next,
+) -> Response {
+    let client = match request.headers().get(API_KEY_HEADER) {
+        Some(key) => match key.to_str().ok().filter(|key| limiters.api_keys.contains_key(*key)) {
+            Some(key) => Client::ApiKey(key.to_string()),
+            None => return (StatusCode::UNAUTHORIZED, "Unknown API key").into_response(),
+        },
+        None => Client::Ip(addr.ip()),
+    };
+    // Note: The matched path is unset for the requests falling back to the 404 handler.
+    let group = request
+        .extensions()
+        .get::<MatchedPath>()
+        .and_then(|path| groups.get(&(request.method().clone(), path.as_str().to_string())))
+        .copied()
+        .unwrap_or(Some(RouteGroup::Point));
+    match group {
+        Some(group) => {
+            if let Err(wait_time) = limiters.check(&client, group) {
+                return too_many_requests(wait_time);
+            }
+        }
+        None => {
+            request.extensions_mut().insert(CallRateLimit { limiters, client });
+        }
+    }
+    next.run(request).await
+}
+
+/// Returns the number of seconds to wait, rounded up, as reported in the `Retry-After` header.
+pub(crate) fn retry_after_secs(wait_time: Duration) -> u64 {
+    (wait_time.as_secs() + u64::from(wait_time.subsec_nanos() > 0)).max(1)
+}
+
+/// Returns a 429 Too Many Requests response, with the number of seconds to wait in the `Retry-After` header.
+fn too_many_requests(wait_time: Duration) -> Response {
+    let retry_after = retry_after_secs(wait_time);
+    let message = format!("Too Many Requests! Wait for {retry_after}s");
+    let mut response = (StatusCode::TOO_MANY_REQUESTS, message).into_response();
+    response.headers_mut().insert(RETRY_AFTER, HeaderValue::from(retry_after));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, middleware, routing::get};
+    use tower::ServiceExt;
+
+    /// Returns the status of a `GET` request to the given path, from the given address.
+    async fn status(router: &Router, addr: SocketAddr, path: &str) -> StatusCode {
+        let mut request = Request::get(path).body(Body::empty()).unwrap();
+        request.extensions_mut().insert(ConnectInfo(addr));
+        router.clone().oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_unknown_routes_are_rate_limited() {
+        let limits = RateLimits { point: 1, bulk: 2, ..RateLimits::new(1) };
+        let limiters = Arc::new(RateLimiters::new(&limits).unwrap());
+        let groups: RouteGroups = [((Method::GET, "/bulk".to_string()), Some(RouteGroup::Bulk))].into_iter().collect();
+        let router = Router::new()
+            .route("/bulk", get(|| async {}))
+            .layer(middleware::from_fn_with_state((limiters, Arc::new(groups)), rate_limit_middleware));
+
+        // Ensure the requests to an unknown route are charged as point requests.
+        let addr = SocketAddr::from(([127, 0, 0, 1], 3030));
+        assert_eq!(status(&router, addr, "/unknown").await, StatusCode::NOT_FOUND);
+        assert_eq!(status(&router, addr, "/unknown").await, StatusCode::TOO_MANY_REQUESTS);
+        // Ensure the requests to a known route are charged to its route group.
+        assert_eq!(status(&router, addr, "/bulk").await, StatusCode::OK);
+        assert_eq!(status(&router, addr, "/bulk").await, StatusCode::OK);
+        assert_eq!(status(&router, addr, "/bulk").await, StatusCode::TOO_MANY_REQUESTS);
+    }
+}
@@ -18,11 +18,45 @@
 #[macro_use]
 extern crate tracing;
 
+mod address;
+use address::AddressIndex;
+
+mod admin;
+
+mod bft;
+
+mod events;
+
+mod fee;
+
+mod health;
+
 mod helpers;
 pub use helpers::*;
 
+#[cfg(feature = "history")]
+mod mapping_history;
+#[cfg(feature = "history")]
+use mapping_history::MappingHistory;
+
+mod mapping_keys;
+use mapping_keys::MappingKeys;
+
+mod memory_pool;
+
+mod openapi;
+use openapi::{Access, ApiRouter, Operation, QueryParam};
+
+mod program;
+
 mod routes;
 
+mod rpc;
+
+mod simulate;
+
+mod stream;
+
 use snarkos_node_consensus::Consensus;
 use snarkos_node_router::{
     Routing,
@@ -34,26 +68,30 @@ use snarkvm::{
     prelude::{Ledger, Network, cfg_into_iter, store::ConsensusStorage},
 };
 
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 use axum::{
     Json,
     body::Body,
     extract::{ConnectInfo, DefaultBodyLimit, Path, Query, State},
-    http::{Method, Request, StatusCode, header::CONTENT_TYPE},
+    http::{HeaderMap, Request, StatusCode, header::CONTENT_TYPE},
     middleware,
     middleware::Next,
     response::Response,
     routing::{get, post},
 };
 use axum_extra::response::ErasedJson;
+use axum_server::tls_rustls::RustlsConfig;
 use parking_lot::Mutex;
-use std::{net::SocketAddr, sync::Arc};
-use tokio::{net::TcpListener, task::JoinHandle};
-use tower_governor::{GovernorLayer, governor::GovernorConfigBuilder};
-use tower_http::{
-    cors::{Any, CorsLayer},
-    trace::TraceLayer,
+use std::{
+    net::SocketAddr,
+    sync::{Arc, atomic::AtomicBool},
+    time::Duration,
 };
+use tokio::{net::TcpListener, sync::watch, task::JoinHandle};
+use tower_http::trace::TraceLayer;
+
+/// The interval at which the TLS certificate and key files are checked for changes.
+const TLS_RELOAD_INTERVAL: Duration = Duration::from_secs(10);
 
 /// A REST API server for the ledger.
 #[derive(Clone)]
@@ -64,6 +102,23 @@ pub struct Rest<N: Network, C: ConsensusStorage<N>, R: Routing<N>> {
     ledger: Ledger<N, C>,
     /// The node (routing).
     routing: Arc<R>,
+    /// The receiver for the latest block height, notified every time the ledger advances.
+    block_height: watch::Receiver<u32>,
+    /// The index from addresses to their transitions, if enabled.
+    address_index: Option<Arc<AddressIndex<N>>>,
+    /// The serialized responses of the most recently requested blocks.
+    block_cache: Arc<BlockCache<N>>,
+    /// The index of the changes to the program mappings, if enabled.
+    #[cfg(feature = "history")]
+    mapping_history: Option<Arc<MappingHistory<N>>>,
+    /// The index of the keys of the program mappings, if enabled.
+    mapping_keys: Option<Arc<MappingKeys<N>>>,
+    /// The shutdown signal of the node.
+    shutdown: Arc<AtomicBool>,
+    /// The JWT settings.
+    jwt: Arc<JwtConfig>,
+    /// The routes of the server, as described in the OpenAPI document.
+    operations: Arc<Vec<Operation>>,
     /// The server handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
 }
@@ -72,15 +127,68 @@ impl<N: Network, C: 'static + ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R>
     /// Initializes a new instance of the server.
     pub async fn start(
         rest_ip: SocketAddr,
-        rest_rps: u32,
+        rest_config: RestConfig,
         consensus: Option<Consensus<N>>,
         ledger: Ledger<N, C>,
         routing: Arc<R>,
+        block_height: watch::Receiver<u32>,
+        shutdown: Arc<AtomicBool>,
     ) -> Result<Self> {
+        let storage_mode = ledger.vm().finalize_store().storage_mode().clone();
+        // Open the address index, if enabled.
+        let address_index = match rest_config.address_index {
+            true => Some(Arc::new(AddressIndex::open(storage_mode.clone())?)),
+            false => None,
+        };
+        // Open the mapping history index, if enabled.
+        #[cfg(feature = "history")]
+        let mapping_history = match rest_config.mapping_history {
+            true => Some(Arc::new(MappingHistory::open(storage_mode.clone())?)),
+            false => None,
+        };
+        #[cfg(not(feature = "history"))]
+        if rest_config.mapping_history {
+            warn!("The mapping history is not enabled, as the node was built without the `history` feature");
+        }
+        // Open the mapping key index, if enabled.
+        let mapping_keys = match rest_config.mapping_index {
+            true => Some(Arc::new(MappingKeys::open(storage_mode)?)),
+            false => None,
+        };
+
         // Initialize the server.
-        let mut server = Self { consensus, ledger, routing, handles: Default::default() };
+        let mut server = Self {
+            consensus,
+            ledger,
+            routing,
+            block_height,
+            address_index,
+            block_cache: Default::default(),
+            #[cfg(feature = "history")]
+            mapping_history,
+            mapping_keys,
+            shutdown,
+            jwt: rest_config.jwt.clone(),
+            operations: Default::default(),
+            handles: Default::default(),
+        };
+        // Index the blocks as they are committed, if the address index is enabled.
+        if let Some(index) = server.address_index.clone() {
+            server.handles.lock().push(tokio::spawn(server.clone().maintain_address_index(index)));
+        }
+        // Record the changes to the mappings as the blocks are committed, if the mapping history is enabled.
+        #[cfg(feature = "history")]
+        if let Some(history) = server.mapping_history.clone() {
+            server.handles.lock().push(tokio::spawn(server.clone().maintain_mapping_history(history)));
+        }
+        // Index the keys of the mappings as the blocks are committed, if the mapping key index is enabled.
+        if let Some(index) = server.mapping_keys.clone() {
+            server.handles.lock().push(tokio::spawn(server.clone().maintain_mapping_keys(index)));
+        }
+        // Reload the JWT revocation file in the background.
+        server.handles.lock().push(tokio::spawn(reload_jwt_revocations(server.jwt.clone())));
         // Spawn the server.
-        server.spawn_server(rest_ip, rest_rps).await;
+        server.spawn_server(rest_ip, rest_config).await?;
         // Return the server.
         Ok(server)
     }
@@ -99,108 +207,176 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
 }
 
 impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
-    async fn spawn_server(&mut self, rest_ip: SocketAddr, rest_rps: u32) {
-        let cors = CorsLayer::new()
-            .allow_origin(Any)
-            .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
-            .allow_headers([CONTENT_TYPE]);
-
-        // Log the REST rate limit per IP.
-        debug!("REST rate limit per IP - {rest_rps} RPS");
-
-        // Prepare the rate limiting setup.
-        let governor_config = Box::new(
-            GovernorConfigBuilder::default()
-                .per_nanosecond((1_000_000_000 / rest_rps) as u64)
-                .burst_size(rest_rps)
-                .error_handler(|error| {
-                    // Properly return a 429 Too Many Requests error
-                    let error_message = error.to_string();
-                    let mut response = Response::new(error_message.clone().into());
-                    *response.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
-                    if error_message.contains("Too Many Requests") {
-                        *response.status_mut() = StatusCode::TOO_MANY_REQUESTS;
-                    }
-                    response
-                })
-                .finish()
-                .expect("Couldn't set up rate limiting for the REST server!"),
+    async fn spawn_server(&mut self, rest_ip: SocketAddr, rest_config: RestConfig) -> Result<()> {
+        let cors = rest_config.cors.to_layer()?;
+
+        // Initialize the rate limiters.
+        let rest_limits = &rest_config.rate_limits;
+        let limiters = Arc::new(RateLimiters::new(rest_limits)?);
+
+        // Log the REST rate limits.
+        debug!(
+            "REST rate limits per IP - {} RPS (point), {} RPS (bulk), {} RPS (broadcast)",
+            rest_limits.point, rest_limits.bulk, rest_limits.broadcast
         );
+        debug!("REST rate limits per API key - {} API keys configured", rest_limits.api_keys.len());
 
-        // Get the network being used.
-        let network = match N::ID {
-            snarkvm::console::network::MainnetV0::ID => "mainnet",
-            snarkvm::console::network::TestnetV0::ID => "testnet",
-            snarkvm::console::network::CanaryV0::ID => "canary",
-            unknown_id => {
-                eprintln!("Unknown network ID ({unknown_id})");
-                return;
+        // Periodically forget the IPs whose rate limits were fully replenished.
+        let retain_limiters = limiters.clone();
+        self.handles.lock().push(tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                retain_limiters.retain_recent();
             }
+        }));
+
+        // Get the network being used.
+        let Some(network) = network_name::<N>() else {
+            error!("Unknown network ID ({})", N::ID);
+            return Ok(());
         };
 
         let router = {
-            let routes = axum::Router::new()
-
-            // All the endpoints before the call to `route_layer` are protected with JWT auth.
-            .route(&format!("/{network}/node/address"), get(Self::get_node_address))
-            .route(&format!("/{network}/program/:id/mapping/:name"), get(Self::get_mapping_values))
-            .route_layer(middleware::from_fn(auth_middleware))
-
-            // GET ../block/..
-            .route(&format!("/{network}/block/height/latest"), get(Self::get_block_height_latest))
-            .route(&format!("/{network}/block/hash/latest"), get(Self::get_block_hash_latest))
-            .route(&format!("/{network}/block/latest"), get(Self::get_block_latest))
-            .route(&format!("/{network}/block/:height_or_hash"), get(Self::get_block))
-            // The path param here is actually only the height, but the name must match the route
-            // above, otherwise there'll be a conflict at runtime.
-            .route(&format!("/{network}/block/:height_or_hash/transactions"), get(Self::get_block_transactions))
-
-            // GET and POST ../transaction/..
-            .route(&format!("/{network}/transaction/:id"), get(Self::get_transaction))
-            .route(&format!("/{network}/transaction/confirmed/:id"), get(Self::get_confirmed_transaction))
-            .route(&format!("/{network}/transaction/broadcast"), post(Self::transaction_broadcast))
-
-            // POST ../solution/broadcast
-            .route(&format!("/{network}/solution/broadcast"), post(Self::solution_broadcast))
-
-            // GET ../find/..
-            .route(&format!("/{network}/find/blockHash/:tx_id"), get(Self::find_block_hash))
-            .route(&format!("/{network}/find/blockHeight/:state_root"), get(Self::find_block_height_from_state_root))
-            .route(&format!("/{network}/find/transactionID/deployment/:program_id"), get(Self::find_transaction_id_from_program_id))
-            .route(&format!("/{network}/find/transactionID/:transition_id"), get(Self::find_transaction_id_from_transition_id))
-            .route(&format!("/{network}/find/transitionID/:input_or_output_id"), get(Self::find_transition_id))
-
-            // GET ../peers/..
-            .route(&format!("/{network}/peers/count"), get(Self::get_peers_count))
-            .route(&format!("/{network}/peers/all"), get(Self::get_peers_all))
-            .route(&format!("/{network}/peers/all/metrics"), get(Self::get_peers_all_metrics))
-
-            // GET ../program/..
-            .route(&format!("/{network}/program/:id"), get(Self::get_program))
-            .route(&format!("/{network}/program/:id/mappings"), get(Self::get_mapping_names))
-            .route(&format!("/{network}/program/:id/mapping/:name/:key"), get(Self::get_mapping_value))
-
-            // GET misc endpoints.
-            .route(&format!("/{network}/blocks"), get(Self::get_blocks))
-            .route(&format!("/{network}/height/:hash"), get(Self::get_height))
-            .route(&format!("/{network}/memoryPool/transmissions"), get(Self::get_memory_pool_transmissions))
-            .route(&format!("/{network}/memoryPool/solutions"), get(Self::get_memory_pool_solutions))
-            .route(&format!("/{network}/memoryPool/transactions"), get(Self::get_memory_pool_transactions))
-            .route(&format!("/{network}/statePath/:commitment"), get(Self::get_state_path_for_commitment))
-            .route(&format!("/{network}/stateRoot/latest"), get(Self::get_state_root_latest))
-            .route(&format!("/{network}/stateRoot/:height"), get(Self::get_state_root))
-            .route(&format!("/{network}/committee/latest"), get(Self::get_committee_latest))
-            .route(&format!("/{network}/committee/:height"), get(Self::get_committee))
-            .route(&format!("/{network}/delegators/:validator"), get(Self::get_delegators_for_validator));
-
-            // If the `history` feature is enabled, enable the additional endpoint.
+            let jwt = &self.jwt;
+
+            // The endpoints reading the mappings and the node address require a JWT with the `read-mapping` scope.
+            let read_mapping_routes = ApiRouter::new()
+                .get(&format!("/{network}/node/address"), RouteGroup::Point, Self::get_node_address)
+                .get(&format!("/{network}/program/:id/mapping/:name"), RouteGroup::Bulk, Self::get_mapping_values)
+                .access(Access::Scoped(Scope::ReadMapping), jwt);
+
+            // The admin endpoints require a JWT with the `admin` scope.
+            let admin_routes = ApiRouter::new()
+                .post(&format!("/{network}/admin/peers/connect"), RouteGroup::Point, Self::admin_connect_peer)
+                .post(&format!("/{network}/admin/peers/disconnect"), RouteGroup::Point, Self::admin_disconnect_peer)
+                .get(&format!("/{network}/admin/peers/trusted"), RouteGroup::Point, Self::admin_get_trusted_peers)
+                .post(&format!("/{network}/admin/peers/trusted/add"), RouteGroup::Point, Self::admin_add_trusted_peer)
+                .post(
+                    &format!("/{network}/admin/peers/trusted/remove"),
+                    RouteGroup::Point,
+                    Self::admin_remove_trusted_peer,
+                )
+                .get(&format!("/{network}/admin/bans"), RouteGroup::Point, Self::admin_get_banned_ips)
+                .post(&format!("/{network}/admin/bans/lift"), RouteGroup::Point, Self::admin_lift_ban)
+                .post(&format!("/{network}/admin/tokens/revoke"), RouteGroup::Point, Self::admin_revoke_token)
+                .post(&format!("/{network}/admin/shutdown"), RouteGroup::Point, Self::admin_shutdown)
+                .access(Access::Scoped(Scope::Admin), jwt);
+
+            // The endpoints that submit transmissions require a JWT with the `broadcast` scope, if configured.
+            let broadcast_routes = ApiRouter::new()
+                .post(&format!("/{network}/transaction/broadcast"), RouteGroup::Broadcast, Self::transaction_broadcast)
+                .post(&format!("/{network}/solution/broadcast"), RouteGroup::Broadcast, Self::solution_broadcast)
+                .access(Access::Broadcast, jwt);
+
+            let routes = ApiRouter::new()
+                .merge(read_mapping_routes)
+                .merge(admin_routes)
+                .merge(broadcast_routes)
+
+                // POST ../rpc, which charges each of its calls to the rate limit of its route group, and checks the access
+                // of each call as the route its method dispatches to does.
+                .post_per_call(&format!("/{network}/rpc"), Self::rpc)
+
+                // GET ../block/..
+                .get(&format!("/{network}/block/height/latest"), RouteGroup::Point, Self::get_block_height_latest)
+                .get(&format!("/{network}/block/hash/latest"), RouteGroup::Point, Self::get_block_hash_latest)
+                .get(&format!("/{network}/block/latest"), RouteGroup::Point, Self::get_block_latest)
+                .get(&format!("/{network}/block/:height_or_hash"), RouteGroup::Point, Self::get_block)
+                // The path param here is actually only the height, but the name must match the route
+                // above, otherwise there'll be a conflict at runtime.
+                .get(&format!("/{network}/block/:height_or_hash/transactions"), RouteGroup::Point, Self::get_block_transactions)
+
+                // GET and POST ../transaction/..
+                .get(&format!("/{network}/transaction/:id"), RouteGroup::Point, Self::get_transaction)
+                .get(&format!("/{network}/transaction/confirmed/:id"), RouteGroup::Point, Self::get_confirmed_transaction)
+                .get(&format!("/{network}/transaction/status/:id"), RouteGroup::Bulk, Self::get_transaction_status)
+                .post(&format!("/{network}/transaction/simulate"), RouteGroup::Bulk, Self::transaction_simulate)
+
+                // GET ../address/..
+                .get(&format!("/{network}/address/:address/transitions"), RouteGroup::Bulk, Self::get_address_transitions)
+
+                // GET ../bft/..
+                .get(&format!("/{network}/bft/rounds"), RouteGroup::Point, Self::get_bft_rounds)
+                .get(&format!("/{network}/bft/dag"), RouteGroup::Bulk, Self::get_bft_dag)
+                .get(&format!("/{network}/bft/certificates/:round"), RouteGroup::Bulk, Self::get_bft_certificates_for_round)
+                .get(&format!("/{network}/bft/certificate/:id"), RouteGroup::Point, Self::get_bft_certificate)
+                .get(&format!("/{network}/bft/leader"), RouteGroup::Point, Self::get_bft_leader)
+                .get(&format!("/{network}/bft/committee/:round"), RouteGroup::Point, Self::get_bft_committee_lookback)
+                .get(&format!("/{network}/bft/proposal"), RouteGroup::Point, Self::get_bft_proposal)
+
+                // GET ../find/..
+                .get(&format!("/{network}/find/blockHash/:tx_id"), RouteGroup::Point, Self::find_block_hash)
+                .get(&format!("/{network}/find/blockHeight/:state_root"), RouteGroup::Point, Self::find_block_height_from_state_root)
+                .get(&format!("/{network}/find/transactionID/deployment/:program_id"), RouteGroup::Point, Self::find_transaction_id_from_program_id)
+                .get(&format!("/{network}/find/transactionID/:transition_id"), RouteGroup::Point, Self::find_transaction_id_from_transition_id)
+                .get(&format!("/{network}/find/transitionID/:input_or_output_id"), RouteGroup::Point, Self::find_transition_id)
+
+                // GET ../peers/..
+                .get(&format!("/{network}/peers/count"), RouteGroup::Point, Self::get_peers_count)
+                .get(&format!("/{network}/peers/all"), RouteGroup::Point, Self::get_peers_all)
+                .get(&format!("/{network}/peers/all/metrics"), RouteGroup::Point, Self::get_peers_all_metrics)
+
+                // GET ../program/..
+                .get(&format!("/{network}/program/:id"), RouteGroup::Point, Self::get_program)
+                .get(&format!("/{network}/program/:id/metadata"), RouteGroup::Bulk, Self::get_program_metadata)
+                .get(&format!("/{network}/program/:id/mappings"), RouteGroup::Point, Self::get_mapping_names)
+                .get(&format!("/{network}/program/:id/mapping/:name/:key"), RouteGroup::Point, Self::get_mapping_value)
+
+                // GET ../memoryPool/..
+                .get(&format!("/{network}/memoryPool/transmissions"), RouteGroup::Bulk, Self::get_memory_pool_transmissions)
+                .get(&format!("/{network}/memoryPool/solutions"), RouteGroup::Bulk, Self::get_memory_pool_solutions)
+                .get(&format!("/{network}/memoryPool/transactions"), RouteGroup::Bulk, Self::get_memory_pool_transactions)
+                .get(&format!("/{network}/memoryPool/transaction/:id"), RouteGroup::Bulk, Self::get_memory_pool_transaction)
+                .get(&format!("/{network}/memoryPool/solution/:id"), RouteGroup::Bulk, Self::get_memory_pool_solution)
+                .get(&format!("/{network}/memoryPool/entries"), RouteGroup::Bulk, Self::get_memory_pool_entries)
+                .get(&format!("/{network}/memoryPool/counts"), RouteGroup::Bulk, Self::get_memory_pool_counts)
+                .get(&format!("/{network}/memoryPool/config"), RouteGroup::Bulk, Self::get_memory_pool_config)
+
+                // GET and POST ../fee/estimate
+                .get(&format!("/{network}/fee/estimate"), RouteGroup::Bulk, Self::get_fee_estimate)
+                .post(&format!("/{network}/fee/estimate"), RouteGroup::Bulk, Self::post_fee_estimate)
+
+                // GET misc endpoints.
+                .get(&format!("/{network}/blocks"), RouteGroup::Bulk, Self::get_blocks)
+                .get(&format!("/{network}/blocks/stream"), RouteGroup::Bulk, Self::get_blocks_stream)
+                .get(&format!("/{network}/height/:hash"), RouteGroup::Point, Self::get_height)
+                .get(&format!("/{network}/statePath/:commitment"), RouteGroup::Point, Self::get_state_path_for_commitment)
+                .get(&format!("/{network}/stateRoot/latest"), RouteGroup::Point, Self::get_state_root_latest)
+                .get(&format!("/{network}/stateRoot/:height"), RouteGroup::Point, Self::get_state_root)
+                .get(&format!("/{network}/committee/latest"), RouteGroup::Point, Self::get_committee_latest)
+                .get(&format!("/{network}/committee/:height"), RouteGroup::Point, Self::get_committee)
+                .get(&format!("/{network}/delegators/:validator"), RouteGroup::Bulk, Self::get_delegators_for_validator)
+                .get(&format!("/{network}/events"), RouteGroup::Bulk, Self::get_events)
+                .get(&format!("/{network}/openapi.json"), RouteGroup::Point, Self::get_openapi)
+
+                // GET /health/..
+                .get("/health/live", RouteGroup::Point, Self::get_health_live)
+                .get("/health/ready", RouteGroup::Point, Self::get_health_ready);
+
+            // If the `history` feature is enabled, enable the additional endpoints.
             #[cfg(feature = "history")]
-            let routes =
-                routes.route(&format!("/{network}/block/:blockHeight/history/:mapping"), get(Self::get_history));
+            let routes = routes
+                .get(&format!("/{network}/block/:blockHeight/history/:mapping"), RouteGroup::Bulk, Self::get_history)
+                .get(
+                    &format!("/{network}/program/:id/mapping/:name/:key/history"),
+                    RouteGroup::Bulk,
+                    Self::get_mapping_history,
+                );
+
+            // Record the routes for the OpenAPI document, along with the route groups they are rate limited by.
+            let (routes, operations) = routes.into_parts();
+            let route_groups: RouteGroups = operations
+                .iter()
+                .map(|operation| ((operation.method.clone(), operation.path.clone()), operation.group))
+                .collect();
+            self.operations = Arc::new(operations);
 
             routes
             // Pass in `Rest` to make things convenient.
             .with_state(self.clone())
+            // Rate limit the requests by their API key, or by IP and route group, before checking their access.
+            // Note: The requests to unknown routes are rate limited as point requests.
+            .layer(middleware::from_fn_with_state((limiters, Arc::new(route_groups)), rate_limit_middleware))
             // Enable tower-http tracing.
             .layer(TraceLayer::new_for_http())
             // Custom logging.
@@ -209,18 +385,59 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
             .layer(cors)
             // Cap body size at 512KiB.
             .layer(DefaultBodyLimit::max(512 * 1024))
-            .layer(GovernorLayer {
-                // We can leak this because it is created only once and it persists.
-                config: Box::leak(governor_config),
-            })
         };
 
-        let rest_listener = TcpListener::bind(rest_ip).await.unwrap();
-        self.handles.lock().push(tokio::spawn(async move {
-            axum::serve(rest_listener, router.into_make_service_with_connect_info::<SocketAddr>())
-                .await
-                .expect("couldn't start rest server");
-        }))
+        // Bind the listener before spawning the server, so that an unavailable address fails the startup.
+        let rest_listener = TcpListener::bind(rest_ip)
+            .await
+            .map_err(|e| anyhow!("Unable to bind the REST server to '{rest_ip}' - {e}"))?;
+        let make_service = router.into_make_service_with_connect_info::<SocketAddr>();
+        match rest_config.tls {
+            // Serve HTTPS, reloading the certificate whenever it is replaced on disk.
+            Some(tls) => {
+                let rustls_config = RustlsConfig::from_pem_file(&tls.cert_path, &tls.key_path)
+                    .await
+                    .map_err(|e| anyhow!("Unable to load the REST TLS certificate - {e}"))?;
+                let rest_listener = rest_listener.into_std()?;
+                self.handles.lock().push(tokio::spawn(reload_tls_certificate(rustls_config.clone(), tls)));
+                self.handles.lock().push(tokio::spawn(async move {
+                    if let Err(error) =
+                        axum_server::from_tcp_rustls(rest_listener, rustls_config).serve(make_service).await
+                    {
+                        error!("The REST server stopped - {error}");
+                    }
+                }));
+            }
+            // Serve plain HTTP.
+            None => {
+                self.handles.lock().push(tokio::spawn(async move {
+                    if let Err(error) = axum::serve(rest_listener, make_service).await {
+                        error!("The REST server stopped - {error}");
+                    }
+                }));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reloads the TLS certificate and key whenever either file is replaced on disk.
+async fn reload_tls_certificate(rustls_config: RustlsConfig, tls: TlsConfig) {
+    let mut last_modified = tls.modified();
+    loop {
+        tokio::time::sleep(TLS_RELOAD_INTERVAL).await;
+        let modified = tls.modified();
+        if modified == last_modified {
+            continue;
+        }
+        // Note: If the files are mid-replacement, the reload fails and is retried on the next interval.
+        match rustls_config.reload_from_pem_file(&tls.cert_path, &tls.key_path).await {
+            Ok(()) => {
+                info!("Reloaded the REST TLS certificate");
+                last_modified = modified;
+            }
+            Err(error) => warn!("Unable to reload the REST TLS certificate - {error}"),
+        }
     }
 }
 
@@ -234,6 +451,16 @@ async fn log_middleware(
     Ok(next.run(request).await)
 }
 
+/// Returns the name of the network, as used in the route paths.
+fn network_name<N: Network>() -> Option<&'static str> {
+    match N::ID {
+        snarkvm::console::network::MainnetV0::ID => Some("mainnet"),
+        snarkvm::console::network::TestnetV0::ID => Some("testnet"),
+        snarkvm::console::network::CanaryV0::ID => Some("canary"),
+        _ => None,
+    }
+}
+
 /// Formats an ID into a truncated identifier (for logging purposes).
 pub fn fmt_id(id: impl ToString) -> String {
     let id = id.to_string();
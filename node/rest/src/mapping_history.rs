@@ -0,0 +1,550 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use snarkvm::prelude::{FromBytes, Identifier, Plaintext, ToBytes, Value, block::Block};
+
+use aleo_std::StorageMode;
+use anyhow::bail;
+use indexmap::IndexMap;
+use rocksdb::WriteBatch;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{collections::HashMap, marker::PhantomData};
+
+/// The prefix of the keys of the changes, followed by the key ID of the entry and the height.
+const CHANGE_PREFIX: u8 = 0;
+/// The prefix of the keys of the entries, followed by the key ID, and holding the program, the mapping and the key.
+const KEY_PREFIX: u8 = 1;
+/// The prefix of the keys of the entries whose key ID is known, followed by the mapping ID and the key.
+const KNOWN_PREFIX: u8 = 2;
+
+/// The tag of a change that removed the entry.
+const REMOVED: u8 = 0;
+/// The tag of a change that wrote a value, followed by the value ID and the value.
+const WRITTEN: u8 = 1;
+/// The tag of a change that wrote a value which is unknown, followed by the value ID.
+const WRITTEN_UNKNOWN: u8 = 2;
+
+/// The query object for `get_mapping_history`.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct MappingHistoryQuery {
+    /// The starting block height (inclusive).
+    start: Option<u32>,
+    /// The ending block height (exclusive).
+    end: Option<u32>,
+}
+
+impl MappingHistoryQuery {
+    /// The query parameters, as documented in the OpenAPI document.
+    pub(crate) const PARAMS: &'static [QueryParam] = &[
+        QueryParam::new("start", "integer", false, "The starting block height (inclusive)"),
+        QueryParam::new("end", "integer", false, "The ending block height (exclusive)"),
+    ];
+}
+
+/// A change to a mapping entry.
+enum EntryChange<N: Network> {
+    /// The entry was removed.
+    Removed,
+    /// The entry was written, with the given value ID, and the value if it is known.
+    Written { value_id: Field<N>, value: Option<Value<N>> },
+}
+
+impl<N: Network> EntryChange<N> {
+    /// Returns the change, encoded as stored in the index.
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        match self {
+            Self::Removed => Ok(vec![REMOVED]),
+            Self::Written { value_id, value: Some(value) } => {
+                let mut bytes = vec![WRITTEN];
+                value_id.write_le(&mut bytes)?;
+                value.write_le(&mut bytes)?;
+                Ok(bytes)
+            }
+            Self::Written { value_id, value: None } => {
+                let mut bytes = vec![WRITTEN_UNKNOWN];
+                value_id.write_le(&mut bytes)?;
+                Ok(bytes)
+            }
+        }
+    }
+
+    /// Returns the change encoded in the given bytes.
+    fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        match bytes.split_first() {
+            Some((&REMOVED, [])) => Ok(Self::Removed),
+            Some((&WRITTEN, mut bytes)) => {
+                Ok(Self::Written { value_id: Field::read_le(&mut bytes)?, value: Some(Value::from_bytes_le(bytes)?) })
+            }
+            Some((&WRITTEN_UNKNOWN, value_id)) => {
+                Ok(Self::Written { value_id: Field::from_bytes_le(value_id)?, value: None })
+            }
+            _ => bail!("Invalid change in the mapping history"),
+        }
+    }
+
+    /// Returns `true` if both changes leave the entry with the same value.
+    fn has_same_value(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Removed, Self::Removed) => true,
+            (Self::Written { value_id, .. }, Self::Written { value_id: other_id, .. }) => value_id == other_id,
+            _ => false,
+        }
+    }
+
+    /// Returns the change at the given height, as returned by `get_mapping_history`.
+    /// Note: The value of an entry that was written, but not removed, is `null` if it is unknown.
+    fn to_json(&self, height: u32) -> serde_json::Value {
+        match self {
+            Self::Removed => json!({ "height": height, "removed": true, "value": null }),
+            Self::Written { value, .. } => json!({ "height": height, "removed": false, "value": value }),
+        }
+    }
+}
+
+/// The index of the changes to the values of every program mapping entry.
+/// The index is built from the finalize operations of each block, in the order of the confirmed transactions,
+/// and is persisted next to the ledger, catching up with the ledger from where it left off.
+/// A write that leaves the value of an entry unchanged is not recorded.
+///
+/// The finalize operations only commit to the keys and values, so the key of a change is found among the keys passed
+/// to the finalize logic of the block, and otherwise among the keys recorded before. The key of an entry is recorded
+/// when it is found, and when the history of the entry is requested, as the keys derived within the finalize logic
+/// are never passed to it. The value of a change is read from the finalize store if its key is known, and kept only
+/// if it is the one the block wrote, as no later block overwrote it; the blocks are indexed as they are committed,
+/// so this is usually the case. Otherwise, the value is found only if the entry still holds it when its history is
+/// requested.
+pub(crate) struct MappingHistory<N: Network> {
+    /// The persisted index.
+    store: IndexStore,
+    /// The lock held while a block is indexed, or a key is recorded, so that they are written one at a time.
+    lock: Mutex<()>,
+    /// PhantomData.
+    _phantom: PhantomData<N>,
+}
+
+impl<N: Network> MappingHistory<N> {
+    /// Opens the mapping history index of the ledger with the given storage mode.
+    pub(crate) fn open(storage_mode: StorageMode) -> Result<Self> {
+        let store = IndexStore::open(&index_store_path(MAPPING_HISTORY_NAME, N::ID, storage_mode))?;
+        Ok(Self { store, lock: Default::default(), _phantom: PhantomData })
+    }
+
+    /// Records the changes of the blocks committed since the last call, starting from the genesis block.
+    fn index_new_blocks<C: ConsensusStorage<N>>(&self, ledger: &Ledger<N, C>) -> Result<()> {
+        loop {
+            let _lock = self.lock.lock();
+            let height = self.store.next_height()?;
+            if height > ledger.latest_height() {
+                return Ok(());
+            }
+            self.index_block(ledger, &ledger.get_block(height)?)?;
+        }
+    }
+
+    /// Records the changes of the given block.
+    /// Note: The mappings are never read in full, so the values of the entries whose key is unknown are unknown.
+    fn index_block<C: ConsensusStorage<N>>(&self, ledger: &Ledger<N, C>, block: &Block<N>) -> Result<()> {
+        // Collect the writes of the block, keeping the last write to each entry.
+        let mut writes = IndexMap::new();
+        for write in mapping_writes(block) {
+            writes.insert(write.key_id, write);
+        }
+
+        // Collect the mappings written to, among the mappings of the programs whose finalize logic the block ran.
+        let arguments = collect_finalize_arguments(block);
+        let mut mappings = IndexMap::new();
+        for program_id in arguments.keys() {
+            let names =
+                ledger.vm().process().read().get_program(*program_id)?.mappings().keys().copied().collect::<Vec<_>>();
+            for name in names {
+                let mapping_id = to_mapping_id(program_id, &name)?;
+                if writes.values().any(|write| write.mapping_id == mapping_id) {
+                    mappings.insert(mapping_id, (*program_id, name));
+                }
+            }
+        }
+
+        let mut entries = Vec::with_capacity(writes.len());
+        // Find the keys of the writes among the keys passed to the finalize logic of the block.
+        let mut keys = HashMap::new();
+        for (mapping_id, (program_id, name)) in &mappings {
+            for key in arguments.get(program_id).into_iter().flat_map(|candidates| candidates.values()) {
+                let key_id = to_key_id(program_id, name, key)?;
+                if writes.contains_key(&key_id) && !keys.contains_key(&key_id) {
+                    entries.extend(self.key_entries(mapping_id, &key_id, program_id, name, key)?);
+                    keys.insert(key_id, (*program_id, *name, key.clone()));
+                }
+            }
+        }
+        // Find the remaining keys among the keys recorded before.
+        for key_id in writes.keys() {
+            if !keys.contains_key(key_id) {
+                if let Some(key) = self.key(key_id)? {
+                    keys.insert(*key_id, key);
+                }
+            }
+        }
+
+        // Record the change of each written entry, unless it leaves the value unchanged.
+        for (key_id, write) in writes {
+            let change = match write.value_id {
+                None => EntryChange::Removed,
+                Some(value_id) => {
+                    let value = match keys.get(&key_id) {
+                        Some((program_id, name, key)) => {
+                            match ledger.vm().finalize_store().get_value_confirmed(*program_id, *name, key)? {
+                                // Note: The value is kept only if it is the one the block wrote.
+                                Some(value) if to_value_id(&key_id, &value)? == value_id => Some(value),
+                                _ => None,
+                            }
+                        }
+                        None => None,
+                    };
+                    EntryChange::Written { value_id, value }
+                }
+            };
+            if self.last_change(&key_id)?.is_some_and(|(_, last)| last.has_same_value(&change)) {
+                continue;
+            }
+            entries.push((change_key(&key_id, block.height())?, change.to_bytes()?));
+        }
+
+        // Store the changes of the block.
+        self.store.insert_block(block.height(), entries)
+    }
+
+    /// Records the given key of the given mapping, so that the values the next blocks write to its entry are known.
+    fn record_key(&self, program_id: &ProgramID<N>, name: &Identifier<N>, key: &Plaintext<N>) -> Result<()> {
+        let _lock = self.lock.lock();
+        let (mapping_id, key_id) = (to_mapping_id(program_id, name)?, to_key_id(program_id, name, key)?);
+        let mut batch = WriteBatch::default();
+        for (key, value) in self.key_entries(&mapping_id, &key_id, program_id, name, key)? {
+            batch.put(key, value);
+        }
+        self.store.write(batch)
+    }
+
+    /// Returns the program, the mapping and the key of the entry with the given key ID, if it is known.
+    #[allow(clippy::type_complexity)]
+    fn key(&self, key_id: &Field<N>) -> Result<Option<(ProgramID<N>, Identifier<N>, Plaintext<N>)>> {
+        let Some(bytes) = self.store.get(&entry_key(key_id)?)? else {
+            return Ok(None);
+        };
+        let mut bytes = &bytes[..];
+        Ok(Some((ProgramID::read_le(&mut bytes)?, Identifier::read_le(&mut bytes)?, Plaintext::read_le(&mut bytes)?)))
+    }
+
+    /// Returns the entries recording the given key of the given mapping, unless they were already recorded.
+    fn key_entries(
+        &self,
+        mapping_id: &Field<N>,
+        key_id: &Field<N>,
+        program_id: &ProgramID<N>,
+        name: &Identifier<N>,
+        key: &Plaintext<N>,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        let known_key = known_key(mapping_id, key)?;
+        if self.store.get(&known_key)?.is_some() {
+            return Ok(Vec::new());
+        }
+        let mut value = program_id.to_bytes_le()?;
+        name.write_le(&mut value)?;
+        key.write_le(&mut value)?;
+        Ok(vec![(entry_key(key_id)?, value), (known_key, Vec::new())])
+    }
+
+    /// Returns the last change of the given entry, if any.
+    fn last_change(&self, key_id: &Field<N>) -> Result<Option<(u32, EntryChange<N>)>> {
+        let prefix = change_prefix(key_id)?;
+        self.store
+            .last_until(&prefix, &change_key(key_id, u32::MAX)?)?
+            .map(|entry| decode_change(&prefix, entry))
+            .transpose()
+    }
+
+    /// Returns the change in effect before the start height, and the changes in the given range, of the given entry.
+    #[allow(clippy::type_complexity)]
+    fn changes(
+        &self,
+        key_id: &Field<N>,
+        start: u32,
+        end: u32,
+    ) -> Result<(Option<(u32, EntryChange<N>)>, Vec<(u32, EntryChange<N>)>)> {
+        let prefix = change_prefix(key_id)?;
+
+        // Find the last change before the start height.
+        let in_effect = match start.checked_sub(1) {
+            Some(until) => self.store.last_until(&prefix, &change_key(key_id, until)?)?,
+            None => None,
+        };
+        let in_effect = in_effect.map(|entry| decode_change(&prefix, entry)).transpose()?;
+        // Collect the changes in the range.
+        let mut changes = Vec::new();
+        for entry in self.store.iter_from(&prefix, &change_key(key_id, start)?) {
+            let (height, change) = decode_change(&prefix, entry?)?;
+            if height >= end {
+                break;
+            }
+            changes.push((height, change));
+        }
+        Ok((in_effect, changes))
+    }
+
+    /// Returns the change in effect before the start height, and the changes in the given range, of the given entry.
+    /// The key of the entry is recorded, and the unknown values matching the value the entry holds are filled in.
+    #[allow(clippy::type_complexity)]
+    fn entry_history<C: ConsensusStorage<N>>(
+        &self,
+        ledger: &Ledger<N, C>,
+        program_id: &ProgramID<N>,
+        name: &Identifier<N>,
+        key: &Plaintext<N>,
+        start: u32,
+        end: u32,
+    ) -> Result<(Option<(u32, EntryChange<N>)>, Vec<(u32, EntryChange<N>)>)> {
+        self.record_key(program_id, name, key)?;
+        let key_id = to_key_id(program_id, name, key)?;
+        let (mut in_effect, mut changes) = self.changes(&key_id, start, end)?;
+        if let Some(current) = ledger.vm().finalize_store().get_value_confirmed(*program_id, *name, key)? {
+            let current_id = to_value_id(&key_id, &current)?;
+            for (_, change) in in_effect.iter_mut().chain(changes.iter_mut()) {
+                if let EntryChange::Written { value_id, value } = change {
+                    if value.is_none() && *value_id == current_id {
+                        *value = Some(current.clone());
+                    }
+                }
+            }
+        }
+        Ok((in_effect, changes))
+    }
+}
+
+/// Returns the height and the change of the given entry of the index, whose key starts with the given prefix.
+fn decode_change<N: Network>(prefix: &[u8], (key, value): (Box<[u8]>, Box<[u8]>)) -> Result<(u32, EntryChange<N>)> {
+    let height = u32::from_be_bytes(key[prefix.len()..].try_into()?);
+    Ok((height, EntryChange::from_bytes(&value)?))
+}
+
+/// Returns the key holding the program, the mapping and the key of the entry with the given key ID.
+fn entry_key<N: Network>(key_id: &Field<N>) -> Result<Vec<u8>> {
+    let mut key = vec![KEY_PREFIX];
+    key_id.write_le(&mut key)?;
+    Ok(key)
+}
+
+/// Returns the key marking the key ID of the given key of the mapping with the given mapping ID as known.
+fn known_key<N: Network>(mapping_id: &Field<N>, mapping_key: &Plaintext<N>) -> Result<Vec<u8>> {
+    let mut key = vec![KNOWN_PREFIX];
+    mapping_id.write_le(&mut key)?;
+    mapping_key.write_le(&mut key)?;
+    Ok(key)
+}
+
+/// Returns the prefix of the keys of the changes of the entry with the given key ID.
+fn change_prefix<N: Network>(key_id: &Field<N>) -> Result<Vec<u8>> {
+    let mut key = vec![CHANGE_PREFIX];
+    key_id.write_le(&mut key)?;
+    Ok(key)
+}
+
+/// Returns the key of the change of the entry with the given key ID at the given height.
+/// Note: The height is big-endian, so that the keys are ordered by height.
+fn change_key<N: Network>(key_id: &Field<N>, height: u32) -> Result<Vec<u8>> {
+    let mut key = change_prefix(key_id)?;
+    key.extend_from_slice(&height.to_be_bytes());
+    Ok(key)
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    /// Keeps the mapping history index up to date, recording the changes of the blocks as they are committed.
+    pub(crate) async fn maintain_mapping_history(self, history: Arc<MappingHistory<N>>) {
+        let mut block_height = self.block_height.clone();
+        loop {
+            // Record the changes of the blocks committed since the last iteration, from where the index left off.
+            let (ledger, history_) = (self.ledger.clone(), history.clone());
+            match tokio::task::spawn_blocking(move || history_.index_new_blocks(&ledger)).await {
+                Ok(Ok(())) => {}
+                Ok(Err(error)) => warn!("Failed to update the mapping history - {error}"),
+                Err(error) => warn!("Failed to update the mapping history - {error}"),
+            }
+            // Wait for the next block. If the sender was dropped, no more blocks will be committed.
+            if block_height.changed().await.is_err() {
+                return;
+            }
+        }
+    }
+
+    // GET /<network>/program/{programID}/mapping/{mappingName}/{mappingKey}/history?start={start}&end={end}
+    pub(crate) async fn get_mapping_history(
+        State(rest): State<Self>,
+        Path((id, name, key)): Path<(ProgramID<N>, Identifier<N>, Plaintext<N>)>,
+        Query(query): Query<MappingHistoryQuery>,
+    ) -> Result<ErasedJson, RestError> {
+        let Some(history) = rest.mapping_history.clone() else {
+            return Err(RestError("The mapping history is not enabled on this node".to_string()));
+        };
+        let start = query.start.unwrap_or(0);
+        let end = query.end.unwrap_or(u32::MAX);
+        // Ensure the end height is greater than the start height.
+        if start > end {
+            return Err(RestError("Invalid block range".to_string()));
+        }
+
+        // Retrieve the changes of the entry.
+        let (ledger, key_) = (rest.ledger.clone(), key.clone());
+        let get_changes = move || -> Result<_> {
+            let (in_effect, changes) = history.entry_history(&ledger, &id, &name, &key_, start, end)?;
+            Ok((in_effect, changes, history.store.next_height()?))
+        };
+        let (in_effect, changes, next_height) = match tokio::task::spawn_blocking(get_changes).await {
+            Ok(Ok(changes)) => changes,
+            Ok(Err(err)) => return Err(RestError(format!("Unable to read the mapping history - {err}"))),
+            Err(err) => return Err(RestError(format!("Unable to read the mapping history - {err}"))),
+        };
+
+        Ok(ErasedJson::pretty(json!({
+            "programId": id,
+            "mapping": name,
+            "key": key,
+            "indexedHeight": next_height.checked_sub(1),
+            // Note: The change in effect before the start height is `null` if the entry was never written.
+            "start": in_effect.map(|(height, change)| change.to_json(height)),
+            "changes": changes.iter().map(|(height, change)| change.to_json(*height)).collect::<Vec<_>>(),
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::{
+        ledger::store::{ConsensusStore, helpers::memory::ConsensusMemory},
+        prelude::{MainnetV0, PrivateKey, Program, VM, block::Transaction},
+        utilities::TestRng,
+    };
+
+    use std::str::FromStr;
+
+    type CurrentNetwork = MainnetV0;
+    type CurrentLedger = Ledger<CurrentNetwork, ConsensusMemory<CurrentNetwork>>;
+
+    /// A program whose finalize logic derives the keys of its mapping.
+    const PROGRAM: &str = r"
+program history_test.aleo;
+
+mapping values:
+    key as field.public;
+    value as u64.public;
+
+function set_value:
+    input r0 as u64.public;
+    input r1 as u64.public;
+    async set_value r0 r1 into r2;
+    output r2 as history_test.aleo/set_value.future;
+
+finalize set_value:
+    input r0 as u64.public;
+    input r1 as u64.public;
+    hash.bhp256 r0 into r2 as field;
+    set r1 into values[r2];
+";
+
+    #[test]
+    fn test_changes_of_derived_keys() {
+        let rng = &mut TestRng::default();
+
+        // Initialize a ledger, whose genesis block gives a balance to the given private key.
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let store = ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(None).unwrap();
+        let genesis = VM::from(store).unwrap().genesis_beacon(&private_key, rng).unwrap();
+        let ledger = CurrentLedger::load(genesis, StorageMode::Production).unwrap();
+
+        // Open an index, and index the genesis block.
+        let directory = tempfile::tempdir().unwrap();
+        let history = MappingHistory::<CurrentNetwork>::open(StorageMode::Custom(directory.path().join("a"))).unwrap();
+        history.index_new_blocks(&ledger).unwrap();
+
+        // Adds a block with the given transaction to the ledger, and indexes it as it is committed.
+        let rng_ = &mut TestRng::default();
+        let mut add_block = |transaction: Transaction<CurrentNetwork>| {
+            let block = ledger
+                .prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![transaction], rng_)
+                .unwrap();
+            ledger.advance_to_next_block(&block).unwrap();
+            history.index_new_blocks(&ledger).unwrap();
+        };
+
+        // Deploy the program.
+        let program = Program::<CurrentNetwork>::from_str(PROGRAM).unwrap();
+        add_block(ledger.vm().deploy(&private_key, &program, None, 0, None, rng).unwrap());
+        // Returns a transaction writing the given value to the entry.
+        let locator = (*program.id(), Identifier::from_str("set_value").unwrap());
+        let mut set_value = |value: &str| {
+            let inputs = [Value::from_str("7u64").unwrap(), Value::from_str(value).unwrap()];
+            ledger.vm().execute(&private_key, locator, inputs.into_iter(), None, 0, None, rng).unwrap()
+        };
+        // Write to the same entry in three consecutive blocks, the last one leaving the value unchanged.
+        for value in ["1u64", "2u64", "2u64"] {
+            add_block(set_value(value));
+        }
+
+        // Retrieve the key of the entry, which was derived within the finalize logic.
+        let name = Identifier::from_str("values").unwrap();
+        let entries = ledger.vm().finalize_store().get_mapping_confirmed(*program.id(), name).unwrap();
+        assert_eq!(entries.len(), 1);
+        let key = entries[0].0.clone();
+        let key_id = to_key_id(program.id(), &name, &key).unwrap();
+
+        // Returns the heights and the values of the given changes.
+        let values = |changes: Vec<(u32, EntryChange<CurrentNetwork>)>| {
+            changes
+                .into_iter()
+                .map(|(height, change)| match change {
+                    EntryChange::Written { value, .. } => (height, value.map(|value| value.to_string())),
+                    EntryChange::Removed => panic!("The entry was never removed"),
+                })
+                .collect::<Vec<_>>()
+        };
+        let changes =
+            |history: &MappingHistory<CurrentNetwork>| values(history.changes(&key_id, 0, u32::MAX).unwrap().1);
+
+        // Ensure the values are unknown, as the key was derived, and the unchanged value is not recorded.
+        assert_eq!(changes(&history), vec![(2, None), (3, None)]);
+        // Ensure the history of the entry fills in the value the entry holds.
+        let (in_effect, entry_changes) =
+            history.entry_history(&ledger, program.id(), &name, &key, 0, u32::MAX).unwrap();
+        assert!(in_effect.is_none());
+        assert_eq!(values(entry_changes), vec![(2, None), (3, Some("2u64".to_string()))]);
+
+        // Ensure the next value is known, as the key was recorded when the history was requested.
+        add_block(set_value("3u64"));
+        assert_eq!(changes(&history), vec![(2, None), (3, None), (5, Some("3u64".to_string()))]);
+
+        // Ensure a change at the start height is only returned in the range, and not as the change in effect.
+        let (in_effect, range) = history.changes(&key_id, 3, u32::MAX).unwrap();
+        assert_eq!(values(in_effect.into_iter().collect()), vec![(2, None)]);
+        assert_eq!(values(range), vec![(3, None), (5, Some("3u64".to_string()))]);
+        let (in_effect, range) = history.changes(&key_id, 4, 5).unwrap();
+        assert_eq!(values(in_effect.into_iter().collect()), vec![(3, None)]);
+        assert!(range.is_empty());
+
+        // Ensure an index catching up with the ledger doesn't know the values, as it doesn't know the key.
+        let history = MappingHistory::<CurrentNetwork>::open(StorageMode::Custom(directory.path().join("b"))).unwrap();
+        history.index_new_blocks(&ledger).unwrap();
+        assert_eq!(changes(&history), vec![(2, None), (3, None), (5, None)]);
+    }
+}
@@ -0,0 +1,455 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use snarkvm::prelude::{FromBytes, Identifier, Literal, Plaintext, ToBytes, Value, block::Block};
+
+use aleo_std::StorageMode;
+use anyhow::bail;
+use indexmap::IndexMap;
+use rocksdb::WriteBatch;
+use std::{
+    collections::{HashMap, HashSet},
+    marker::PhantomData,
+};
+
+/// The prefix of the keys of the entries, followed by the mapping ID and the key as a string, and holding the key.
+const ENTRY_PREFIX: u8 = 0;
+/// The prefix of the keys of the indexed key IDs, followed by the key ID, and holding the key of the entry.
+const ID_PREFIX: u8 = 1;
+/// The prefix of the keys of the entries whose key is unknown, followed by the mapping ID and the key ID, and holding
+/// the program ID and the mapping name.
+const UNRESOLVED_PREFIX: u8 = 2;
+
+/// The index of the keys of the program mappings, ordered by their string, to page through the entries of a mapping.
+/// The index is built from the finalize operations of each block, in the order of the confirmed transactions,
+/// and is persisted next to the ledger, catching up with the ledger from where it left off.
+///
+/// The finalize operations only commit to the keys, so the key inserted by a block is found among the keys passed
+/// to the finalize logic of the block, and the key removed by a block among the keys indexed before. The entries
+/// written by the ratifications are found among the addresses they reward. If an inserted key is derived within
+/// the finalize logic instead, its key ID is recorded as unresolved, until the blocks committed so far are indexed;
+/// the keys of the mappings holding an unresolved key ID are then read from the finalize store, once per mapping.
+pub(crate) struct MappingKeys<N: Network> {
+    /// The persisted index.
+    store: IndexStore,
+    /// PhantomData.
+    _phantom: PhantomData<N>,
+}
+
+impl<N: Network> MappingKeys<N> {
+    /// Opens the mapping key index of the ledger with the given storage mode.
+    pub(crate) fn open(storage_mode: StorageMode) -> Result<Self> {
+        let store = IndexStore::open(&index_store_path(MAPPING_KEYS_NAME, N::ID, storage_mode))?;
+        Ok(Self { store, _phantom: PhantomData })
+    }
+
+    /// Indexes the keys written by the blocks committed since the last call, starting from the genesis block,
+    /// then resolves the keys that were derived within the finalize logic.
+    fn index_new_blocks<C: ConsensusStorage<N>>(&self, ledger: &Ledger<N, C>) -> Result<()> {
+        loop {
+            let height = self.store.next_height()?;
+            if height > ledger.latest_height() {
+                return self.resolve_keys(ledger);
+            }
+            self.index_block(ledger, &ledger.get_block(height)?)?;
+        }
+    }
+
+    /// Indexes the keys inserted and removed by the given block.
+    /// Note: The keys derived within the finalize logic are unresolved, and left to `resolve_keys`.
+    fn index_block<C: ConsensusStorage<N>>(&self, ledger: &Ledger<N, C>, block: &Block<N>) -> Result<()> {
+        let writes = mapping_writes(block);
+
+        // Find the keys of the writes among the keys passed to the finalize logic of the block.
+        let arguments = collect_finalize_arguments(block);
+        let mut keys = HashMap::new();
+        let mut mappings = HashMap::new();
+        for (program_id, candidates) in &arguments {
+            let names =
+                ledger.vm().process().read().get_program(*program_id)?.mappings().keys().copied().collect::<Vec<_>>();
+            for name in names {
+                let mapping_id = to_mapping_id(program_id, &name)?;
+                if !writes.iter().any(|write| write.mapping_id == mapping_id) {
+                    continue;
+                }
+                mappings.insert(mapping_id, (*program_id, name));
+                for key in candidates.values() {
+                    keys.insert(to_key_id(program_id, &name, key)?, (mapping_id, key.clone()));
+                }
+            }
+        }
+
+        let mut batch = WriteBatch::default();
+        // The keys of the entries indexed by the block, by key ID, or `None` if they were removed by the block.
+        let mut indexed = HashMap::new();
+        for write in writes {
+            match (write.value_id.is_some(), self.indexed_key(&indexed, &write.key_id)?) {
+                // Note: The key of an entry that is already indexed is unchanged by an update.
+                (true, Some(_)) => {}
+                (true, None) => match keys.get(&write.key_id) {
+                    Some((mapping_id, key)) => {
+                        let entry_key = index_key(&mut batch, mapping_id, &write.key_id, key)?;
+                        indexed.insert(write.key_id, Some(entry_key));
+                    }
+                    // Note: The program and the mapping are recorded, so that the key is resolved from the mapping.
+                    None => {
+                        let Some((program_id, name)) = mappings.get(&write.mapping_id) else {
+                            bail!("Missing the program of mapping '{}' in block {}", write.mapping_id, block.height());
+                        };
+                        let mut mapping = program_id.to_bytes_le()?;
+                        name.write_le(&mut mapping)?;
+                        batch.put(unresolved_key(&write.mapping_id, &write.key_id)?, mapping);
+                    }
+                },
+                (false, Some(entry_key)) => {
+                    batch.delete(entry_key);
+                    batch.delete(id_key(&write.key_id)?);
+                    indexed.insert(write.key_id, None);
+                }
+                (false, None) => batch.delete(unresolved_key(&write.mapping_id, &write.key_id)?),
+            }
+        }
+
+        // Index the `credits.aleo` entries of the addresses rewarded by the ratifications, which hold them now.
+        // Note: An entry removed since is removed from the index again, as the blocks removing it are indexed.
+        let addresses = ratified_addresses(block);
+        if !addresses.is_empty() {
+            let (program_id, _) = account_mapping::<N>()?;
+            let names =
+                ledger.vm().process().read().get_program(program_id)?.mappings().keys().copied().collect::<Vec<_>>();
+            for name in names {
+                let mapping_id = to_mapping_id(&program_id, &name)?;
+                for address in &addresses {
+                    let key = Plaintext::from(Literal::Address(*address));
+                    let key_id = to_key_id(&program_id, &name, &key)?;
+                    if self.indexed_key(&indexed, &key_id)?.is_none()
+                        && ledger.vm().finalize_store().contains_key_confirmed(program_id, name, &key)?
+                    {
+                        let entry_key = index_key(&mut batch, &mapping_id, &key_id, &key)?;
+                        indexed.insert(key_id, Some(entry_key));
+                    }
+                }
+            }
+        }
+        self.store.write_block(block.height(), batch)
+    }
+
+    /// Returns the key of the indexed entry with the given key ID, as indexed by the current block, or stored.
+    fn indexed_key(&self, indexed: &HashMap<Field<N>, Option<Vec<u8>>>, key_id: &Field<N>) -> Result<Option<Vec<u8>>> {
+        match indexed.get(key_id) {
+            Some(entry_key) => Ok(entry_key.clone()),
+            None => self.store.get(&id_key(key_id)?),
+        }
+    }
+
+    /// Indexes the keys of the mappings holding an unresolved key ID, reading each of these mappings once.
+    /// Note: The key of an entry removed in the meantime is not found, and is left to the block removing it.
+    fn resolve_keys<C: ConsensusStorage<N>>(&self, ledger: &Ledger<N, C>) -> Result<()> {
+        // Collect the unresolved key IDs, by mapping.
+        let prefix = [UNRESOLVED_PREFIX];
+        let mut unresolved = IndexMap::<_, (ProgramID<N>, Identifier<N>, HashSet<_>)>::new();
+        for entry in self.store.iter_from(&prefix, &prefix) {
+            let (entry_key, mapping) = entry?;
+            let mut ids = &entry_key[prefix.len()..];
+            let (mapping_id, key_id) = (Field::<N>::read_le(&mut ids)?, Field::<N>::read_le(&mut ids)?);
+            let mut mapping = &mapping[..];
+            let (program_id, name) = (ProgramID::read_le(&mut mapping)?, Identifier::read_le(&mut mapping)?);
+            unresolved.entry(mapping_id).or_insert_with(|| (program_id, name, HashSet::new())).2.insert(key_id);
+        }
+
+        // Find the unresolved key IDs among the keys of each mapping, skipping the keys that are already indexed.
+        let mut batch = WriteBatch::default();
+        for (mapping_id, (program_id, name, key_ids)) in unresolved {
+            let entry_prefix = mapping_prefix(ENTRY_PREFIX, &mapping_id)?;
+            for (key, _) in ledger.vm().finalize_store().get_mapping_confirmed(program_id, name)? {
+                if self.store.get(&[&entry_prefix[..], key.to_string().as_bytes()].concat())?.is_some() {
+                    continue;
+                }
+                let key_id = to_key_id(&program_id, &name, &key)?;
+                if key_ids.contains(&key_id) {
+                    index_key(&mut batch, &mapping_id, &key_id, &key)?;
+                }
+            }
+        }
+        self.store.write(batch)
+    }
+
+    /// Returns up to `limit` entries of the given mapping whose key starts with the given prefix, following the given
+    /// key, ordered by the string of their key, along with the bytes of that string, and `true` if every key of the
+    /// mapping is resolved.
+    /// Note: The entries are read as of the latest block, unless a block is added in the meantime.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn page<C: ConsensusStorage<N>>(
+        &self,
+        ledger: &Ledger<N, C>,
+        program_id: ProgramID<N>,
+        name: Identifier<N>,
+        prefix: &str,
+        after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<(Vec<(Vec<u8>, Plaintext<N>, Value<N>)>, bool)> {
+        // Ensure the index caught up with the ledger.
+        let (next_height, latest_height) = (self.store.next_height()?, ledger.latest_height());
+        if next_height <= latest_height {
+            bail!("The mapping index is catching up with the ledger (at block {next_height} of {latest_height})");
+        }
+
+        let mapping_id = to_mapping_id(&program_id, &name)?;
+        let entry_prefix = mapping_prefix(ENTRY_PREFIX, &mapping_id)?;
+        // Note: The keys starting with the prefix are contiguous, so the entries past them are never read.
+        let prefix = [&entry_prefix[..], prefix.as_bytes()].concat();
+        let from = match after {
+            Some(after) => prefix.clone().max([&entry_prefix[..], after].concat()),
+            None => prefix.clone(),
+        };
+        let mut entries = Vec::with_capacity(limit);
+        for entry in self.store.iter_from(&prefix, &from) {
+            if entries.len() == limit {
+                break;
+            }
+            let (entry_key, key) = entry?;
+            let key_bytes = &entry_key[entry_prefix.len()..];
+            // Skip the last key of the previous page.
+            if after == Some(key_bytes) {
+                continue;
+            }
+            let key = Plaintext::from_bytes_le(&key)?;
+            // Skip the entries removed by a block added in the meantime.
+            if let Some(value) = ledger.vm().finalize_store().get_value_confirmed(program_id, name, &key)? {
+                entries.push((key_bytes.to_vec(), key, value));
+            }
+        }
+
+        // Check whether the mapping has an entry whose key is unresolved.
+        let unresolved_prefix = mapping_prefix(UNRESOLVED_PREFIX, &mapping_id)?;
+        let is_complete = self.store.iter_from(&unresolved_prefix, &unresolved_prefix).next().transpose()?.is_none();
+        Ok((entries, is_complete))
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    /// Keeps the mapping key index up to date, indexing the blocks as they are committed.
+    pub(crate) async fn maintain_mapping_keys(self, index: Arc<MappingKeys<N>>) {
+        let mut block_height = self.block_height.clone();
+        loop {
+            // Index the blocks committed since the last iteration, starting from the genesis block.
+            let (ledger, index_) = (self.ledger.clone(), index.clone());
+            match tokio::task::spawn_blocking(move || index_.index_new_blocks(&ledger)).await {
+                Ok(Ok(())) => {}
+                Ok(Err(error)) => warn!("Failed to update the mapping key index - {error}"),
+                Err(error) => warn!("Failed to update the mapping key index - {error}"),
+            }
+            // Wait for the next block. If the sender was dropped, no more blocks will be committed.
+            if block_height.changed().await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+/// Indexes the given key of the given mapping in the given batch, returning the key of its entry.
+fn index_key<N: Network>(
+    batch: &mut WriteBatch,
+    mapping_id: &Field<N>,
+    key_id: &Field<N>,
+    key: &Plaintext<N>,
+) -> Result<Vec<u8>> {
+    let mut entry_key = mapping_prefix(ENTRY_PREFIX, mapping_id)?;
+    entry_key.extend_from_slice(key.to_string().as_bytes());
+    batch.put(&entry_key, key.to_bytes_le()?);
+    batch.put(id_key(key_id)?, &entry_key);
+    batch.delete(unresolved_key(mapping_id, key_id)?);
+    Ok(entry_key)
+}
+
+/// Returns the prefix of the keys of the given kind for the given mapping.
+fn mapping_prefix<N: Network>(kind: u8, mapping_id: &Field<N>) -> Result<Vec<u8>> {
+    let mut key = vec![kind];
+    mapping_id.write_le(&mut key)?;
+    Ok(key)
+}
+
+/// Returns the key holding the key of the indexed entry with the given key ID.
+fn id_key<N: Network>(key_id: &Field<N>) -> Result<Vec<u8>> {
+    let mut key = vec![ID_PREFIX];
+    key_id.write_le(&mut key)?;
+    Ok(key)
+}
+
+/// Returns the key marking the entry with the given key ID of the given mapping as unresolved.
+fn unresolved_key<N: Network>(mapping_id: &Field<N>, key_id: &Field<N>) -> Result<Vec<u8>> {
+    let mut key = mapping_prefix(UNRESOLVED_PREFIX, mapping_id)?;
+    key_id.write_le(&mut key)?;
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::{
+        ledger::store::{ConsensusStore, helpers::memory::ConsensusMemory},
+        prelude::{Address, MainnetV0, PrivateKey, Program, U64, VM, block::Transaction},
+        utilities::TestRng,
+    };
+    use std::str::FromStr;
+
+    type CurrentNetwork = MainnetV0;
+    type CurrentLedger = Ledger<CurrentNetwork, ConsensusMemory<CurrentNetwork>>;
+
+    /// A program whose finalize logic derives the keys of its mapping.
+    const PROGRAM: &str = r"
+program keys_test.aleo;
+
+mapping values:
+    key as field.public;
+    value as u64.public;
+
+function set_value:
+    input r0 as u64.public;
+    input r1 as u64.public;
+    async set_value r0 r1 into r2;
+    output r2 as keys_test.aleo/set_value.future;
+
+finalize set_value:
+    input r0 as u64.public;
+    input r1 as u64.public;
+    hash.bhp256 r0 into r2 as field;
+    set r1 into values[r2];
+";
+
+    /// Returns every entry of the given mapping whose key starts with the given prefix, reading the pages of the
+    /// given size, and `true` if every key of the mapping is resolved.
+    #[allow(clippy::type_complexity)]
+    fn read_pages(
+        index: &MappingKeys<CurrentNetwork>,
+        ledger: &CurrentLedger,
+        program_id: ProgramID<CurrentNetwork>,
+        name: Identifier<CurrentNetwork>,
+        prefix: &str,
+        limit: usize,
+    ) -> (Vec<(Plaintext<CurrentNetwork>, Value<CurrentNetwork>)>, bool) {
+        let mut entries = Vec::new();
+        let mut after = None;
+        loop {
+            let (page, is_complete) = index.page(ledger, program_id, name, prefix, after.as_deref(), limit).unwrap();
+            let Some((key_bytes, ..)) = page.last() else {
+                return (entries, is_complete);
+            };
+            after = Some(key_bytes.clone());
+            entries.extend(page.into_iter().map(|(_, key, value)| (key, value)));
+        }
+    }
+
+    #[test]
+    fn test_pages_of_added_blocks() {
+        let rng = &mut TestRng::default();
+
+        // Initialize a ledger, whose genesis block gives a balance to the given private key.
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let store = ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(None).unwrap();
+        let genesis = VM::from(store).unwrap().genesis_beacon(&private_key, rng).unwrap();
+        let ledger = CurrentLedger::load(genesis, StorageMode::Production).unwrap();
+
+        // Returns the entries of the account mapping whose key starts with the given prefix, ordered by their key.
+        let (program_id, name) = account_mapping::<CurrentNetwork>().unwrap();
+        let expected = |prefix: &str| {
+            let mut entries = ledger.vm().finalize_store().get_mapping_confirmed(program_id, name).unwrap();
+            entries.retain(|(key, _)| key.to_string().starts_with(prefix));
+            entries.sort_by_key(|(key, _)| key.to_string());
+            entries
+        };
+
+        // Open an index, and ensure it is caught up with the ledger before a page is read.
+        let directory = tempfile::tempdir().unwrap();
+        let storage_mode = StorageMode::Custom(directory.path().to_path_buf());
+        let index = MappingKeys::<CurrentNetwork>::open(storage_mode.clone()).unwrap();
+        assert!(index.page(&ledger, program_id, name, "", None, 1).is_err());
+        index.index_new_blocks(&ledger).unwrap();
+
+        // Ensure the pages hold every entry of the genesis block, in order.
+        assert_eq!(read_pages(&index, &ledger, program_id, name, "", 2), (expected(""), true));
+
+        // Transfer to a new address, indexing the block as it is committed.
+        let address = Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+        let inputs = [Value::from(Literal::Address(address)), Value::from(Literal::U64(U64::new(1_000)))];
+        let locator = (program_id, Identifier::from_str("transfer_public").unwrap());
+        let transaction = ledger.vm().execute(&private_key, locator, inputs.into_iter(), None, 0, None, rng).unwrap();
+        let block =
+            ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![transaction], rng).unwrap();
+        ledger.advance_to_next_block(&block).unwrap();
+        index.index_new_blocks(&ledger).unwrap();
+
+        // Ensure the pages hold the new entry.
+        let (entries, is_complete) = read_pages(&index, &ledger, program_id, name, "", 2);
+        assert!(entries.iter().any(|(key, _)| *key == Plaintext::from(Literal::Address(address))));
+        assert_eq!(entries, expected(""));
+        assert!(is_complete);
+
+        // Ensure the pages of a prefix hold only the entries whose key starts with it.
+        let prefix = &address.to_string()[..8];
+        let (entries, _) = read_pages(&index, &ledger, program_id, name, prefix, 1);
+        assert!(!entries.is_empty());
+        assert_eq!(entries, expected(prefix));
+
+        // Ensure the index is kept when it is reopened.
+        drop(index);
+        let index = MappingKeys::<CurrentNetwork>::open(storage_mode).unwrap();
+        assert_eq!(read_pages(&index, &ledger, program_id, name, "", 1), (expected(""), true));
+    }
+
+    #[test]
+    fn test_pages_of_derived_keys() {
+        let rng = &mut TestRng::default();
+
+        // Initialize a ledger, whose genesis block gives a balance to the given private key.
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let store = ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(None).unwrap();
+        let genesis = VM::from(store).unwrap().genesis_beacon(&private_key, rng).unwrap();
+        let ledger = CurrentLedger::load(genesis, StorageMode::Production).unwrap();
+
+        // Adds a block with the given transaction to the ledger.
+        let rng_ = &mut TestRng::default();
+        let mut add_block = |transaction: Transaction<CurrentNetwork>| {
+            let block = ledger
+                .prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![transaction], rng_)
+                .unwrap();
+            ledger.advance_to_next_block(&block).unwrap();
+        };
+
+        // Deploy the program, and write to an entry whose key is derived within the finalize logic.
+        let program = Program::<CurrentNetwork>::from_str(PROGRAM).unwrap();
+        add_block(ledger.vm().deploy(&private_key, &program, None, 0, None, rng).unwrap());
+        let locator = (*program.id(), Identifier::from_str("set_value").unwrap());
+        let inputs = [Value::from_str("7u64").unwrap(), Value::from_str("1u64").unwrap()];
+        add_block(ledger.vm().execute(&private_key, locator, inputs.into_iter(), None, 0, None, rng).unwrap());
+
+        // Index the blocks, without resolving the keys.
+        let directory = tempfile::tempdir().unwrap();
+        let index = MappingKeys::<CurrentNetwork>::open(StorageMode::Custom(directory.path().to_path_buf())).unwrap();
+        for height in 0..=ledger.latest_height() {
+            index.index_block(&ledger, &ledger.get_block(height).unwrap()).unwrap();
+        }
+        // Ensure the page is incomplete, as the key of the entry is unresolved.
+        let name = Identifier::from_str("values").unwrap();
+        assert_eq!(read_pages(&index, &ledger, *program.id(), name, "", 1), (vec![], false));
+
+        // Ensure the page holds the entry once the keys are resolved.
+        index.resolve_keys(&ledger).unwrap();
+        let expected = ledger.vm().finalize_store().get_mapping_confirmed(*program.id(), name).unwrap();
+        assert_eq!(expected.len(), 1);
+        assert_eq!(read_pages(&index, &ledger, *program.id(), name, "", 1), (expected, true));
+    }
+}
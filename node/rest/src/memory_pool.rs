@@ -0,0 +1,261 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use snarkos_node_bft::Primary;
+use snarkvm::{
+    ledger::{
+        narwhal::{Transmission, TransmissionID},
+        puzzle::SolutionID,
+    },
+    prelude::block::Transaction,
+};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use time::OffsetDateTime;
+
+/// The stages of the memory pool, from the earliest to the latest.
+const STAGES: [&str; 3] = ["inbound", "worker", "proposed"];
+/// The types of transactions.
+const TRANSACTION_TYPES: [&str; 3] = ["deploy", "execute", "fee"];
+
+/// The query object for `get_memory_pool_entries`.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct MemoryPoolQuery {
+    /// The program that the transactions must deploy or execute.
+    program: Option<String>,
+    /// The function that the transactions must execute.
+    function: Option<String>,
+    /// The type of the transactions, as `deploy`, `execute` or `fee`.
+    #[serde(rename = "type")]
+    transaction_type: Option<String>,
+    /// The stage of the transactions, as `inbound`, `worker` or `proposed`.
+    stage: Option<String>,
+}
+
+impl MemoryPoolQuery {
+    /// The query parameters, as documented in the OpenAPI document.
+    pub(crate) const PARAMS: &'static [QueryParam] = &[
+        QueryParam::new("program", "string", false, "The program that the transactions must deploy or execute"),
+        QueryParam::new("function", "string", false, "The function that the transactions must execute"),
+        QueryParam::new("type", "string", false, "The type of the transactions: deploy, execute or fee"),
+        QueryParam::new("stage", "string", false, "The stage of the transactions: inbound, worker or proposed"),
+    ];
+
+    /// Returns `true` if the given transaction in the given stage matches the filters.
+    fn matches<N: Network>(&self, stage: &str, transaction: &Transaction<N>) -> bool {
+        if self.stage.as_ref().is_some_and(|expected| expected != stage) {
+            return false;
+        }
+        if self.transaction_type.as_ref().is_some_and(|expected| expected != transaction_type(transaction)) {
+            return false;
+        }
+        // Note: A deployment matches its program, and an execution matches the programs of its transitions.
+        let deployed = transaction.deployment().map(|deployment| *deployment.program_id());
+        let is_match = |program_id: &ProgramID<N>, function: Option<String>| {
+            self.program.as_ref().map_or(true, |expected| *expected == program_id.to_string())
+                && self.function.as_ref().map_or(true, |expected| Some(expected) == function.as_ref())
+        };
+        match deployed {
+            Some(program_id) => is_match(&program_id, None),
+            None => transaction
+                .transitions()
+                .any(|transition| is_match(transition.program_id(), Some(transition.function_name().to_string()))),
+        }
+    }
+}
+
+/// Returns the type of the given transaction.
+fn transaction_type<N: Network>(transaction: &Transaction<N>) -> &'static str {
+    match transaction {
+        Transaction::Deploy(..) => "deploy",
+        Transaction::Execute(..) => "execute",
+        Transaction::Fee(..) => "fee",
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    /// Returns the transmissions of each stage of the memory pool, from the earliest to the latest stage.
+    #[allow(clippy::type_complexity)]
+    fn memory_pool_stages(consensus: &Consensus<N>) -> [(&'static str, Vec<(TransmissionID<N>, Transmission<N>)>); 3] {
+        let proposed = match consensus.bft().primary().proposed_batch().read().as_ref() {
+            Some(proposal) => proposal.transmissions().clone().into_iter().collect(),
+            None => Vec::new(),
+        };
+        [
+            ("inbound", consensus.inbound_transmissions().collect()),
+            ("worker", consensus.worker_transmissions().collect()),
+            ("proposed", proposed),
+        ]
+    }
+
+    /// Returns the number of seconds since the given transmission was first seen, if it is tracked.
+    fn memory_pool_age(consensus: &Consensus<N>, transmission_id: &TransmissionID<N>) -> Option<i64> {
+        let first_seen = consensus.transmission_first_seen(transmission_id)?;
+        Some(OffsetDateTime::now_utc().unix_timestamp().saturating_sub(first_seen))
+    }
+
+    // GET /<network>/memoryPool/counts
+    pub(crate) async fn get_memory_pool_counts(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        let Some(consensus) = &rest.consensus else {
+            return Err(RestError("Route isn't available for this node type".to_string()));
+        };
+        let counts = Self::memory_pool_stages(consensus)
+            .into_iter()
+            .map(|(stage, transmissions)| {
+                let count = |is_kind: fn(&TransmissionID<N>) -> bool| {
+                    transmissions.iter().filter(|(transmission_id, _)| is_kind(transmission_id)).count()
+                };
+                let counts = json!({
+                    "transactions": count(|id| matches!(id, TransmissionID::Transaction(..))),
+                    "solutions": count(|id| matches!(id, TransmissionID::Solution(..))),
+                    "ratifications": count(|id| matches!(id, TransmissionID::Ratification)),
+                });
+                (stage, counts)
+            })
+            .collect::<IndexMap<_, _>>();
+        Ok(ErasedJson::pretty(counts))
+    }
+
+    // GET /<network>/memoryPool/config
+    pub(crate) async fn get_memory_pool_config(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        let Some(consensus) = &rest.consensus else {
+            return Err(RestError("Route isn't available for this node type".to_string()));
+        };
+        let config = consensus.config();
+        Ok(ErasedJson::pretty(json!({
+            "capacityForDeployments": config.capacity_for_deployments,
+            "capacityForExecutions": config.capacity_for_executions,
+            "capacityForSolutions": config.capacity_for_solutions,
+            "maxDeploymentsPerInterval": config.max_deployments_per_interval,
+            "maxPendingPerPayer": config.max_pending_per_payer,
+            "maxTransmissionsTolerance": Primary::<N>::MAX_TRANSMISSIONS_TOLERANCE,
+        })))
+    }
+
+    // GET /<network>/memoryPool/transaction/{transactionID}
+    pub(crate) async fn get_memory_pool_transaction(
+        State(rest): State<Self>,
+        Path(tx_id): Path<N::TransactionID>,
+    ) -> Result<ErasedJson, RestError> {
+        let Some(consensus) = rest.consensus.clone() else {
+            return Err(RestError("Route isn't available for this node type".to_string()));
+        };
+        let is_match = move |transmission_id: &TransmissionID<N>| match transmission_id {
+            TransmissionID::Transaction(id, _) => *id == tx_id,
+            _ => false,
+        };
+        match tokio::task::spawn_blocking(move || Self::find_memory_pool_entry(&consensus, is_match)).await {
+            Ok(Ok(Some(entry))) => Ok(ErasedJson::pretty(entry)),
+            Ok(Ok(None)) => Err(RestError(format!("Missing transaction '{tx_id}' in the memory pool"))),
+            Ok(Err(err)) => Err(RestError(format!("Unable to find transaction '{tx_id}' - {err}"))),
+            Err(err) => Err(RestError(format!("Unable to find transaction '{tx_id}' - {err}"))),
+        }
+    }
+
+    // GET /<network>/memoryPool/solution/{solutionID}
+    pub(crate) async fn get_memory_pool_solution(
+        State(rest): State<Self>,
+        Path(solution_id): Path<SolutionID<N>>,
+    ) -> Result<ErasedJson, RestError> {
+        let Some(consensus) = rest.consensus.clone() else {
+            return Err(RestError("Route isn't available for this node type".to_string()));
+        };
+        let is_match = move |transmission_id: &TransmissionID<N>| match transmission_id {
+            TransmissionID::Solution(id, _) => *id == solution_id,
+            _ => false,
+        };
+        match tokio::task::spawn_blocking(move || Self::find_memory_pool_entry(&consensus, is_match)).await {
+            Ok(Ok(Some(entry))) => Ok(ErasedJson::pretty(entry)),
+            Ok(Ok(None)) => Err(RestError(format!("Missing solution '{solution_id}' in the memory pool"))),
+            Ok(Err(err)) => Err(RestError(format!("Unable to find solution '{solution_id}' - {err}"))),
+            Err(err) => Err(RestError(format!("Unable to find solution '{solution_id}' - {err}"))),
+        }
+    }
+
+    /// Returns the entry of the latest stage holding a transmission that matches, if one exists.
+    fn find_memory_pool_entry(
+        consensus: &Consensus<N>,
+        is_match: impl Fn(&TransmissionID<N>) -> bool,
+    ) -> Result<Option<Value>> {
+        for (stage, transmissions) in Self::memory_pool_stages(consensus).into_iter().rev() {
+            if let Some((transmission_id, transmission)) = transmissions.into_iter().find(|(id, _)| is_match(id)) {
+                let transmission = match transmission {
+                    Transmission::Transaction(transaction) => json!(transaction.deserialize_blocking()?),
+                    Transmission::Solution(solution) => json!(solution.deserialize_blocking()?),
+                    Transmission::Ratification => Value::Null,
+                };
+                return Ok(Some(json!({
+                    "id": transmission_id,
+                    "stage": stage,
+                    "ageSecs": Self::memory_pool_age(consensus, &transmission_id),
+                    "transmission": transmission,
+                })));
+            }
+        }
+        Ok(None)
+    }
+
+    // GET /<network>/memoryPool/entries?program={programID}&function={functionName}&type={type}&stage={stage}
+    pub(crate) async fn get_memory_pool_entries(
+        State(rest): State<Self>,
+        Query(query): Query<MemoryPoolQuery>,
+    ) -> Result<ErasedJson, RestError> {
+        let Some(consensus) = rest.consensus.clone() else {
+            return Err(RestError("Route isn't available for this node type".to_string()));
+        };
+        // Ensure the stage and the type are known.
+        if let Some(stage) = query.stage.as_ref().filter(|stage| !STAGES.contains(&stage.as_str())) {
+            return Err(RestError(format!("Unknown stage '{stage}', expected one of {}", STAGES.join(", "))));
+        }
+        if let Some(kind) = query.transaction_type.as_ref().filter(|kind| !TRANSACTION_TYPES.contains(&kind.as_str())) {
+            let expected = TRANSACTION_TYPES.join(", ");
+            return Err(RestError(format!("Unknown transaction type '{kind}', expected one of {expected}")));
+        }
+
+        // Prepare a closure for the blocking work.
+        let get_entries = move || -> Result<Vec<Value>> {
+            let mut entries = Vec::new();
+            for (stage, transmissions) in Self::memory_pool_stages(&consensus) {
+                for (transmission_id, transmission) in transmissions {
+                    let Transmission::Transaction(transaction) = transmission else {
+                        continue;
+                    };
+                    let transaction = transaction.deserialize_blocking()?;
+                    if !query.matches(stage, &transaction) {
+                        continue;
+                    }
+                    entries.push(json!({
+                        "id": transaction.id(),
+                        "stage": stage,
+                        "type": transaction_type(&transaction),
+                        "ageSecs": Self::memory_pool_age(&consensus, &transmission_id),
+                        "transaction": transaction,
+                    }));
+                }
+            }
+            Ok(entries)
+        };
+
+        // Filter the transactions of the memory pool.
+        match tokio::task::spawn_blocking(get_entries).await {
+            Ok(Ok(entries)) => Ok(ErasedJson::pretty(entries)),
+            Ok(Err(err)) => Err(RestError(format!("Unable to filter the memory pool - {err}"))),
+            Err(err) => Err(RestError(format!("Unable to filter the memory pool - {err}"))),
+        }
+    }
+}
@@ -0,0 +1,1093 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+#[cfg(feature = "history")]
+use crate::mapping_history::MappingHistoryQuery;
+use crate::{
+    address::AddressTransitionsQuery,
+    events::EventsQuery,
+    fee::FeeEstimateQuery,
+    memory_pool::MemoryPoolQuery,
+    routes::{BlockRange, MappingPage, Metadata},
+    stream::BlockStreamRange,
+};
+
+use axum::{Router, handler::Handler, http::Method, routing::MethodRouter};
+use serde_json::{Map, Value, json};
+
+/// A query parameter of a route, declared next to the query struct that deserializes it.
+pub(crate) struct QueryParam {
+    /// The name of the parameter.
+    name: &'static str,
+    /// The JSON schema type of the parameter.
+    kind: &'static str,
+    /// Whether the parameter must be provided.
+    required: bool,
+    /// The description of the parameter.
+    description: &'static str,
+}
+
+impl QueryParam {
+    /// Initializes a new query parameter.
+    pub(crate) const fn new(name: &'static str, kind: &'static str, required: bool, description: &'static str) -> Self {
+        Self { name, kind, required, description }
+    }
+}
+
+/// The access control applied to a route.
+#[derive(Copy, Clone)]
+pub(crate) enum Access {
+    /// The route is public.
+    Public,
+    /// The route requires a JWT with the given scope.
+    Scoped(Scope),
+    /// The route requires a JWT with the `broadcast` scope, if the node is configured to protect it.
+    Broadcast,
+}
+
+impl Access {
+    /// Returns the scope required by the route, given whether the node protects the broadcast routes.
+    pub(crate) fn scope(self, broadcast_protected: bool) -> Option<Scope> {
+        match self {
+            Self::Public => None,
+            Self::Scoped(scope) => Some(scope),
+            Self::Broadcast => broadcast_protected.then_some(Scope::Broadcast),
+        }
+    }
+}
+
+/// The request body accepted by a route.
+enum RequestBody {
+    /// A JSON value, with the given description and schema.
+    Json(&'static str, Value),
+    /// A JSON value with the given description and schema, or its binary encoding.
+    JsonOrBinary(&'static str, Value),
+}
+
+/// The response returned by a route on success.
+#[derive(Copy, Clone)]
+enum ResponseBody {
+    /// A JSON value.
+    Json,
+    /// A JSON value, or its binary encoding if requested through the `Accept` header.
+    Negotiated,
+    /// A stream of NDJSON lines, or of length-prefixed binary items if requested through the `Accept` header.
+    Stream,
+    /// A stream of server-sent events.
+    EventStream,
+}
+
+/// A route registered on the REST server, as described in the OpenAPI document.
+pub(crate) struct Operation {
+    /// The HTTP method.
+    pub(crate) method: Method,
+    /// The path of the route, with the path parameters prefixed by a colon.
+    pub(crate) path: String,
+    /// The access control applied to the route.
+    access: Access,
+    /// The route group whose rate limit is charged per request, or `None` if the handler charges each of its calls.
+    pub(crate) group: Option<RouteGroup>,
+}
+
+/// A router recording the routes it registers, so that the OpenAPI document describes the routes served.
+pub(crate) struct ApiRouter<S> {
+    /// The router.
+    router: Router<S>,
+    /// The routes registered on the router.
+    operations: Vec<Operation>,
+}
+
+impl<S: Clone + Send + Sync + 'static> ApiRouter<S> {
+    /// Initializes a new router, without any routes.
+    pub(crate) fn new() -> Self {
+        Self { router: Router::new(), operations: Vec::new() }
+    }
+
+    /// Registers a public route, charged to the rate limit of the given group, or of each call if `None`.
+    fn route(mut self, method: Method, path: &str, group: Option<RouteGroup>, handler: MethodRouter<S>) -> Self {
+        self.router = self.router.route(path, handler);
+        self.operations.push(Operation { method, path: path.to_string(), access: Access::Public, group });
+        self
+    }
+
+    /// Registers a public `GET` route, charged to the rate limit of the given group.
+    pub(crate) fn get<H: Handler<T, S>, T: 'static>(self, path: &str, group: RouteGroup, handler: H) -> Self {
+        self.route(Method::GET, path, Some(group), get(handler))
+    }
+
+    /// Registers a public `POST` route, charged to the rate limit of the given group.
+    pub(crate) fn post<H: Handler<T, S>, T: 'static>(self, path: &str, group: RouteGroup, handler: H) -> Self {
+        self.route(Method::POST, path, Some(group), post(handler))
+    }
+
+    /// Registers a public `POST` route, whose handler charges each of its calls to the rate limit of the call's group.
+    pub(crate) fn post_per_call<H: Handler<T, S>, T: 'static>(self, path: &str, handler: H) -> Self {
+        self.route(Method::POST, path, None, post(handler))
+    }
+
+    /// Applies the given access control to the routes registered so far.
+    pub(crate) fn access(mut self, access: Access, jwt: &Arc<JwtConfig>) -> Self {
+        if let Some(scope) = access.scope(jwt.is_broadcast_protected()) {
+            self.router =
+                self.router.route_layer(middleware::from_fn_with_state((jwt.clone(), scope), auth_middleware));
+        }
+        for operation in &mut self.operations {
+            operation.access = access;
+        }
+        self
+    }
+
+    /// Merges the routes of the given router.
+    pub(crate) fn merge(mut self, other: Self) -> Self {
+        self.router = self.router.merge(other.router);
+        self.operations.extend(other.operations);
+        self
+    }
+
+    /// Returns the router, along with the routes registered on it.
+    pub(crate) fn into_parts(self) -> (Router<S>, Vec<Operation>) {
+        (self.router, self.operations)
+    }
+}
+
+/// The description of a route in the OpenAPI document.
+struct Description {
+    /// The summary of the route.
+    summary: &'static str,
+    /// The query parameters of the route.
+    query: &'static [&'static [QueryParam]],
+    /// The request body of the route, if any.
+    body: Option<RequestBody>,
+    /// The response of the route.
+    response: ResponseBody,
+    /// The JSON schema of the response, or of each item of a stream.
+    schema: Value,
+    /// Whether the route rejects a transaction or solution with a machine-readable error code.
+    rejects: bool,
+}
+
+impl Description {
+    /// Initializes the description of a route returning JSON with the given schema, without any query or body.
+    fn new(summary: &'static str, schema: Value) -> Self {
+        Self { summary, query: &[], body: None, response: ResponseBody::Json, schema, rejects: false }
+    }
+
+    /// Sets the query parameters of the route.
+    fn query(mut self, query: &'static [&'static [QueryParam]]) -> Self {
+        self.query = query;
+        self
+    }
+
+    /// Sets the response of the route.
+    fn response(mut self, response: ResponseBody) -> Self {
+        self.response = response;
+        self
+    }
+
+    /// Sets the request body of the route.
+    fn body(mut self, body: RequestBody) -> Self {
+        self.body = Some(body);
+        self
+    }
+
+    /// Documents the rejections of the transaction or solution broadcast by the route.
+    fn rejects(mut self) -> Self {
+        self.rejects = true;
+        self
+    }
+}
+
+/// Returns the description of the route with the given method and path, where the path follows `/<network>`
+/// for the prefixed routes and has its path parameters in braces, or `None` if the route is not described.
+fn describe(method: &str, path: &str) -> Option<Description> {
+    let peer = || object(&[("ip", string("The listening address of the peer, as `<ip>:<port>`"))]);
+    let description = match (method, path) {
+        // The routes requiring a JWT.
+        ("get", "/node/address") => Description::new("Returns the address of the node", address()),
+        ("get", "/program/{id}/mapping/{name}") => {
+            Description::new("Returns the entries of a mapping, in full or by page", mapping_values())
+                .query(&[Metadata::PARAMS, MappingPage::PARAMS])
+        }
+        ("post", "/admin/peers/connect") => Description::new(
+            "Connects to a peer",
+            object(&[("ip", string("The address of the peer")), ("connected", boolean())]),
+        )
+        .body(RequestBody::Json("The peer to connect to", peer())),
+        ("post", "/admin/peers/disconnect") => Description::new(
+            "Disconnects from a peer",
+            object(&[("ip", string("The address of the peer")), ("disconnected", boolean())]),
+        )
+        .body(RequestBody::Json("The peer to disconnect from", peer())),
+        ("get", "/admin/peers/trusted") => {
+            Description::new("Returns the trusted peers", array(string("The address of the peer")))
+        }
+        ("post", "/admin/peers/trusted/add") => Description::new(
+            "Adds a trusted peer",
+            object(&[("ip", string("The address of the peer")), ("inserted", boolean())]),
+        )
+        .body(RequestBody::Json("The peer to trust", peer())),
+        ("post", "/admin/peers/trusted/remove") => Description::new(
+            "Removes a trusted peer",
+            object(&[("ip", string("The address of the peer")), ("removed", boolean())]),
+        )
+        .body(RequestBody::Json("The peer to stop trusting", peer())),
+        ("get", "/admin/bans") => Description::new(
+            "Returns the banned IP addresses",
+            array(object(&[("ip", string("The banned IP address")), ("bannedForSecs", integer())])),
+        ),
+        ("post", "/admin/bans/lift") => Description::new(
+            "Lifts the ban on an IP address",
+            object(&[("ip", string("The banned IP address")), ("lifted", boolean())]),
+        )
+        .body(RequestBody::Json("The banned IP address", object(&[("ip", string("The IP address"))]))),
+        ("post", "/admin/tokens/revoke") => {
+            Description::new("Revokes a JWT", object(&[("id", string("The ID of the token")), ("revoked", boolean())]))
+                .body(RequestBody::Json(
+                    "The token to revoke",
+                    object(&[("id", string("The `jti` claim of the token"))]),
+                ))
+        }
+        ("post", "/admin/shutdown") => Description::new("Shuts down the node", object(&[("shuttingDown", boolean())])),
+        ("post", "/transaction/broadcast") => {
+            Description::new("Broadcasts a transaction", prefixed("at1", "The ID of the transaction"))
+                .body(RequestBody::JsonOrBinary("The transaction", component("Transaction")))
+                .rejects()
+        }
+        ("post", "/solution/broadcast") => {
+            Description::new("Broadcasts a solution", prefixed("solution1", "The ID of the solution"))
+                .body(RequestBody::Json("The solution", component("Solution")))
+                .rejects()
+        }
+        ("post", "/rpc") => Description::new("Dispatches a JSON-RPC 2.0 request or batch", rpc_response())
+            .body(RequestBody::Json("The JSON-RPC 2.0 request or batch", rpc_request())),
+        // The block routes.
+        ("get", "/block/height/latest") => Description::new("Returns the latest block height", height()),
+        ("get", "/block/hash/latest") => {
+            Description::new("Returns the latest block hash", prefixed("ab1", "The hash of the block"))
+        }
+        ("get", "/block/latest") => {
+            Description::new("Returns the latest block", component("Block")).response(ResponseBody::Negotiated)
+        }
+        ("get", "/block/{height_or_hash}") => {
+            Description::new("Returns the block for the given height or hash", component("Block"))
+                .response(ResponseBody::Negotiated)
+        }
+        ("get", "/block/{height_or_hash}/transactions") => Description::new(
+            "Returns the transactions of the block at the given height",
+            array(component("ConfirmedTransaction")),
+        )
+        .response(ResponseBody::Negotiated),
+        // The transaction routes.
+        ("get", "/transaction/{id}") => {
+            Description::new("Returns the transaction", component("Transaction")).response(ResponseBody::Negotiated)
+        }
+        ("get", "/transaction/confirmed/{id}") => {
+            Description::new("Returns the confirmed transaction", component("ConfirmedTransaction"))
+                .response(ResponseBody::Negotiated)
+        }
+        ("get", "/transaction/status/{id}") => {
+            Description::new("Returns the lifecycle stage of the transaction", transaction_status())
+        }
+        ("post", "/transaction/simulate") => {
+            Description::new("Simulates a transaction against the latest ledger state", simulation())
+                .body(RequestBody::Json("The transaction", component("Transaction")))
+        }
+        // The address routes.
+        ("get", "/address/{address}/transitions") => {
+            Description::new("Returns the indexed transitions and balances of the address", address_transitions())
+                .query(&[AddressTransitionsQuery::PARAMS])
+        }
+        // The BFT routes.
+        ("get", "/bft/rounds") => Description::new(
+            "Returns the current and garbage collection rounds of the BFT",
+            object(&[
+                ("currentRound", integer()),
+                ("gcRound", integer()),
+                ("maxGcRounds", integer()),
+                ("isSynced", boolean()),
+            ]),
+        ),
+        ("get", "/bft/dag") => Description::new(
+            "Returns the certificate IDs in storage for each round",
+            map(array(object(&[
+                ("certificateId", string("The ID of the certificate")),
+                ("batchId", string("The ID of the batch")),
+                ("author", address()),
+            ]))),
+        ),
+        ("get", "/bft/certificates/{round}") => {
+            Description::new("Returns the certificates in storage for the round", array(component("BatchCertificate")))
+                .response(ResponseBody::Negotiated)
+        }
+        ("get", "/bft/certificate/{id}") => {
+            Description::new("Returns the certificate in storage", component("BatchCertificate"))
+                .response(ResponseBody::Negotiated)
+        }
+        ("get", "/bft/leader") => Description::new(
+            "Returns the latest and current leaders",
+            object(&[
+                ("latestLeader", nullable(object(&[("round", integer()), ("leader", address())]))),
+                ("leader", nullable(address())),
+                ("leaderCertificateId", nullable(string("The ID of the leader certificate"))),
+            ]),
+        ),
+        ("get", "/bft/committee/{round}") => {
+            Description::new("Returns the committee lookback for the round", component("Committee"))
+        }
+        ("get", "/bft/proposal") => Description::new(
+            "Returns the batch proposal pending signatures, if any",
+            nullable(object(&[
+                ("batchId", string("The ID of the batch")),
+                ("round", integer()),
+                ("timestamp", integer()),
+                ("author", address()),
+                ("transmissionIds", array(string("The ID of the transmission"))),
+                ("signers", array(address())),
+                ("nonsigners", array(address())),
+                ("isQuorumThresholdReached", boolean()),
+            ])),
+        ),
+        // The find routes.
+        ("get", "/find/blockHash/{tx_id}") => Description::new(
+            "Returns the hash of the block containing the transaction",
+            nullable(prefixed("ab1", "The hash of the block")),
+        ),
+        ("get", "/find/blockHeight/{state_root}") => {
+            Description::new("Returns the height of the block with the state root", nullable(height()))
+        }
+        ("get", "/find/transactionID/deployment/{program_id}") => Description::new(
+            "Returns the ID of the program's deployment",
+            nullable(prefixed("at1", "The ID of the transaction")),
+        ),
+        ("get", "/find/transactionID/{transition_id}") => Description::new(
+            "Returns the ID of the transaction containing the transition",
+            nullable(prefixed("at1", "The ID of the transaction")),
+        ),
+        ("get", "/find/transitionID/{input_or_output_id}") => Description::new(
+            "Returns the ID of the transition with the input or output",
+            nullable(prefixed("au1", "The ID of the transition")),
+        ),
+        // The peer routes.
+        ("get", "/peers/count") => Description::new("Returns the number of connected peers", integer()),
+        ("get", "/peers/all") => {
+            Description::new("Returns the connected peers", array(string("The address of the peer")))
+        }
+        ("get", "/peers/all/metrics") => Description::new(
+            "Returns the connected peers and their node types",
+            array(pair("The address of the peer, and its node type")),
+        ),
+        // The program routes.
+        ("get", "/program/{id}") => Description::new("Returns the program", component("Program")),
+        ("get", "/program/{id}/metadata") => Description::new(
+            "Returns the imports, deployment, functions and mappings of the program",
+            program_metadata(),
+        ),
+        ("get", "/program/{id}/mappings") => {
+            Description::new("Returns the mapping names of the program", array(string("The name of the mapping")))
+        }
+        ("get", "/program/{id}/mapping/{name}/{key}") => Description::new(
+            "Returns the value of the mapping entry",
+            one_of([
+                nullable(component("Value")),
+                object(&[("data", nullable(component("Value"))), ("height", height())]),
+            ]),
+        )
+        .query(&[Metadata::PARAMS]),
+        // The miscellaneous routes.
+        ("get", "/blocks") => Description::new("Returns the blocks in the given range", array(component("Block")))
+            .query(&[BlockRange::PARAMS])
+            .response(ResponseBody::Negotiated),
+        ("get", "/blocks/stream") => Description::new("Streams the blocks in the given range", component("Block"))
+            .query(&[BlockStreamRange::PARAMS])
+            .response(ResponseBody::Stream),
+        ("get", "/height/{hash}") => Description::new("Returns the height of the block with the hash", height()),
+        ("get", "/memoryPool/transmissions") => {
+            Description::new("Returns the transmissions in the memory pool", map(component("Transmission")))
+        }
+        ("get", "/memoryPool/solutions") => {
+            Description::new("Returns the solutions in the memory pool", map(component("Solution")))
+        }
+        ("get", "/memoryPool/transactions") => {
+            Description::new("Returns the transactions in the memory pool", map(component("Transaction")))
+        }
+        ("get", "/memoryPool/transaction/{id}") => Description::new(
+            "Returns the transaction in the memory pool, with its stage and age",
+            memory_pool_entry("transmission", component("Transmission")),
+        ),
+        ("get", "/memoryPool/solution/{id}") => Description::new(
+            "Returns the solution in the memory pool, with its stage and age",
+            memory_pool_entry("transmission", component("Transmission")),
+        ),
+        ("get", "/memoryPool/entries") => Description::new(
+            "Returns the transactions in the memory pool that match the filters",
+            array(memory_pool_entry("transaction", component("Transaction"))),
+        )
+        .query(&[MemoryPoolQuery::PARAMS]),
+        ("get", "/memoryPool/counts") => Description::new(
+            "Returns the number of transmissions in each stage of the memory pool",
+            map(object(&[("transactions", integer()), ("solutions", integer()), ("ratifications", integer())])),
+        ),
+        ("get", "/memoryPool/config") => Description::new(
+            "Returns the effective limits of the memory pool",
+            object(&[
+                ("capacityForDeployments", integer()),
+                ("capacityForExecutions", integer()),
+                ("capacityForSolutions", integer()),
+                ("maxDeploymentsPerInterval", integer()),
+                ("maxPendingPerPayer", integer()),
+                ("maxTransmissionsTolerance", integer()),
+            ]),
+        ),
+        ("get", "/statePath/{commitment}") => {
+            Description::new("Returns the state path for the commitment", component("StatePath"))
+                .response(ResponseBody::Negotiated)
+        }
+        ("get", "/stateRoot/latest") => Description::new("Returns the latest state root", state_root()),
+        ("get", "/stateRoot/{height}") => Description::new("Returns the state root at the given height", state_root()),
+        ("get", "/committee/latest") => Description::new("Returns the latest committee", component("Committee")),
+        ("get", "/committee/{height}") => {
+            Description::new("Returns the committee at the given height", component("Committee"))
+        }
+        ("get", "/delegators/{validator}") => {
+            Description::new("Returns the delegators of the validator", array(address()))
+        }
+        ("get", "/fee/estimate") => {
+            Description::new("Returns priority fee estimates from recent blocks", fee_estimate())
+                .query(&[FeeEstimateQuery::PARAMS])
+        }
+        ("post", "/fee/estimate") => Description::new("Returns the base fee of a deployment or execution", base_fee())
+            .body(RequestBody::Json(
+                "The deployment or execution",
+                one_of([component("Deployment"), component("Execution")]),
+            )),
+        ("get", "/events") => Description::new(
+            "Streams the ledger and memory pool events",
+            string("The events, as `event` and `data` fields"),
+        )
+        .query(&[EventsQuery::PARAMS])
+        .response(ResponseBody::EventStream),
+        ("get", "/openapi.json") => {
+            Description::new("Returns this OpenAPI document", object_with("The OpenAPI 3 document"))
+        }
+        ("get", "/health/live") => Description::new(
+            "Returns whether the node is live",
+            object(&[("live", boolean()), ("height", height()), ("failing", array(string("The failing check")))]),
+        ),
+        ("get", "/health/ready") => Description::new(
+            "Returns whether the node is ready, along with the failing checks",
+            object(&[
+                ("ready", boolean()),
+                ("failing", array(string("The failing check"))),
+                ("checks", map(object_with("The details of the check, and whether it passed as `ok`"))),
+            ]),
+        ),
+        #[cfg(feature = "history")]
+        ("get", "/block/{blockHeight}/history/{mapping}") => Description::new(
+            "Returns the historical values of a staking mapping",
+            object_with("The entries of the mapping at the given height"),
+        ),
+        #[cfg(feature = "history")]
+        ("get", "/program/{id}/mapping/{name}/{key}/history") => Description::new(
+            "Returns the value of the mapping entry at every height where it changed",
+            mapping_history(),
+        )
+        .query(&[MappingHistoryQuery::PARAMS]),
+        _ => return None,
+    };
+    Some(description)
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // GET /<network>/openapi.json
+    pub(crate) async fn get_openapi(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        let Some(network) = network_name::<N>() else {
+            return Err(RestError(format!("Unknown network ID ({})", N::ID)));
+        };
+        Ok(ErasedJson::pretty(openapi_document(network, &rest.operations, rest.jwt.is_broadcast_protected())))
+    }
+}
+
+/// Returns the OpenAPI 3 document describing the given routes of the given network.
+fn openapi_document(network: &str, operations: &[Operation], broadcast_protected: bool) -> Value {
+    let prefix = format!("/{network}");
+    // Group the operations by path.
+    let mut paths = Map::new();
+    for operation in operations {
+        // Write the path parameters in braces, as in the OpenAPI document.
+        let path = operation
+            .path
+            .split('/')
+            .map(|segment| match segment.strip_prefix(':') {
+                Some(name) => format!("{{{name}}}"),
+                None => segment.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+        let method = operation.method.as_str().to_lowercase();
+        // Describe the route, by its path following `/<network>` if prefixed.
+        let description = describe(&method, path.strip_prefix(&prefix).unwrap_or(&path))
+            .unwrap_or_else(|| Description::new("Undocumented", json!({})));
+        let entry = paths.entry(path.clone()).or_insert_with(|| json!({}));
+        entry[method] = describe_operation(&path, operation, &description, broadcast_protected);
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "snarkOS REST API",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": paths,
+        "components": {
+            "schemas": components(),
+            "securitySchemes": {
+                "bearerAuth": { "type": "http", "scheme": "bearer", "bearerFormat": "JWT" },
+            },
+        },
+    })
+}
+
+/// Returns the OpenAPI operation object for the given route, with the given path and description.
+fn describe_operation(path: &str, route: &Operation, description: &Description, broadcast_protected: bool) -> Value {
+    // Collect the path parameters, followed by the query parameters.
+    let mut parameters = path
+        .split('/')
+        .filter_map(|segment| segment.strip_prefix('{')?.strip_suffix('}'))
+        .map(|name| {
+            let schema = path_param_schema(name).unwrap_or_else(|| json!({ "type": "string" }));
+            json!({ "name": name, "in": "path", "required": true, "schema": schema })
+        })
+        .collect::<Vec<_>>();
+    parameters.extend(description.query.iter().flat_map(|params| params.iter()).map(|param| {
+        json!({
+            "name": param.name,
+            "in": "query",
+            "required": param.required,
+            "description": param.description,
+            "schema": query_param_schema(param.kind),
+        })
+    }));
+
+    let mut operation = json!({
+        "summary": description.summary,
+        "parameters": parameters,
+        "responses": {
+            "200": { "description": "Success", "content": response_content(description.response, &description.schema) },
+            "500": { "description": "Failure", "content": { "text/plain": { "schema": { "type": "string" } } } },
+        },
+    });
+
+    // Describe the request body.
+    if let Some(body) = &description.body {
+        let (description, content) = match body {
+            RequestBody::Json(description, schema) => {
+                (description, json!({ "application/json": { "schema": schema } }))
+            }
+            RequestBody::JsonOrBinary(description, schema) => (
+                description,
+                json!({
+                    "application/json": { "schema": schema },
+                    OCTET_STREAM: { "schema": { "type": "string", "format": "binary" } },
+                }),
+            ),
+        };
+        operation["requestBody"] = json!({ "description": description, "required": true, "content": content });
+    }
+
+    // Describe the rejections, whose body holds the error code, whether to retry, and the error message.
+    if description.rejects {
+        operation["responses"]["200"]["description"] = json!(
+            "Accepted for processing. The transmission is verified once it leaves the memory pool queue, \
+             and a refused transaction is reported by `/transaction/status/{id}`"
+        );
+        let rejections = [
+            ("400", "Rejected as a fee transaction (`fee_transaction`)"),
+            ("409", "Rejected as recently received (`duplicate`), or as already in the ledger (`already_in_ledger`)"),
+            ("413", "Rejected as too large (`too_large`)"),
+            (
+                "422",
+                "Rejected as not well-formed (`malformed`), which only covers a transaction failing to serialize \
+                 or to expose its priority fee, and a solution failing the puzzle on a node without consensus",
+            ),
+            ("429", "Rejected as the fee payer has too many pending transactions (`payer_limit_exceeded`)"),
+            ("503", "Rejected as the memory pool is full (`queue_full`), or as the node is syncing (`node_syncing`)"),
+        ];
+        for (status, description) in rejections {
+            let schema = json!({
+                "type": "object",
+                "properties": {
+                    "code": { "type": "string" },
+                    "retryable": { "type": "boolean" },
+                    "message": { "type": "string" },
+                },
+            });
+            let content = json!({ "application/json": { "schema": schema } });
+            operation["responses"][status] = json!({ "description": description, "content": content });
+        }
+    }
+
+    // Describe the rate limit.
+    operation["x-rate-limit-group"] = json!(route.group.map_or("per-call", RouteGroup::name));
+    // Describe the access control.
+    if let Some(scope) = route.access.scope(broadcast_protected) {
+        operation["security"] = json!([{ "bearerAuth": [] }]);
+        operation["x-required-scope"] = json!(scope.to_string());
+        operation["responses"]["401"] = json!({ "description": "Missing, invalid, expired or revoked JWT" });
+        operation["responses"]["403"] = json!({ "description": format!("JWT lacks the '{scope}' scope") });
+    }
+
+    operation
+}
+
+/// Returns the JSON schema of the path parameter with the given name, if it is known.
+/// Note: The path parameters are named consistently across the routes, so the name determines the type.
+fn path_param_schema(name: &str) -> Option<Value> {
+    let height = json!({ "type": "integer", "minimum": 0, "maximum": u32::MAX });
+    let schema = match name {
+        "height" | "blockHeight" => height,
+        "round" => json!({ "type": "integer", "minimum": 0 }),
+        "height_or_hash" => json!({ "oneOf": [height, { "type": "string", "pattern": "^ab1" }] }),
+        "address" | "validator" => json!({ "type": "string", "pattern": "^aleo1" }),
+        "hash" => json!({ "type": "string", "pattern": "^ab1" }),
+        "tx_id" => json!({ "type": "string", "pattern": "^at1" }),
+        "transition_id" => json!({ "type": "string", "pattern": "^au1" }),
+        "state_root" => json!({ "type": "string", "pattern": "^sr1" }),
+        "program_id" => json!({ "type": "string", "pattern": "\\.aleo$" }),
+        "id" | "name" | "key" | "mapping" | "commitment" | "input_or_output_id" => json!({ "type": "string" }),
+        _ => return None,
+    };
+    Some(schema)
+}
+
+/// Returns the JSON schema of a query parameter of the given type.
+/// Note: The integer query parameters are heights, counts and limits, which are never negative.
+fn query_param_schema(kind: &str) -> Value {
+    match kind {
+        "integer" => integer(),
+        kind => json!({ "type": kind }),
+    }
+}
+
+/// Returns the OpenAPI content object for the given response, whose JSON values have the given schema.
+fn response_content(response: ResponseBody, schema: &Value) -> Value {
+    let binary = json!({ "schema": { "type": "string", "format": "binary" } });
+    match response {
+        ResponseBody::Json => json!({ "application/json": { "schema": schema } }),
+        ResponseBody::Negotiated => json!({ "application/json": { "schema": schema }, OCTET_STREAM: binary }),
+        ResponseBody::Stream => json!({ "application/x-ndjson": { "schema": schema }, OCTET_STREAM: binary }),
+        ResponseBody::EventStream => json!({ "text/event-stream": { "schema": schema } }),
+    }
+}
+
+/// Returns the schemas of the snarkVM types, as serialized to JSON by snarkVM.
+fn components() -> Value {
+    json!({
+        "Block": object_with("A block"),
+        "ConfirmedTransaction": object_with("A transaction, along with its finalize operations and whether it was accepted"),
+        "Transaction": object_with("A deployment, execution or fee transaction"),
+        "Transmission": object_with("A transaction, solution or ratification in the memory pool"),
+        "Solution": object_with("A puzzle solution"),
+        "BatchCertificate": object_with("A batch certificate of the BFT"),
+        "Committee": object_with("The committee of validators"),
+        "Deployment": object_with("The deployment of a program"),
+        "Execution": object_with("The execution of a program"),
+        "Program": string("The source code of the program"),
+        "StatePath": string("The state path of a commitment"),
+        "Value": string("A value, in the syntax of Aleo instructions"),
+    })
+}
+
+/// Returns the schema of the snarkVM type with the given name.
+fn component(name: &str) -> Value {
+    json!({ "$ref": format!("#/components/schemas/{name}") })
+}
+
+/// Returns the schema of a non-negative integer.
+fn integer() -> Value {
+    json!({ "type": "integer", "minimum": 0 })
+}
+
+/// Returns the schema of a block height.
+fn height() -> Value {
+    json!({ "type": "integer", "minimum": 0, "maximum": u32::MAX })
+}
+
+/// Returns the schema of a boolean.
+fn boolean() -> Value {
+    json!({ "type": "boolean" })
+}
+
+/// Returns the schema of a string with the given description.
+fn string(description: &str) -> Value {
+    json!({ "type": "string", "description": description })
+}
+
+/// Returns the schema of a string with the given description, starting with the given prefix, as the IDs do.
+fn prefixed(prefix: &str, description: &str) -> Value {
+    json!({ "type": "string", "pattern": format!("^{prefix}"), "description": description })
+}
+
+/// Returns the schema of an address.
+fn address() -> Value {
+    prefixed("aleo1", "The address")
+}
+
+/// Returns the schema of a state root.
+fn state_root() -> Value {
+    prefixed("sr1", "The state root")
+}
+
+/// Returns the schema of a pair of strings with the given description.
+fn pair(description: &str) -> Value {
+    json!({ "type": "array", "items": { "type": "string" }, "minItems": 2, "maxItems": 2, "description": description })
+}
+
+/// Returns the schema of an array of items with the given schema.
+fn array(items: Value) -> Value {
+    json!({ "type": "array", "items": items })
+}
+
+/// Returns the schema of an object whose values have the given schema, keyed by strings.
+fn map(values: Value) -> Value {
+    json!({ "type": "object", "additionalProperties": values })
+}
+
+/// Returns the schema of an object with the given description, whose properties are left undescribed.
+fn object_with(description: &str) -> Value {
+    json!({ "type": "object", "description": description })
+}
+
+/// Returns the schema of an object with the given properties, which are all present.
+fn object(properties: &[(&str, Value)]) -> Value {
+    let required = properties.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+    let properties = properties.iter().map(|(name, schema)| (name.to_string(), schema.clone())).collect::<Map<_, _>>();
+    json!({ "type": "object", "properties": properties, "required": required })
+}
+
+/// Returns the schema of an object with the given required properties, followed by the given optional properties.
+fn object_with_optional(properties: &[(&str, Value)], optional: &[(&str, Value)]) -> Value {
+    let mut schema = object(properties);
+    for (name, property) in optional {
+        schema["properties"][*name] = property.clone();
+    }
+    schema
+}
+
+/// Returns the schema of the values with the given schema, or `null`.
+fn nullable(schema: Value) -> Value {
+    match schema.get("$ref") {
+        // Note: The siblings of a reference are ignored, so the reference is wrapped.
+        Some(_) => json!({ "allOf": [schema], "nullable": true }),
+        None => {
+            let mut schema = schema;
+            schema["nullable"] = json!(true);
+            schema
+        }
+    }
+}
+
+/// Returns the schema of the values matching exactly one of the given schemas.
+fn one_of<const LEN: usize>(schemas: [Value; LEN]) -> Value {
+    json!({ "oneOf": Vec::from(schemas) })
+}
+
+/// Returns the schema of the response of `get_mapping_values`, in full or by page, with or without metadata.
+fn mapping_values() -> Value {
+    let entries = array(pair("The key and the value of the entry"));
+    one_of([
+        entries.clone(),
+        object(&[("data", entries.clone()), ("height", height())]),
+        object(&[
+            ("data", entries),
+            ("height", height()),
+            ("cursor", nullable(string("The cursor of the next page, if there is one"))),
+            ("complete", json!({ "type": "boolean", "description": "Whether every key of the mapping is indexed" })),
+        ]),
+    ])
+}
+
+/// Returns the schema of the response of `rpc`, for a single request or a batch.
+fn rpc_response() -> Value {
+    let response = object_with_optional(
+        &[("jsonrpc", json!({ "type": "string", "enum": ["2.0"] })), ("id", object_with("The ID of the request"))],
+        &[
+            ("result", object_with("The result of the call")),
+            ("error", object_with("The error of the call, with its code, message and data")),
+        ],
+    );
+    one_of([response.clone(), array(response)])
+}
+
+/// Returns the schema of the body of `rpc`, for a single request or a batch.
+fn rpc_request() -> Value {
+    let request = object_with_optional(
+        &[("jsonrpc", json!({ "type": "string", "enum": ["2.0"] })), ("method", string("The name of the method"))],
+        &[("params", object_with("The parameters of the method")), ("id", object_with("The ID of the request"))],
+    );
+    one_of([request.clone(), array(request)])
+}
+
+/// Returns the schema of the response of `get_transaction_status`.
+fn transaction_status() -> Value {
+    let stages = ["confirmed", "rejected", "aborted", "certificate", "worker", "inbound", "dropped", "unknown"];
+    object_with_optional(
+        &[("id", prefixed("at1", "The ID of the transaction")), ("stage", json!({ "type": "string", "enum": stages }))],
+        &[
+            ("height", height()),
+            ("reason", string("The reason the transaction was rejected, aborted or dropped")),
+            ("feeTransactionId", prefixed("at1", "The ID of the fee transaction of a rejected transaction")),
+            ("certificateId", string("The ID of the certificate holding the transaction")),
+            ("round", integer()),
+            ("droppedAt", integer()),
+        ],
+    )
+}
+
+/// Returns the schema of the response of `transaction_simulate`.
+fn simulation() -> Value {
+    object_with_optional(
+        &[
+            ("transactionId", prefixed("at1", "The ID of the transaction")),
+            ("valid", boolean()),
+            ("status", json!({ "type": "string", "enum": ["accepted", "rejected", "aborted", "invalid"] })),
+            ("baseFee", integer()),
+            ("priorityFee", integer()),
+            ("mappingWrites", array(simulated_write())),
+        ],
+        &[("reason", string("The reason the transaction would not be accepted"))],
+    )
+}
+
+/// Returns the schema of a mapping write of `transaction_simulate`, whose key and value are `null` if not found.
+fn simulated_write() -> Value {
+    // Note: The key and value are decoded on a best-effort basis, as the finalize operations only commit to their IDs.
+    let mut value = nullable(component("Value"));
+    value["description"] = json!(
+        "The value written, if it is among the plaintext arguments passed to the finalize logic. \
+         A value computed by the finalize logic is `null`, while its `valueId` is set"
+    );
+    object(&[
+        ("programId", nullable(string("The ID of the program"))),
+        ("mapping", nullable(string("The name of the mapping"))),
+        (
+            "key",
+            nullable(string(
+                "The key of the entry, if it is among the plaintext arguments passed to the finalize logic",
+            )),
+        ),
+        ("value", value),
+        ("removed", boolean()),
+        ("mappingId", string("The ID of the mapping")),
+        ("keyId", string("The ID of the key")),
+        ("valueId", nullable(string("The ID of the value written"))),
+    ])
+}
+
+/// Returns the schema of the response of `get_address_transitions`.
+fn address_transitions() -> Value {
+    let transition = object(&[
+        ("height", height()),
+        ("transactionId", prefixed("at1", "The ID of the transaction")),
+        ("transitionId", prefixed("au1", "The ID of the transition")),
+        ("programId", string("The program of the transition")),
+        ("functionName", string("The function of the transition")),
+    ]);
+    object(&[
+        ("address", address()),
+        ("transitions", array(transition)),
+        ("balances", array(object(&[("height", height()), ("balance", nullable(integer()))]))),
+        ("cursor", nullable(string("The cursor of the next page, if there is one"))),
+        ("indexedHeight", nullable(height())),
+    ])
+}
+
+/// Returns the schema of the response of `get_program_metadata`.
+fn program_metadata() -> Value {
+    object(&[
+        ("programId", string("The ID of the program")),
+        ("imports", array(string("The ID of the imported program"))),
+        ("dependencyGraph", map(array(string("The ID of the imported program")))),
+        (
+            "deployment",
+            nullable(object(&[
+                ("transactionId", prefixed("at1", "The ID of the deployment transaction")),
+                ("height", nullable(height())),
+                ("edition", nullable(integer())),
+            ])),
+        ),
+        (
+            "functions",
+            array(object(&[
+                ("name", string("The name of the function")),
+                ("inputs", array(string("The type of the input"))),
+                ("outputs", array(string("The type of the output"))),
+                ("hasFinalize", boolean()),
+            ])),
+        ),
+        (
+            "mappings",
+            array(object(&[
+                ("name", string("The name of the mapping")),
+                ("key", string("The type of the keys")),
+                ("value", string("The type of the values")),
+            ])),
+        ),
+    ])
+}
+
+/// Returns the schema of an entry of the memory pool, holding the given transmission.
+fn memory_pool_entry(name: &str, transmission: Value) -> Value {
+    let mut properties = vec![
+        ("id", string("The ID of the transmission")),
+        ("stage", json!({ "type": "string", "enum": ["inbound", "worker", "proposed"] })),
+        ("ageSecs", nullable(integer())),
+        (name, transmission),
+    ];
+    if name == "transaction" {
+        properties.push(("type", json!({ "type": "string", "enum": ["deploy", "execute", "fee"] })));
+    }
+    object(&properties)
+}
+
+/// Returns the schema of the response of `get_fee_estimate`.
+fn fee_estimate() -> Value {
+    object(&[
+        ("startHeight", height()),
+        ("endHeight", height()),
+        ("numTransactions", integer()),
+        ("memoryPoolTransactions", nullable(integer())),
+        ("pressure", nullable(json!({ "type": "number", "minimum": 0 }))),
+        ("percentiles", map(integer())),
+        ("priorityFee", map(integer())),
+    ])
+}
+
+/// Returns the schema of the response of `post_fee_estimate`, for a deployment or an execution.
+fn base_fee() -> Value {
+    one_of([
+        object(&[
+            ("type", json!({ "type": "string", "enum": ["deploy"] })),
+            ("baseFee", integer()),
+            ("storageCost", integer()),
+            ("synthesisCost", integer()),
+            ("namespaceCost", integer()),
+        ]),
+        object(&[
+            ("type", json!({ "type": "string", "enum": ["execute"] })),
+            ("baseFee", integer()),
+            ("storageCost", integer()),
+            ("finalizeCost", integer()),
+        ]),
+    ])
+}
+
+/// Returns the schema of the response of `get_mapping_history`.
+#[cfg(feature = "history")]
+fn mapping_history() -> Value {
+    let change = object(&[("height", height()), ("removed", boolean()), ("value", nullable(component("Value")))]);
+    object(&[
+        ("programId", string("The ID of the program")),
+        ("mapping", string("The name of the mapping")),
+        ("key", string("The key of the entry")),
+        ("indexedHeight", nullable(height())),
+        ("start", nullable(change.clone())),
+        ("changes", array(change)),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the routes of a router covering the path parameters, the query parameters and the access control.
+    fn operations() -> Vec<Operation> {
+        let jwt = Arc::new(JwtConfig::default());
+        let scoped = ApiRouter::<()>::new()
+            .get("/mainnet/program/:id/mapping/:name", RouteGroup::Bulk, || async {})
+            .access(Access::Scoped(Scope::ReadMapping), &jwt);
+        let broadcast = ApiRouter::new()
+            .post("/mainnet/transaction/broadcast", RouteGroup::Broadcast, || async {})
+            .access(Access::Broadcast, &jwt);
+        let (_, operations) = ApiRouter::new()
+            .merge(scoped)
+            .merge(broadcast)
+            .get("/mainnet/stateRoot/:height", RouteGroup::Point, || async {})
+            .get("/mainnet/fee/estimate", RouteGroup::Bulk, || async {})
+            .post("/mainnet/fee/estimate", RouteGroup::Bulk, || async {})
+            .post_per_call("/mainnet/rpc", || async {})
+            .get("/mainnet/unknown", RouteGroup::Point, || async {})
+            .get("/health/live", RouteGroup::Point, || async {})
+            .into_parts();
+        operations
+    }
+
+    #[test]
+    fn test_path_params() {
+        // Ensure the path parameters of the routes have a known schema.
+        for name in ["height", "height_or_hash", "address", "tx_id", "id", "name", "key"] {
+            assert!(path_param_schema(name).is_some(), "The path parameter '{name}' has no schema");
+        }
+
+        // Ensure the integer path parameters are documented as such.
+        let document = openapi_document("mainnet", &operations(), false);
+        let parameters = &document["paths"]["/mainnet/stateRoot/{height}"]["get"]["parameters"];
+        assert_eq!(parameters[0]["name"], "height");
+        assert_eq!(parameters[0]["schema"]["type"], "integer");
+    }
+
+    #[test]
+    fn test_schemas() {
+        let document = openapi_document("mainnet", &operations(), false);
+        let paths = &document["paths"];
+
+        // Ensure the query parameters and the response are typed.
+        let operation = &paths["/mainnet/program/{id}/mapping/{name}"]["get"];
+        let parameters = operation["parameters"].as_array().unwrap();
+        let limit = parameters.iter().find(|parameter| parameter["name"] == "limit").unwrap();
+        assert_eq!(limit["schema"]["type"], "integer");
+        assert_eq!(limit["schema"]["minimum"], 0);
+        let schema = &operation["responses"]["200"]["content"]["application/json"]["schema"];
+        assert_eq!(schema["oneOf"].as_array().unwrap().len(), 3);
+        assert_eq!(schema["oneOf"][2]["properties"]["cursor"]["nullable"], true);
+        // Ensure the scoped route requires a JWT.
+        assert_eq!(operation["x-required-scope"], "read-mapping");
+        // Ensure the rate limit group of the route is described.
+        assert_eq!(operation["x-rate-limit-group"], "bulk");
+        assert_eq!(paths["/mainnet/stateRoot/{height}"]["get"]["x-rate-limit-group"], "point");
+
+        // Ensure a route registered for both methods is described for each.
+        assert_eq!(
+            paths["/mainnet/fee/estimate"]["get"]["summary"],
+            "Returns priority fee estimates from recent blocks"
+        );
+        // Ensure the request body is typed, and its schema is defined.
+        let operation = &paths["/mainnet/fee/estimate"]["post"];
+        let schema = &operation["requestBody"]["content"]["application/json"]["schema"];
+        assert_eq!(schema["oneOf"][0]["$ref"], "#/components/schemas/Deployment");
+        assert_eq!(document["components"]["schemas"]["Deployment"]["type"], "object");
+
+        // Ensure the broadcast route only requires a JWT if the node protects it.
+        assert!(paths["/mainnet/transaction/broadcast"]["post"]["x-required-scope"].is_null());
+        let protected = openapi_document("mainnet", &operations(), true);
+        assert_eq!(protected["paths"]["/mainnet/transaction/broadcast"]["post"]["x-required-scope"], "broadcast");
+        // Ensure the route charging each of its calls is described as such.
+        assert_eq!(paths["/mainnet/rpc"]["post"]["x-rate-limit-group"], "per-call");
+
+        // Ensure the routes are listed as registered, whether described or not.
+        assert_eq!(paths["/mainnet/unknown"]["get"]["summary"], "Undocumented");
+        assert_eq!(paths["/health/live"]["get"]["summary"], "Returns whether the node is live");
+    }
+}
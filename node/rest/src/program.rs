@@ -0,0 +1,107 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use snarkvm::prelude::Program;
+
+use indexmap::IndexMap;
+use serde_json::{Value, json};
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // GET /<network>/program/{programID}/metadata
+    pub(crate) async fn get_program_metadata(
+        State(rest): State<Self>,
+        Path(id): Path<ProgramID<N>>,
+    ) -> Result<ErasedJson, RestError> {
+        // Prepare a closure for the blocking work.
+        let get_metadata = move || -> Result<ErasedJson, RestError> {
+            let program = rest.ledger.get_program(id)?;
+
+            // Resolve the imports recursively, listing the direct imports of each program in the graph.
+            let mut dependencies = IndexMap::new();
+            let mut pending = vec![program.clone()];
+            while let Some(program) = pending.pop() {
+                let imports = program.imports().keys().copied().collect::<Vec<_>>();
+                for import in &imports {
+                    if !dependencies.contains_key(import) && !pending.iter().any(|pending| pending.id() == import) {
+                        pending.push(rest.ledger.get_program(*import)?);
+                    }
+                }
+                dependencies.insert(*program.id(), imports);
+            }
+
+            // Retrieve the deployment, which is missing for the programs included in the genesis block.
+            let deployment = match rest.ledger.find_transaction_id_from_program_id(&id)? {
+                Some(transaction_id) => {
+                    let transaction = rest.ledger.get_transaction(transaction_id)?;
+                    let block_hash = rest.ledger.find_block_hash(&transaction_id)?;
+                    let height = block_hash.map(|hash| rest.ledger.get_height(&hash)).transpose()?;
+                    json!({
+                        "transactionId": transaction_id,
+                        "height": height,
+                        "edition": transaction.deployment().map(|deployment| deployment.edition()),
+                    })
+                }
+                None => Value::Null,
+            };
+
+            Ok(ErasedJson::pretty(json!({
+                "programId": id,
+                "imports": program.imports().keys().collect::<Vec<_>>(),
+                "dependencyGraph": dependencies,
+                "deployment": deployment,
+                "functions": functions_of(&program),
+                "mappings": mappings_of(&program),
+            })))
+        };
+
+        // Retrieve the program and its dependencies from the ledger.
+        match tokio::task::spawn_blocking(get_metadata).await {
+            Ok(json) => json,
+            Err(err) => Err(RestError(format!("Unable to get the metadata of '{id}' - {err}"))),
+        }
+    }
+}
+
+/// Returns the functions of the given program, with their input and output types.
+fn functions_of<N: Network>(program: &Program<N>) -> Vec<Value> {
+    program
+        .functions()
+        .values()
+        .map(|function| {
+            json!({
+                "name": function.name(),
+                "inputs": function.inputs().iter().map(|input| input.value_type().to_string()).collect::<Vec<_>>(),
+                "outputs": function.outputs().iter().map(|output| output.value_type().to_string()).collect::<Vec<_>>(),
+                "hasFinalize": function.finalize_logic().is_some(),
+            })
+        })
+        .collect()
+}
+
+/// Returns the mappings of the given program, with their key and value types.
+fn mappings_of<N: Network>(program: &Program<N>) -> Vec<Value> {
+    program
+        .mappings()
+        .values()
+        .map(|mapping| {
+            json!({
+                "name": mapping.name(),
+                "key": mapping.key().plaintext_type().to_string(),
+                "value": mapping.value().plaintext_type().to_string(),
+            })
+        })
+        .collect()
+}
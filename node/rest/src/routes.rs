@@ -14,16 +14,19 @@
 // limitations under the License.
 
 use super::*;
+use snarkos_node_consensus::AdmissionError;
 use snarkos_node_router::{SYNC_LENIENCY, messages::UnconfirmedSolution};
 use snarkvm::{
     ledger::puzzle::Solution,
     prelude::{Address, Identifier, LimitedWriter, Plaintext, ToBytes, block::Transaction},
 };
 
+use axum::response::IntoResponse;
 use indexmap::IndexMap;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::fmt::Write;
 
 /// The `get_blocks` query object.
 #[derive(Deserialize, Serialize)]
@@ -34,13 +37,54 @@ pub(crate) struct BlockRange {
     end: u32,
 }
 
+impl BlockRange {
+    /// The query parameters, as documented in the OpenAPI document.
+    pub(crate) const PARAMS: &'static [QueryParam] = &[
+        QueryParam::new("start", "integer", true, "The starting block height (inclusive)"),
+        QueryParam::new("end", "integer", true, "The ending block height (exclusive)"),
+    ];
+}
+
 /// The query object for `get_mapping_value` and `get_mapping_values`.
-#[derive(Copy, Clone, Deserialize, Serialize)]
+#[derive(Copy, Clone, Default, Deserialize, Serialize)]
 pub(crate) struct Metadata {
     metadata: Option<bool>,
     all: Option<bool>,
 }
 
+impl Metadata {
+    /// The query parameters, as documented in the OpenAPI document.
+    pub(crate) const PARAMS: &'static [QueryParam] = &[
+        QueryParam::new("metadata", "boolean", false, "Whether to include the height and the hash of the latest block"),
+        QueryParam::new("all", "boolean", false, "Whether to return every entry of the mapping"),
+    ];
+}
+
+/// The query object for paginating `get_mapping_values`.
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub(crate) struct MappingPage {
+    /// The maximum number of entries to return.
+    limit: Option<usize>,
+    /// The cursor returned by the previous page, if any, until the ledger advances.
+    cursor: Option<String>,
+    /// The prefix that the keys must start with, if any.
+    prefix: Option<String>,
+}
+
+impl MappingPage {
+    /// The query parameters, as documented in the OpenAPI document.
+    pub(crate) const PARAMS: &'static [QueryParam] = &[
+        QueryParam::new("limit", "integer", false, "The maximum number of entries to return"),
+        QueryParam::new("cursor", "string", false, "The cursor of the previous page, until the ledger advances"),
+        QueryParam::new("prefix", "string", false, "The prefix that the keys must start with"),
+    ];
+}
+
+/// The number of mapping entries returned per page when the caller does not specify a limit.
+const DEFAULT_MAPPING_PAGE_SIZE: usize = 100;
+/// The maximum number of mapping entries returned per page.
+const MAX_MAPPING_PAGE_SIZE: usize = 1000;
+
 impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
     // GET /<network>/block/height/latest
     pub(crate) async fn get_block_height_latest(State(rest): State<Self>) -> ErasedJson {
@@ -53,36 +97,42 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
     }
 
     // GET /<network>/block/latest
-    pub(crate) async fn get_block_latest(State(rest): State<Self>) -> ErasedJson {
-        ErasedJson::pretty(rest.ledger.latest_block())
+    pub(crate) async fn get_block_latest(State(rest): State<Self>, headers: HeaderMap) -> Result<Response, RestError> {
+        negotiate(&headers, rest.ledger.latest_block())
     }
 
     // GET /<network>/block/{height}
     // GET /<network>/block/{blockHash}
     pub(crate) async fn get_block(
         State(rest): State<Self>,
+        headers: HeaderMap,
         Path(height_or_hash): Path<String>,
-    ) -> Result<ErasedJson, RestError> {
+    ) -> Result<Response, RestError> {
         // Manually parse the height or the height of the hash, axum doesn't support different types
         // for the same path param.
-        let block = if let Ok(height) = height_or_hash.parse::<u32>() {
-            rest.ledger.get_block(height)?
+        let (height, hash) = if let Ok(height) = height_or_hash.parse::<u32>() {
+            (height, rest.ledger.get_hash(height)?)
         } else {
             let hash = height_or_hash
                 .parse::<N::BlockHash>()
                 .map_err(|_| RestError("invalid input, it is neither a block height nor a block hash".to_string()))?;
 
-            rest.ledger.get_block_by_hash(&hash)?
+            (rest.ledger.get_height(&hash)?, hash)
         };
 
-        Ok(ErasedJson::pretty(block))
+        // Note: The blocks below the tip never change, so they are served from the block cache when possible.
+        let is_immutable = height < rest.ledger.latest_height();
+        conditional(&headers, entity_tag(hash, accepts_binary(&headers))?, is_immutable, || {
+            rest.block_cache.respond(&headers, hash, || rest.ledger.get_block(height))
+        })
     }
 
     // GET /<network>/blocks?start={start_height}&end={end_height}
     pub(crate) async fn get_blocks(
         State(rest): State<Self>,
+        headers: HeaderMap,
         Query(block_range): Query<BlockRange>,
-    ) -> Result<ErasedJson, RestError> {
+    ) -> Result<Response, RestError> {
         let start_height = block_range.start;
         let end_height = block_range.end;
 
@@ -102,16 +152,16 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         }
 
         // Prepare a closure for the blocking work.
-        let get_json_blocks = move || -> Result<ErasedJson, RestError> {
+        let get_blocks = move || -> Result<Response, RestError> {
             let blocks = cfg_into_iter!((start_height..end_height))
                 .map(|height| rest.ledger.get_block(height))
                 .collect::<Result<Vec<_>, _>>()?;
 
-            Ok(ErasedJson::pretty(blocks))
+            negotiate_all(&headers, blocks)
         };
 
-        // Fetch the blocks from ledger and serialize to json.
-        match tokio::task::spawn_blocking(get_json_blocks).await {
+        // Fetch the blocks from ledger and serialize them.
+        match tokio::task::spawn_blocking(get_blocks).await {
             Ok(json) => json,
             Err(err) => Err(RestError(format!("Failed to get blocks '{start_height}..{end_height}' - {err}"))),
         }
@@ -128,25 +178,113 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
     // GET /<network>/block/{height}/transactions
     pub(crate) async fn get_block_transactions(
         State(rest): State<Self>,
+        headers: HeaderMap,
         Path(height): Path<u32>,
-    ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.get_transactions(height)?))
+    ) -> Result<Response, RestError> {
+        let hash = rest.ledger.get_hash(height)?;
+        let is_immutable = height < rest.ledger.latest_height();
+        conditional(&headers, entity_tag(hash, accepts_binary(&headers))?, is_immutable, || {
+            negotiate(&headers, rest.ledger.get_transactions(height)?)
+        })
     }
 
     // GET /<network>/transaction/{transactionID}
     pub(crate) async fn get_transaction(
         State(rest): State<Self>,
+        headers: HeaderMap,
         Path(tx_id): Path<N::TransactionID>,
-    ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.get_transaction(tx_id)?))
+    ) -> Result<Response, RestError> {
+        // Ensure the transaction is confirmed, as a client may hold the entity tag of any transaction ID.
+        if !rest.ledger.contains_transaction_id(&tx_id)? {
+            return Err(RestError(format!("Missing transaction '{tx_id}' in the ledger")));
+        }
+        conditional(&headers, entity_tag(tx_id, accepts_binary(&headers))?, true, || {
+            negotiate(&headers, rest.ledger.get_transaction(tx_id)?)
+        })
     }
 
     // GET /<network>/transaction/confirmed/{transactionID}
     pub(crate) async fn get_confirmed_transaction(
+        State(rest): State<Self>,
+        headers: HeaderMap,
+        Path(tx_id): Path<N::TransactionID>,
+    ) -> Result<Response, RestError> {
+        negotiate(&headers, rest.ledger.get_confirmed_transaction(tx_id)?)
+    }
+
+    // GET /<network>/transaction/status/{transactionID}
+    pub(crate) async fn get_transaction_status(
         State(rest): State<Self>,
         Path(tx_id): Path<N::TransactionID>,
     ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.get_confirmed_transaction(tx_id)?))
+        // Retrieve the drop record, which is the only source of the reason a transaction was aborted.
+        let dropped = rest.consensus.as_ref().and_then(|consensus| consensus.get_dropped_transaction(&tx_id));
+
+        // Check if the transaction was included in a block.
+        if let Some(block_hash) = rest.ledger.find_block_hash(&tx_id)? {
+            let block = rest.ledger.get_block_by_hash(&block_hash)?;
+            let height = block.height();
+
+            if block.aborted_transaction_ids().contains(&tx_id) {
+                let reason = match dropped {
+                    Some(dropped) => dropped.reason.to_string(),
+                    None => format!("aborted in block {height}"),
+                };
+                return Ok(ErasedJson::pretty(json!({
+                    "id": tx_id,
+                    "stage": "aborted",
+                    "height": height,
+                    "reason": reason,
+                })));
+            }
+            for confirmed in block.transactions().iter() {
+                if confirmed.to_unconfirmed_transaction_id()? != tx_id {
+                    continue;
+                }
+                return Ok(match confirmed.is_accepted() {
+                    true => ErasedJson::pretty(json!({
+                        "id": tx_id,
+                        "stage": "confirmed",
+                        "height": height,
+                    })),
+                    false => ErasedJson::pretty(json!({
+                        "id": tx_id,
+                        "stage": "rejected",
+                        "height": height,
+                        "reason": "rejected during finalize, only the fee was charged",
+                        "feeTransactionId": confirmed.id(),
+                    })),
+                });
+            }
+        }
+
+        // Otherwise, check the memory pool stages, from the latest to the earliest.
+        let Some(consensus) = rest.consensus else {
+            return Err(RestError("Route isn't available for this node type".to_string()));
+        };
+        if let Some(certificate) = consensus.find_certificate_for_transaction(&tx_id) {
+            return Ok(ErasedJson::pretty(json!({
+                "id": tx_id,
+                "stage": "certificate",
+                "certificateId": certificate.id(),
+                "round": certificate.round(),
+            })));
+        }
+        if consensus.worker_transactions().any(|(id, _)| id == tx_id) {
+            return Ok(ErasedJson::pretty(json!({ "id": tx_id, "stage": "worker" })));
+        }
+        if consensus.contains_inbound_transaction(&tx_id) {
+            return Ok(ErasedJson::pretty(json!({ "id": tx_id, "stage": "inbound" })));
+        }
+        match dropped {
+            Some(dropped) => Ok(ErasedJson::pretty(json!({
+                "id": tx_id,
+                "stage": "dropped",
+                "reason": dropped.reason.to_string(),
+                "droppedAt": dropped.timestamp,
+            }))),
+            None => Ok(ErasedJson::pretty(json!({ "id": tx_id, "stage": "unknown" }))),
+        }
     }
 
     // GET /<network>/memoryPool/transmissions
@@ -178,9 +316,16 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
     // GET /<network>/program/{programID}
     pub(crate) async fn get_program(
         State(rest): State<Self>,
+        headers: HeaderMap,
         Path(id): Path<ProgramID<N>>,
-    ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.get_program(id)?))
+    ) -> Result<Response, RestError> {
+        // Ensure the program is deployed, as a client may hold the entity tag of any program ID.
+        if !rest.ledger.vm().contains_program(&id) {
+            return Err(RestError(format!("Missing program '{id}' in the ledger")));
+        }
+        conditional(&headers, entity_tag(id, false)?, true, || {
+            Ok(ErasedJson::pretty(rest.ledger.get_program(id)?).into_response())
+        })
     }
 
     // GET /<network>/program/{programID}/mappings
@@ -214,14 +359,23 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
     }
 
     // GET /<network>/program/{programID}/mapping/{mappingName}?all={true}&metadata={true}
+    // GET /<network>/program/{programID}/mapping/{mappingName}?limit={limit}&cursor={cursor}&prefix={prefix}
     pub(crate) async fn get_mapping_values(
         State(rest): State<Self>,
         Path((id, name)): Path<(ProgramID<N>, Identifier<N>)>,
         metadata: Query<Metadata>,
+        Query(page): Query<MappingPage>,
     ) -> Result<ErasedJson, RestError> {
+        // If pagination is requested, return a single page of the mapping.
+        if page.limit.is_some() || page.cursor.is_some() || page.prefix.is_some() {
+            return Self::get_mapping_page(rest, id, name, page).await;
+        }
+
         // Return an error if the `all` query parameter is not set to `true`.
         if metadata.all != Some(true) {
-            return Err(RestError("Invalid query parameter. At this time, 'all=true' must be included".to_string()));
+            return Err(RestError(
+                "Invalid query parameter. At this time, 'all=true' or pagination must be included".to_string(),
+            ));
         }
 
         // Retrieve the latest height.
@@ -248,12 +402,84 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         }
     }
 
+    /// Returns the page of the mapping entries following the cursor, ordered by the string of their key.
+    /// The cursor holds the height the previous page was read at, and the bytes of its last key, so a page is
+    /// only read at the height of the previous page, as the entries may have changed since.
+    /// The page is flagged as incomplete if an entry of the mapping has a key that is not indexed yet.
+    async fn get_mapping_page(
+        rest: Self,
+        id: ProgramID<N>,
+        name: Identifier<N>,
+        page: MappingPage,
+    ) -> Result<ErasedJson, RestError> {
+        let Some(index) = rest.mapping_keys.clone() else {
+            return Err(RestError("The mapping index is not enabled on this node".to_string()));
+        };
+        let limit = page.limit.unwrap_or(DEFAULT_MAPPING_PAGE_SIZE);
+        // Ensure the page size is bounded.
+        if limit == 0 || limit > MAX_MAPPING_PAGE_SIZE {
+            return Err(RestError(format!(
+                "The limit must be between 1 and {MAX_MAPPING_PAGE_SIZE} (requested {limit})"
+            )));
+        }
+        // Decode the cursor into the height of the previous page, and its last key.
+        let cursor = match &page.cursor {
+            Some(cursor) => Some(decode_cursor(cursor)?),
+            None => None,
+        };
+
+        let get_page = move || -> Result<ErasedJson, RestError> {
+            // Ensure the previous page was read at the latest height.
+            let height = rest.ledger.latest_height();
+            if let Some((cursor_height, _)) = &cursor {
+                if *cursor_height != height {
+                    return Err(stale_cursor(*cursor_height, height));
+                }
+            }
+
+            // Read one entry past the page, to tell whether there is a next page.
+            let after = cursor.as_ref().map(|(_, key_bytes)| key_bytes.as_slice());
+            let prefix = page.prefix.as_deref().unwrap_or_default();
+            let (mut entries, is_complete) = index.page(&rest.ledger, id, name, prefix, after, limit + 1)?;
+
+            // Ensure the ledger didn't advance while the page was read, so that the entries belong to the height.
+            let latest_height = rest.ledger.latest_height();
+            if latest_height != height {
+                return Err(match cursor {
+                    Some(_) => stale_cursor(height, latest_height),
+                    None => RestError("The ledger advanced while reading the mapping, please retry".to_string()),
+                });
+            }
+
+            // Return the page, along with the cursor for the next page if there is one.
+            let cursor = match entries.len() > limit {
+                true => Some(encode_cursor(height, &entries[limit - 1].0)),
+                false => None,
+            };
+            entries.truncate(limit);
+            let data = entries.into_iter().map(|(_, key, value)| (key, value)).collect::<Vec<_>>();
+
+            Ok(ErasedJson::pretty(json!({
+                "data": data,
+                "height": height,
+                "cursor": cursor,
+                "complete": is_complete,
+            })))
+        };
+
+        match tokio::task::spawn_blocking(get_page).await {
+            Ok(json) => json,
+            Err(err) => Err(RestError(format!("Unable to read mapping - {err}"))),
+        }
+    }
+
     // GET /<network>/statePath/{commitment}
     pub(crate) async fn get_state_path_for_commitment(
         State(rest): State<Self>,
+        headers: HeaderMap,
         Path(commitment): Path<Field<N>>,
-    ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.get_state_path_for_commitment(&commitment)?))
+    ) -> Result<Response, RestError> {
+        negotiate(&headers, rest.ledger.get_state_path_for_commitment(&commitment)?)
     }
 
     // GET /<network>/stateRoot/latest
@@ -363,11 +589,16 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
     // POST /<network>/transaction/broadcast
     pub(crate) async fn transaction_broadcast(
         State(rest): State<Self>,
-        Json(tx): Json<Transaction<N>>,
-    ) -> Result<ErasedJson, RestError> {
+        BinaryOrJson(tx): BinaryOrJson<Transaction<N>>,
+    ) -> Result<ErasedJson, BroadcastError> {
+        let tx_id = tx.id();
         // Do not process the transaction if the node is too far behind.
         if rest.routing.num_blocks_behind() > SYNC_LENIENCY {
-            return Err(RestError(format!("Unable to broadcast transaction '{}' (node is syncing)", fmt_id(tx.id()))));
+            return Err(BroadcastError::syncing(tx_id));
+        }
+        // Fee transactions are only ever created by the ledger.
+        if tx.is_fee() {
+            return Err(AdmissionError::<N>::FeeTransaction(tx_id.to_string()).into());
         }
 
         // If the transaction exceeds the transaction size limit, return an error.
@@ -376,17 +607,21 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         // TODO: Should this be a blocking task?
         let buffer = Vec::with_capacity(3000);
         if tx.write_le(LimitedWriter::new(buffer, N::MAX_TRANSACTION_SIZE)).is_err() {
-            return Err(RestError("Transaction size exceeds the byte limit".to_string()));
+            return Err(AdmissionError::<N>::TooLarge(tx_id.to_string()).into());
         }
 
+        // Ensure the transaction does not already exist in the ledger.
+        if rest.ledger.contains_transaction_id(&tx_id).map_err(BroadcastError::new::<N>)? {
+            return Err(AdmissionError::<N>::AlreadyInLedger(tx_id.to_string()).into());
+        }
         // If the consensus module is enabled, add the unconfirmed transaction to the memory pool.
         if let Some(consensus) = rest.consensus {
             // Add the unconfirmed transaction to the memory pool.
-            consensus.add_unconfirmed_transaction(tx.clone()).await?;
+            // Note: The transaction is accepted for processing, as the workers verify it once it leaves the queue.
+            consensus.add_unconfirmed_transaction(tx.clone()).await.map_err(BroadcastError::new::<N>)?;
         }
 
         // Prepare the unconfirmed transaction message.
-        let tx_id = tx.id();
         let message = Message::UnconfirmedTransaction(UnconfirmedTransaction {
             transaction_id: tx_id,
             transaction: Data::Object(tx),
@@ -402,24 +637,21 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
     pub(crate) async fn solution_broadcast(
         State(rest): State<Self>,
         Json(solution): Json<Solution<N>>,
-    ) -> Result<ErasedJson, RestError> {
+    ) -> Result<ErasedJson, BroadcastError> {
         // Do not process the solution if the node is too far behind.
         if rest.routing.num_blocks_behind() > SYNC_LENIENCY {
-            return Err(RestError(format!(
-                "Unable to broadcast solution '{}' (node is syncing)",
-                fmt_id(solution.id())
-            )));
+            return Err(BroadcastError::syncing(solution.id()));
         }
 
         // If the consensus module is enabled, add the unconfirmed solution to the memory pool.
         // Otherwise, verify it prior to broadcasting.
         match rest.consensus {
             // Add the unconfirmed solution to the memory pool.
-            Some(consensus) => consensus.add_unconfirmed_solution(solution).await?,
+            Some(consensus) => consensus.add_unconfirmed_solution(solution).await.map_err(BroadcastError::new::<N>)?,
             // Verify the solution.
             None => {
                 // Compute the current epoch hash.
-                let epoch_hash = rest.ledger.latest_epoch_hash()?;
+                let epoch_hash = rest.ledger.latest_epoch_hash().map_err(BroadcastError::new::<N>)?;
                 // Retrieve the current proof target.
                 let proof_target = rest.ledger.latest_proof_target();
                 // Ensure that the solution is valid for the given epoch.
@@ -430,9 +662,10 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
                 {
                     Ok(Ok(())) => {}
                     Ok(Err(err)) => {
-                        return Err(RestError(format!("Invalid solution '{}' - {err}", fmt_id(solution.id()))));
+                        let id = solution.id().to_string();
+                        return Err(AdmissionError::<N>::Malformed { id, reason: err.to_string() }.into());
                     }
-                    Err(err) => return Err(RestError(format!("Invalid solution '{}' - {err}", fmt_id(solution.id())))),
+                    Err(err) => return Err(BroadcastError::new::<N>(anyhow!(err))),
                 }
             }
         }
@@ -463,3 +696,81 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         Ok((StatusCode::OK, [(CONTENT_TYPE, "application/json")], result))
     }
 }
+
+/// Returns the pagination cursor for the given height and bytes of a mapping key.
+fn encode_cursor(height: u32, key_bytes: &[u8]) -> String {
+    key_bytes.iter().fold(format!("{height}-"), |mut cursor, byte| {
+        let _ = write!(cursor, "{byte:02x}");
+        cursor
+    })
+}
+
+/// Returns the height and the bytes of the mapping key encoded in the given pagination cursor.
+fn decode_cursor(cursor: &str) -> Result<(u32, Vec<u8>), RestError> {
+    let invalid = || RestError(format!("Invalid cursor '{cursor}'"));
+    let (height, key) = cursor.split_once('-').ok_or_else(invalid)?;
+    if height.is_empty() || !height.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let height = height.parse().map_err(|_| invalid())?;
+    if key.is_empty() || key.len() % 2 != 0 || !key.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    let key_bytes = (0..key.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(key.get(i..i + 2).ok_or_else(invalid)?, 16).map_err(|_| invalid()))
+        .collect::<Result<_, _>>()?;
+    Ok((height, key_bytes))
+}
+
+/// Returns the error for a cursor read at the given height, while the ledger is at the given latest height.
+fn stale_cursor(cursor_height: u32, latest_height: u32) -> RestError {
+    RestError(format!(
+        "The cursor is stale, as it was read at height {cursor_height} and the ledger is now at height \
+         {latest_height} - please restart from the first page"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor() {
+        // Ensure the cursor round-trips.
+        for height in [0, 1, u32::MAX] {
+            for key_bytes in [vec![0u8], vec![0, 1, 0x7f, 0x80, 0xff], (0..=255).collect()] {
+                let cursor = encode_cursor(height, &key_bytes);
+                assert_eq!(decode_cursor(&cursor).ok(), Some((height, key_bytes)));
+            }
+        }
+        // Ensure the uppercase hex digits are accepted.
+        assert_eq!(decode_cursor("7-0aFF").ok(), Some((7, vec![0x0a, 0xff])));
+    }
+
+    #[test]
+    fn test_cursor_malformed() {
+        // An empty cursor.
+        assert!(decode_cursor("").is_err());
+        assert!(decode_cursor("-").is_err());
+        // A cursor without a height, or with an invalid height.
+        assert!(decode_cursor("00").is_err());
+        assert!(decode_cursor("-00").is_err());
+        assert!(decode_cursor("+1-00").is_err());
+        assert!(decode_cursor("-1-00").is_err());
+        assert!(decode_cursor("4294967296-00").is_err());
+        // A truncated cursor.
+        assert!(decode_cursor("1-").is_err());
+        assert!(decode_cursor("1-0").is_err());
+        assert!(decode_cursor("1-00f").is_err());
+        assert!(decode_cursor(&encode_cursor(1, &[1, 2, 3])[..7]).is_err());
+        // A cursor that isn't hex-encoded.
+        assert!(decode_cursor("1-zz").is_err());
+        assert!(decode_cursor("1-0x00").is_err());
+        assert!(decode_cursor("1-+1").is_err());
+        // A cursor with a multi-byte character.
+        assert!(decode_cursor("1-0é").is_err());
+        assert!(decode_cursor("1-éé").is_err());
+        assert!(decode_cursor("é-00").is_err());
+    }
+}
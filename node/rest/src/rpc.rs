@@ -0,0 +1,298 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::routes::Metadata;
+
+use axum::{Extension, body::Bytes, response::IntoResponse};
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::{Value, json};
+use snarkvm::{console::program::Identifier, synthesizer::program::FinalizeStoreTrait};
+
+/// The maximum number of calls permitted in a single JSON-RPC batch.
+const MAX_BATCH_SIZE: usize = 50;
+
+/// The JSON-RPC error code for invalid JSON.
+const PARSE_ERROR: i64 = -32700;
+/// The JSON-RPC error code for a malformed request object.
+const INVALID_REQUEST: i64 = -32600;
+/// The JSON-RPC error code for an unknown method.
+const METHOD_NOT_FOUND: i64 = -32601;
+/// The JSON-RPC error code for missing or malformed parameters.
+const INVALID_PARAMS: i64 = -32602;
+/// The JSON-RPC error code for a failure in the underlying REST handler.
+const SERVER_ERROR: i64 = -32000;
+/// The JSON-RPC error code for a call lacking a JWT with the scope its method requires.
+const UNAUTHORIZED: i64 = -32001;
+/// The JSON-RPC error code for a block, transaction, mapping or committee missing from the ledger.
+const NOT_FOUND: i64 = -32004;
+/// The JSON-RPC error code for a call exceeding the rate limit of its method.
+const RATE_LIMITED: i64 = -32005;
+
+/// A JSON-RPC 2.0 error object.
+#[derive(Debug, Serialize)]
+pub(crate) struct RpcError {
+    /// The error code.
+    code: i64,
+    /// The error message.
+    message: String,
+    /// The additional information about the error, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<Value>,
+}
+
+impl RpcError {
+    /// Initializes a new JSON-RPC error.
+    fn new(code: i64, message: impl ToString) -> Self {
+        Self { code, message: message.to_string(), data: None }
+    }
+}
+
+impl From<RestError> for RpcError {
+    fn from(error: RestError) -> Self {
+        Self::new(SERVER_ERROR, error.0)
+    }
+}
+
+impl From<BroadcastError> for RpcError {
+    fn from(error: BroadcastError) -> Self {
+        let data = json!({ "code": error.code(), "retryable": error.is_retryable() });
+        Self { data: Some(data), ..Self::new(SERVER_ERROR, error.message()) }
+    }
+}
+
+/// Returns the route group and the access of the given method, as those of the route it dispatches to.
+fn rpc_method(method: &str) -> Option<(RouteGroup, Access)> {
+    match method {
+        "getBlock" | "getTransaction" | "getMappingValue" | "getCommittee" => Some((RouteGroup::Point, Access::Public)),
+        "broadcastTransaction" => Some((RouteGroup::Broadcast, Access::Broadcast)),
+        _ => None,
+    }
+}
+
+/// Returns the JSON-RPC response object for the given call ID and result.
+fn rpc_response(id: Value, result: Result<Value, RpcError>) -> Value {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "result": result, "id": id }),
+        Err(error) => json!({ "jsonrpc": "2.0", "error": error, "id": id }),
+    }
+}
+
+/// Returns the parameter at the given position or with the given name.
+fn rpc_param<T: DeserializeOwned>(params: &Option<Value>, index: usize, name: &str) -> Result<T, RpcError> {
+    match rpc_optional_param(params, index, name)? {
+        Some(param) => Ok(param),
+        None => Err(RpcError::new(INVALID_PARAMS, format!("Missing parameter '{name}'"))),
+    }
+}
+
+/// Returns the parameter at the given position or with the given name, if it is present.
+fn rpc_optional_param<T: DeserializeOwned>(
+    params: &Option<Value>,
+    index: usize,
+    name: &str,
+) -> Result<Option<T>, RpcError> {
+    let param = match params {
+        Some(Value::Array(params)) => params.get(index),
+        Some(Value::Object(params)) => params.get(name),
+        Some(Value::Null) | None => None,
+        Some(_) => return Err(RpcError::new(INVALID_PARAMS, "Parameters must be an array or an object")),
+    };
+    match param {
+        Some(Value::Null) | None => Ok(None),
+        Some(param) => serde_json::from_value(param.clone())
+            .map(Some)
+            .map_err(|error| RpcError::new(INVALID_PARAMS, format!("Invalid parameter '{name}' - {error}"))),
+    }
+}
+
+/// Charges a call to the rate limit of the given route group.
+fn rpc_check_rate_limit(rate_limit: &CallRateLimit, group: RouteGroup) -> Result<(), RpcError> {
+    rate_limit.check(group).map_err(|wait_time| {
+        let retry_after = retry_after_secs(wait_time);
+        let message = format!("Too Many Requests! Wait for {retry_after}s");
+        RpcError { data: Some(json!({ "retryAfter": retry_after })), ..RpcError::new(RATE_LIMITED, message) }
+    })
+}
+
+/// Charges a call of the given method to the rate limit of its route group, and ensures the given headers carry
+/// a JWT with the scope the method requires, if any.
+fn rpc_authorize(
+    jwt: &JwtConfig,
+    rate_limit: &CallRateLimit,
+    headers: &HeaderMap,
+    method: &str,
+) -> Result<(), RpcError> {
+    // Note: A call of an unknown method is charged as a point route, and rejected when it is dispatched.
+    let (group, access) = rpc_method(method).unwrap_or((RouteGroup::Point, Access::Public));
+    rpc_check_rate_limit(rate_limit, group)?;
+    if let Some(scope) = access.scope(jwt.is_broadcast_protected()) {
+        authorize(jwt, headers, scope).map_err(|(status, message)| {
+            let message = match message.is_empty() {
+                true => status.canonical_reason().unwrap_or_default().to_string(),
+                false => message,
+            };
+            RpcError { data: Some(json!({ "status": status.as_u16() })), ..RpcError::new(UNAUTHORIZED, message) }
+        })?;
+    }
+    Ok(())
+}
+
+/// Converts the JSON produced by a REST handler into a response.
+fn into_response(json: ErasedJson) -> Response {
+    json.into_response()
+}
+
+/// Converts the JSON body produced by a REST handler back into a JSON value.
+async fn into_value(response: Response) -> Result<Value, RpcError> {
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .map_err(|error| RpcError::new(SERVER_ERROR, error))?;
+    serde_json::from_slice(&body).map_err(|error| RpcError::new(SERVER_ERROR, error))
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // POST /<network>/rpc
+    pub(crate) async fn rpc(
+        State(rest): State<Self>,
+        Extension(rate_limit): Extension<CallRateLimit>,
+        headers: HeaderMap,
+        body: Bytes,
+    ) -> Response {
+        // Charge the request itself, so that malformed requests and rejected batches are rate limited as well.
+        if let Err(error) = rpc_check_rate_limit(&rate_limit, RouteGroup::Point) {
+            return Json(rpc_response(Value::Null, Err(error))).into_response();
+        }
+        // Parse the body, which is either a single call or a batch of calls.
+        let request = match serde_json::from_slice::<Value>(&body) {
+            Ok(request) => request,
+            Err(error) => {
+                let response = rpc_response(Value::Null, Err(RpcError::new(PARSE_ERROR, error)));
+                return Json(response).into_response();
+            }
+        };
+
+        let response = match request {
+            Value::Array(calls) => {
+                // Ensure the batch is neither empty nor too large.
+                if calls.is_empty() || calls.len() > MAX_BATCH_SIZE {
+                    let error = format!("A batch must contain between 1 and {MAX_BATCH_SIZE} calls");
+                    return Json(rpc_response(Value::Null, Err(RpcError::new(INVALID_REQUEST, error)))).into_response();
+                }
+                // Process the calls concurrently, omitting the responses to notifications.
+                let calls = calls.into_iter().map(|call| rest.clone().rpc_call(&rate_limit, &headers, call));
+                let responses = futures::future::join_all(calls).await.into_iter().flatten().collect::<Vec<_>>();
+                match responses.is_empty() {
+                    true => None,
+                    false => Some(Value::Array(responses)),
+                }
+            }
+            call => rest.rpc_call(&rate_limit, &headers, call).await,
+        };
+
+        match response {
+            Some(response) => Json(response).into_response(),
+            // Notifications do not receive a response.
+            None => StatusCode::NO_CONTENT.into_response(),
+        }
+    }
+
+    /// Processes a single JSON-RPC call, returning `None` if the call is a notification.
+    /// Each call is charged to the rate limit of the route group of its method, or of the point routes if invalid.
+    async fn rpc_call(self, rate_limit: &CallRateLimit, headers: &HeaderMap, call: Value) -> Option<Value> {
+        let Value::Object(call) = call else {
+            let result = rpc_check_rate_limit(rate_limit, RouteGroup::Point)
+                .and(Err(RpcError::new(INVALID_REQUEST, "Invalid request object")));
+            return Some(rpc_response(Value::Null, result));
+        };
+        // A call without an ID is a notification.
+        let id = call.get("id").cloned();
+        let result = match (call.get("jsonrpc"), call.get("method")) {
+            (Some(Value::String(version)), Some(Value::String(method))) if version == "2.0" => {
+                match rpc_authorize(&self.jwt, rate_limit, headers, method) {
+                    Ok(()) => self.rpc_dispatch(method, &call.get("params").cloned()).await,
+                    Err(error) => Err(error),
+                }
+            }
+            _ => rpc_check_rate_limit(rate_limit, RouteGroup::Point)
+                .and(Err(RpcError::new(INVALID_REQUEST, "Invalid request object"))),
+        };
+        id.map(|id| rpc_response(id, result))
+    }
+
+    /// Dispatches the given JSON-RPC method to the corresponding REST handler.
+    /// Note: The REST handlers report every failure as a server error, so the parameters are checked against
+    /// the ledger beforehand, to tell invalid parameters and missing entries apart from the server errors.
+    async fn rpc_dispatch(self, method: &str, params: &Option<Value>) -> Result<Value, RpcError> {
+        let result = match method {
+            "getBlock" => {
+                // The block may be requested either by height or by hash.
+                let height_or_hash = match rpc_param::<Value>(params, 0, "heightOrHash")? {
+                    Value::String(height_or_hash) => height_or_hash,
+                    height => height.to_string(),
+                };
+                let is_found = match (height_or_hash.parse::<u32>(), height_or_hash.parse::<N::BlockHash>()) {
+                    (Ok(height), _) => height <= self.ledger.latest_height(),
+                    (_, Ok(hash)) => self.ledger.contains_block_hash(&hash).map_err(RestError::from)?,
+                    _ => {
+                        let message =
+                            "Invalid parameter 'heightOrHash' - it is neither a block height nor a block hash";
+                        return Err(RpcError::new(INVALID_PARAMS, message));
+                    }
+                };
+                if !is_found {
+                    return Err(RpcError::new(NOT_FOUND, format!("Missing block '{height_or_hash}' in the ledger")));
+                }
+                Self::get_block(State(self), HeaderMap::new(), Path(height_or_hash)).await
+            }
+            "getTransaction" => {
+                let id = rpc_param::<N::TransactionID>(params, 0, "id")?;
+                if !self.ledger.contains_transaction_id(&id).map_err(RestError::from)? {
+                    return Err(RpcError::new(NOT_FOUND, format!("Missing transaction '{id}' in the ledger")));
+                }
+                Self::get_transaction(State(self), HeaderMap::new(), Path(id)).await
+            }
+            "getMappingValue" => {
+                let id = rpc_param::<ProgramID<N>>(params, 0, "programId")?;
+                let name = rpc_param::<Identifier<N>>(params, 1, "mappingName")?;
+                let key = rpc_param(params, 2, "key")?;
+                if !self.ledger.vm().finalize_store().contains_mapping_confirmed(&id, &name).map_err(RestError::from)? {
+                    return Err(RpcError::new(NOT_FOUND, format!("Missing mapping '{id}/{name}' in the ledger")));
+                }
+                Self::get_mapping_value(State(self), Path((id, name, key)), Query(Metadata::default()))
+                    .await
+                    .map(into_response)
+            }
+            "broadcastTransaction" => {
+                let transaction = BinaryOrJson(rpc_param(params, 0, "transaction")?);
+                // Note: The rejection reason of the transaction is returned in the error data.
+                let json = Self::transaction_broadcast(State(self), transaction).await?;
+                return into_value(into_response(json)).await;
+            }
+            "getCommittee" => match rpc_optional_param::<u32>(params, 0, "height")? {
+                Some(height) if height > self.ledger.latest_height() => {
+                    return Err(RpcError::new(
+                        NOT_FOUND,
+                        format!("Missing committee for block '{height}' in the ledger"),
+                    ));
+                }
+                Some(height) => Self::get_committee(State(self), Path(height)).await.map(into_response),
+                None => Self::get_committee_latest(State(self)).await.map(into_response),
+            },
+            _ => return Err(RpcError::new(METHOD_NOT_FOUND, format!("Method '{method}' not found"))),
+        };
+        into_value(result?).await
+    }
+}
@@ -0,0 +1,259 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use snarkos_node_router::SYNC_LENIENCY;
+use snarkvm::{
+    ledger::block::Solutions,
+    prelude::{Identifier, LimitedWriter, Plaintext, ToBytes, Value, block::Transaction},
+    synthesizer::program::{FinalizeGlobalState, FinalizeOperation},
+};
+
+use indexmap::IndexMap;
+use serde::Serialize;
+use std::collections::HashMap;
+use time::OffsetDateTime;
+
+/// The outcome of simulating a transaction against the latest ledger state.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Simulation<N: Network> {
+    /// The ID of the simulated transaction.
+    transaction_id: N::TransactionID,
+    /// Whether the transaction passed validation.
+    valid: bool,
+    /// The verdict, one of `accepted`, `rejected`, `aborted` or `invalid`.
+    status: &'static str,
+    /// The reason the transaction would not be accepted, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+    /// The base fee paid by the transaction, in microcredits.
+    base_fee: u64,
+    /// The priority fee paid by the transaction, in microcredits.
+    priority_fee: u64,
+    /// The mapping writes the transaction would perform if it were accepted.
+    mapping_writes: Vec<SimulatedWrite<N>>,
+}
+
+/// A mapping write the transaction would perform.
+/// Note: The finalize operations only commit to the keys and values, through their IDs, so the key and value
+/// are only decoded if they are among the arguments passed to the finalize logic of the transaction.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SimulatedWrite<N: Network> {
+    /// The ID of the program, if the mapping was found.
+    program_id: Option<ProgramID<N>>,
+    /// The name of the mapping, if the mapping was found.
+    mapping: Option<Identifier<N>>,
+    /// The key of the entry, if it was found.
+    key: Option<Plaintext<N>>,
+    /// The value written, if it was found.
+    value: Option<Value<N>>,
+    /// Whether the entry is removed.
+    removed: bool,
+    /// The ID of the mapping.
+    mapping_id: Field<N>,
+    /// The ID of the key.
+    key_id: Field<N>,
+    /// The ID of the value written, or `None` if the entry is removed.
+    value_id: Option<Field<N>>,
+}
+
+impl<N: Network> Simulation<N> {
+    /// Initializes a simulation for a transaction that did not make it to speculation.
+    fn invalid(transaction_id: N::TransactionID, base_fee: u64, priority_fee: u64, reason: String) -> Self {
+        Self {
+            transaction_id,
+            valid: false,
+            status: "invalid",
+            reason: Some(reason),
+            base_fee,
+            priority_fee,
+            mapping_writes: Vec::new(),
+        }
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // POST /<network>/transaction/simulate
+    pub(crate) async fn transaction_simulate(
+        State(rest): State<Self>,
+        Json(tx): Json<Transaction<N>>,
+    ) -> Result<ErasedJson, RestError> {
+        // Do not simulate the transaction if the node is too far behind, as the result would be stale.
+        if rest.routing.num_blocks_behind() > SYNC_LENIENCY {
+            return Err(RestError(format!("Unable to simulate transaction '{}' (node is syncing)", fmt_id(tx.id()))));
+        }
+
+        match tokio::task::spawn_blocking(move || simulate_transaction(&rest.ledger, &tx)).await {
+            Ok(Ok(simulation)) => Ok(ErasedJson::pretty(simulation)),
+            Ok(Err(err)) => Err(RestError(format!("Unable to simulate transaction - {err}"))),
+            Err(err) => Err(RestError(format!("Unable to simulate transaction - {err}"))),
+        }
+    }
+}
+
+/// Validates the given transaction and speculatively finalizes it on top of the latest block.
+/// The ledger and the memory pool are left untouched.
+fn simulate_transaction<N: Network, C: ConsensusStorage<N>>(
+    ledger: &Ledger<N, C>,
+    tx: &Transaction<N>,
+) -> Result<Simulation<N>> {
+    let transaction_id = tx.id();
+
+    // Ensure the transaction does not already exist in the ledger, as a duplicate would not be finalized again.
+    if ledger.contains_transaction_id(&transaction_id)? {
+        let reason = "Transaction already exists in the ledger".to_string();
+        return Ok(Simulation::invalid(transaction_id, 0, 0, reason));
+    }
+    // Fee transactions are only ever created by the ledger.
+    if tx.is_fee() {
+        return Ok(Simulation::invalid(transaction_id, 0, 0, "Fee transactions cannot be simulated".to_string()));
+    }
+
+    // Retrieve the fees paid by the transaction.
+    let base_fee = *tx.base_fee_amount()?;
+    let priority_fee = *tx.priority_fee_amount()?;
+
+    // Ensure the transaction is within the transaction size limit.
+    if tx.write_le(LimitedWriter::new(Vec::with_capacity(3000), N::MAX_TRANSACTION_SIZE)).is_err() {
+        let reason = "Transaction size exceeds the byte limit".to_string();
+        return Ok(Simulation::invalid(transaction_id, base_fee, priority_fee, reason));
+    }
+    // Ensure the transaction is well-formed and its proofs verify.
+    if let Err(err) = ledger.check_transaction_basic(tx, None, &mut rand::thread_rng()) {
+        return Ok(Simulation::invalid(transaction_id, base_fee, priority_fee, err.to_string()));
+    }
+
+    // Prepare the global state as if the transaction were included in the next block.
+    let latest_block = ledger.latest_block();
+    let state = FinalizeGlobalState::new::<N>(
+        latest_block.round().saturating_add(1),
+        latest_block.height().saturating_add(1),
+        latest_block.cumulative_weight(),
+        latest_block.cumulative_proof_target(),
+        latest_block.hash(),
+    )?;
+    let time_since_last_block = OffsetDateTime::now_utc().unix_timestamp().saturating_sub(latest_block.timestamp());
+
+    // Speculatively finalize the transaction, discarding the resulting state.
+    let (_, confirmed, aborted, _) = ledger.vm().speculate(
+        state,
+        time_since_last_block,
+        None,
+        vec![],
+        &Solutions::from(None),
+        [tx].into_iter(),
+        &mut rand::thread_rng(),
+    )?;
+
+    // Report the verdict.
+    // Note: The speculation only reports the IDs of the aborted transactions, and logs the reasons.
+    if aborted.contains(&transaction_id) {
+        return Ok(Simulation {
+            transaction_id,
+            valid: true,
+            status: "aborted",
+            reason: Some("The transaction was aborted during speculation".to_string()),
+            base_fee,
+            priority_fee,
+            mapping_writes: Vec::new(),
+        });
+    }
+    match confirmed.into_iter().next() {
+        Some(confirmed) => Ok(Simulation {
+            transaction_id,
+            valid: true,
+            status: if confirmed.is_accepted() { "accepted" } else { "rejected" },
+            reason: None,
+            base_fee,
+            priority_fee,
+            mapping_writes: decode_mapping_writes(ledger, tx, confirmed.finalize_operations())?,
+        }),
+        None => {
+            let reason = "Speculation was inconclusive".to_string();
+            Ok(Simulation::invalid(transaction_id, base_fee, priority_fee, reason))
+        }
+    }
+}
+
+/// Decodes the mapping writes of the given finalize operations,
+/// finding their keys and values among the arguments passed to the finalize logic of the transaction.
+/// Note: The decoding is best-effort, as the speculative finalize store is discarded once the transaction is
+/// speculated on. A key or value computed by the finalize logic, rather than passed to it, is left as `None`.
+fn decode_mapping_writes<N: Network, C: ConsensusStorage<N>>(
+    ledger: &Ledger<N, C>,
+    tx: &Transaction<N>,
+    operations: &[FinalizeOperation<N>],
+) -> Result<Vec<SimulatedWrite<N>>> {
+    let writes = operations.iter().filter_map(MappingWrite::from_operation).collect::<Vec<_>>();
+
+    // Find the mappings and keys of the writes among the programs and arguments of the transaction.
+    let mut arguments = IndexMap::new();
+    collect_transaction_arguments(tx, &mut arguments);
+    let mut mappings = HashMap::new();
+    let mut keys = HashMap::new();
+    for (program_id, candidates) in &arguments {
+        // Note: The program of a deployment is not in the process, but its mappings are not written to yet.
+        let Ok(names) = ledger
+            .vm()
+            .process()
+            .read()
+            .get_program(*program_id)
+            .map(|program| program.mappings().keys().copied().collect::<Vec<_>>())
+        else {
+            continue;
+        };
+        for name in &names {
+            let mapping_id = to_mapping_id(program_id, name)?;
+            if !writes.iter().any(|write| write.mapping_id == mapping_id) {
+                continue;
+            }
+            mappings.insert(mapping_id, (*program_id, *name));
+            for key in candidates.values() {
+                keys.insert(to_key_id(program_id, name, key)?, key.clone());
+            }
+        }
+    }
+
+    writes
+        .into_iter()
+        .map(|write| {
+            let (program_id, mapping) = mappings.get(&write.mapping_id).copied().unzip();
+            let key = keys.get(&write.key_id).cloned();
+            // Find the value among the arguments of the program, if the key was found.
+            let mut value = None;
+            if let (Some(program_id), Some(value_id), Some(_)) = (program_id, write.value_id, &key) {
+                for candidate in arguments.get(&program_id).into_iter().flat_map(|candidates| candidates.values()) {
+                    let candidate = Value::Plaintext(candidate.clone());
+                    if to_value_id(&write.key_id, &candidate)? == value_id {
+                        value = Some(candidate);
+                        break;
+                    }
+                }
+            }
+            Ok(SimulatedWrite {
+                program_id,
+                mapping,
+                key,
+                value,
+                removed: write.value_id.is_none(),
+                mapping_id: write.mapping_id,
+                key_id: write.key_id,
+                value_id: write.value_id,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,115 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use snarkvm::prelude::ToBytes;
+
+use axum::response::IntoResponse;
+use serde::{Deserialize, Serialize};
+use std::io;
+use tokio::sync::mpsc;
+
+/// The number of encoded blocks buffered ahead of the client.
+/// Note: Once the buffer is full, reading from the ledger pauses until the client catches up.
+const BLOCK_STREAM_BUFFER: usize = 8;
+
+/// The query object for `get_blocks_stream`.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct BlockStreamRange {
+    /// The starting block height (inclusive).
+    start: u32,
+    /// The ending block height (exclusive), defaulting to the latest block height plus one.
+    end: Option<u32>,
+}
+
+impl BlockStreamRange {
+    /// The query parameters, as documented in the OpenAPI document.
+    pub(crate) const PARAMS: &'static [QueryParam] = &[
+        QueryParam::new("start", "integer", true, "The starting block height (inclusive)"),
+        QueryParam::new("end", "integer", false, "The ending block height (exclusive)"),
+    ];
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // GET /<network>/blocks/stream?start={start_height}&end={end_height}
+    pub(crate) async fn get_blocks_stream(
+        State(rest): State<Self>,
+        headers: HeaderMap,
+        Query(range): Query<BlockStreamRange>,
+    ) -> Result<Response, RestError> {
+        let start_height = range.start;
+        // Ensure the range does not extend past the latest block.
+        let end_height = range.end.unwrap_or(u32::MAX).min(rest.ledger.latest_height().saturating_add(1));
+
+        // Ensure the end height is greater than the start height.
+        if start_height > end_height {
+            return Err(RestError("Invalid block range".to_string()));
+        }
+
+        // Determine the encoding. The binary encoding prefixes each block with its length as a little-endian `u32`.
+        let binary = accepts_binary(&headers);
+        let content_type = match binary {
+            true => OCTET_STREAM,
+            false => "application/x-ndjson",
+        };
+
+        // Read the blocks in a task, which waits whenever the buffer is full.
+        // Note: Each block is read in its own blocking task, so that a slow client doesn't hold a blocking thread.
+        let (sender, receiver) = mpsc::channel::<io::Result<Vec<u8>>>(BLOCK_STREAM_BUFFER);
+        tokio::spawn(async move {
+            for height in start_height..end_height {
+                let rest = rest.clone();
+                let encoded = match tokio::task::spawn_blocking(move || rest.encode_block(height, binary)).await {
+                    Ok(encoded) => encoded,
+                    Err(error) => Err(anyhow!(error)),
+                };
+                // Note: An error aborts the response, so that the client can tell the stream is incomplete.
+                let encoded = encoded.map_err(|error| {
+                    warn!("Failed to stream block {height} - {error}");
+                    io::Error::other(error.to_string())
+                });
+                let is_err = encoded.is_err();
+                // Stop reading once the client disconnects, or after an error.
+                if sender.send(encoded).await.is_err() || is_err {
+                    break;
+                }
+            }
+        });
+
+        let stream = futures::stream::unfold(receiver, |mut receiver| async move {
+            receiver.recv().await.map(|chunk| (chunk, receiver))
+        });
+        Ok(([(CONTENT_TYPE, content_type)], Body::from_stream(stream)).into_response())
+    }
+
+    /// Returns the block at the given height, encoded for the block stream.
+    fn encode_block(&self, height: u32, binary: bool) -> Result<Vec<u8>> {
+        let block = self.ledger.get_block(height)?;
+        match binary {
+            true => {
+                let bytes = block.to_bytes_le()?;
+                let mut encoded = Vec::with_capacity(bytes.len() + 4);
+                encoded.extend_from_slice(&u32::try_from(bytes.len())?.to_le_bytes());
+                encoded.extend_from_slice(&bytes);
+                Ok(encoded)
+            }
+            false => {
+                let mut encoded = serde_json::to_vec(&block)?;
+                encoded.push(b'\n');
+                Ok(encoded)
+            }
+        }
+    }
+}
@@ -292,9 +292,9 @@ pub trait Heartbeat<N: Network>: Outbound<N> {
         // Ensure that the trusted nodes are connected.
         for peer_ip in self.router().trusted_peers() {
             // If the peer is not connected, attempt to connect to it.
-            if !self.router().is_connected(peer_ip) {
+            if !self.router().is_connected(&peer_ip) {
                 // Attempt to connect to the trusted peer.
-                self.router().connect(*peer_ip);
+                self.router().connect(peer_ip);
             }
         }
     }
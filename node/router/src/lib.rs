@@ -83,7 +83,7 @@ pub struct InnerRouter<N: Network> {
     /// The resolver.
     resolver: Resolver,
     /// The set of trusted peers.
-    trusted_peers: HashSet<SocketAddr>,
+    trusted_peers: RwLock<HashSet<SocketAddr>>,
     /// The map of connected peer IPs to their peer handlers.
     connected_peers: RwLock<HashMap<SocketAddr, Peer<N>>>,
     /// The set of handshaking peers. While `Tcp` already recognizes the connecting IP addresses
@@ -143,7 +143,7 @@ impl<N: Network> Router<N> {
             account,
             cache: Default::default(),
             resolver: Default::default(),
-            trusted_peers: trusted_peers.iter().copied().collect(),
+            trusted_peers: RwLock::new(trusted_peers.iter().copied().collect()),
             connected_peers: Default::default(),
             connecting_peers: Default::default(),
             candidate_peers: Default::default(),
@@ -329,7 +329,7 @@ impl<N: Network> Router<N> {
 
     /// Returns `true` if the given IP is trusted.
     pub fn is_trusted(&self, ip: &SocketAddr) -> bool {
-        self.trusted_peers.contains(ip)
+        self.trusted_peers.read().contains(ip)
     }
 
     /// Returns the maximum number of connected peers.
@@ -409,8 +409,8 @@ impl<N: Network> Router<N> {
     }
 
     /// Returns the list of trusted peers.
-    pub fn trusted_peers(&self) -> &HashSet<SocketAddr> {
-        &self.trusted_peers
+    pub fn trusted_peers(&self) -> HashSet<SocketAddr> {
+        self.trusted_peers.read().clone()
     }
 
     /// Returns the list of bootstrap peers.
@@ -525,6 +525,16 @@ impl<N: Network> Router<N> {
         self.update_metrics();
     }
 
+    /// Inserts the given peer into the trusted peers, returning `false` if it was already trusted.
+    pub fn insert_trusted_peer(&self, peer_ip: SocketAddr) -> bool {
+        self.trusted_peers.write().insert(peer_ip)
+    }
+
+    /// Removes the given peer from the trusted peers, returning `false` if it was not trusted.
+    pub fn remove_trusted_peer(&self, peer_ip: SocketAddr) -> bool {
+        self.trusted_peers.write().remove(&peer_ip)
+    }
+
     /// Updates the connected peer with the given function.
     pub fn update_connected_peer<Fn: FnMut(&mut Peer<N>)>(
         &self,
@@ -18,7 +18,7 @@ mod router;
 use crate::traits::NodeInterface;
 use snarkos_account::Account;
 use snarkos_node_bft::ledger_service::CoreLedgerService;
-use snarkos_node_rest::Rest;
+use snarkos_node_rest::{Rest, RestConfig};
 use snarkos_node_router::{
     Heartbeat,
     Inbound,
@@ -127,7 +127,7 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
     pub async fn new(
         node_ip: SocketAddr,
         rest_ip: Option<SocketAddr>,
-        rest_rps: u32,
+        rest_config: RestConfig,
         account: Account<N>,
         trusted_peers: &[SocketAddr],
         genesis: Block<N>,
@@ -155,6 +155,8 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
 
         // Initialize the ledger service.
         let ledger_service = Arc::new(CoreLedgerService::<N, C>::new(ledger.clone(), shutdown.clone()));
+        // Subscribe to the blocks added to the ledger.
+        let block_height = ledger_service.subscribe_to_blocks();
         // Determine if the client should allow external peers.
         let allow_external_peers = true;
 
@@ -194,7 +196,18 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
 
         // Initialize the REST server.
         if let Some(rest_ip) = rest_ip {
-            node.rest = Some(Rest::start(rest_ip, rest_rps, None, ledger.clone(), Arc::new(node.clone())).await?);
+            node.rest = Some(
+                Rest::start(
+                    rest_ip,
+                    rest_config,
+                    None,
+                    ledger.clone(),
+                    Arc::new(node.clone()),
+                    block_height,
+                    node.shutdown.clone(),
+                )
+                .await?,
+            );
         }
         // Initialize the routing.
         node.initialize_routing().await;
@@ -15,6 +15,8 @@
 
 use crate::{Client, Prover, Validator, traits::NodeInterface};
 use snarkos_account::Account;
+use snarkos_node_consensus::ConsensusConfig;
+use snarkos_node_rest::RestConfig;
 use snarkos_node_router::messages::NodeType;
 use snarkvm::prelude::{
     Address,
@@ -47,7 +49,7 @@ impl<N: Network> Node<N> {
         node_ip: SocketAddr,
         bft_ip: Option<SocketAddr>,
         rest_ip: Option<SocketAddr>,
-        rest_rps: u32,
+        rest_config: RestConfig,
         account: Account<N>,
         trusted_peers: &[SocketAddr],
         trusted_validators: &[SocketAddr],
@@ -56,6 +58,7 @@ impl<N: Network> Node<N> {
         storage_mode: StorageMode,
         allow_external_peers: bool,
         dev_txs: bool,
+        consensus_config: ConsensusConfig,
         shutdown: Arc<AtomicBool>,
     ) -> Result<Self> {
         Ok(Self::Validator(Arc::new(
@@ -63,7 +66,7 @@ impl<N: Network> Node<N> {
                 node_ip,
                 bft_ip,
                 rest_ip,
-                rest_rps,
+                rest_config,
                 account,
                 trusted_peers,
                 trusted_validators,
@@ -72,6 +75,7 @@ impl<N: Network> Node<N> {
                 storage_mode,
                 allow_external_peers,
                 dev_txs,
+                consensus_config,
                 shutdown,
             )
             .await?,
@@ -94,7 +98,7 @@ impl<N: Network> Node<N> {
     pub async fn new_client(
         node_ip: SocketAddr,
         rest_ip: Option<SocketAddr>,
-        rest_rps: u32,
+        rest_config: RestConfig,
         account: Account<N>,
         trusted_peers: &[SocketAddr],
         genesis: Block<N>,
@@ -107,7 +111,7 @@ impl<N: Network> Node<N> {
             Client::new(
                 node_ip,
                 rest_ip,
-                rest_rps,
+                rest_config,
                 account,
                 trusted_peers,
                 genesis,
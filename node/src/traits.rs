@@ -87,9 +87,25 @@ pub trait NodeInterface<N: Network>: Routing<N> {
             tokio::signal::ctrl_c()
         }
 
+        // Return when the shutdown flag is raised on an initialized node, e.g. by the REST admin API.
+        let node_clone = node.clone();
+        let shutdown_flag_clone = shutdown_flag.clone();
+        let shutdown_listener = async move {
+            loop {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                if node_clone.get().is_some() && shutdown_flag_clone.load(Ordering::Acquire) {
+                    break;
+                }
+            }
+        };
+
         let node_clone = node.clone();
         tokio::task::spawn(async move {
-            match signal_listener().await {
+            let result = tokio::select! {
+                result = signal_listener() => result,
+                _ = shutdown_listener => Ok(()),
+            };
+            match result {
                 Ok(()) => {
                     warn!("==========================================================================================");
                     warn!("⚠️  Attention - Starting the graceful shutdown procedure (ETA: 30 seconds)...");
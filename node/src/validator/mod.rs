@@ -18,8 +18,8 @@ mod router;
 use crate::traits::NodeInterface;
 use snarkos_account::Account;
 use snarkos_node_bft::{helpers::init_primary_channels, ledger_service::CoreLedgerService, spawn_blocking};
-use snarkos_node_consensus::Consensus;
-use snarkos_node_rest::Rest;
+use snarkos_node_consensus::{Consensus, ConsensusConfig};
+use snarkos_node_rest::{Rest, RestConfig};
 use snarkos_node_router::{
     Heartbeat,
     Inbound,
@@ -77,7 +77,7 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
         node_ip: SocketAddr,
         bft_ip: Option<SocketAddr>,
         rest_ip: Option<SocketAddr>,
-        rest_rps: u32,
+        rest_config: RestConfig,
         account: Account<N>,
         trusted_peers: &[SocketAddr],
         trusted_validators: &[SocketAddr],
@@ -86,6 +86,7 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
         storage_mode: StorageMode,
         allow_external_peers: bool,
         dev_txs: bool,
+        consensus_config: ConsensusConfig,
         shutdown: Arc<AtomicBool>,
     ) -> Result<Self> {
         // Initialize the signal handler.
@@ -107,10 +108,18 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
 
         // Initialize the ledger service.
         let ledger_service = Arc::new(CoreLedgerService::new(ledger.clone(), shutdown.clone()));
+        // Subscribe to the blocks added to the ledger.
+        let block_height = ledger_service.subscribe_to_blocks();
 
         // Initialize the consensus.
-        let mut consensus =
-            Consensus::new(account.clone(), ledger_service.clone(), bft_ip, trusted_validators, storage_mode.clone())?;
+        let mut consensus = Consensus::new(
+            account.clone(),
+            ledger_service.clone(),
+            bft_ip,
+            trusted_validators,
+            storage_mode.clone(),
+            consensus_config,
+        )?;
         // Initialize the primary channels.
         let (primary_sender, primary_receiver) = init_primary_channels::<N>();
         // Start the consensus.
@@ -149,8 +158,18 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
 
         // Initialize the REST server.
         if let Some(rest_ip) = rest_ip {
-            node.rest =
-                Some(Rest::start(rest_ip, rest_rps, Some(consensus), ledger.clone(), Arc::new(node.clone())).await?);
+            node.rest = Some(
+                Rest::start(
+                    rest_ip,
+                    rest_config,
+                    Some(consensus),
+                    ledger.clone(),
+                    Arc::new(node.clone()),
+                    block_height,
+                    node.shutdown.clone(),
+                )
+                .await?,
+            );
         }
         // Initialize the routing.
         node.initialize_routing().await;
@@ -484,7 +503,7 @@ mod tests {
             node,
             None,
             Some(rest),
-            10,
+            RestConfig::new(10),
             account,
             &[],
             &[],
@@ -493,6 +512,7 @@ mod tests {
             storage_mode,
             false,
             dev_txs,
+            ConsensusConfig::default(),
             Default::default(),
         )
         .await
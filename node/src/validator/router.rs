@@ -14,6 +14,7 @@
 // limitations under the License.
 
 use super::*;
+use snarkos_node_consensus::AdmissionError;
 use snarkos_node_router::messages::{
     BlockRequest,
     BlockResponse,
@@ -275,7 +276,10 @@ impl<N: Network, C: ConsensusStorage<N>> Inbound<N> for Validator<N, C> {
         // Add the unconfirmed solution to the memory pool.
         if let Err(error) = self.consensus.add_unconfirmed_solution(solution).await {
             trace!("[UnconfirmedSolution] {error}");
-            return true; // Maintain the connection.
+            // Note: A recently received solution is still propagated, as only REST clients are told of duplicates.
+            if !is_duplicate::<N>(&error) {
+                return true; // Maintain the connection.
+            }
         }
         let message = Message::UnconfirmedSolution(serialized);
         // Propagate the "UnconfirmedSolution" to the connected validators.
@@ -293,7 +297,10 @@ impl<N: Network, C: ConsensusStorage<N>> Inbound<N> for Validator<N, C> {
         // Add the unconfirmed transaction to the memory pool.
         if let Err(error) = self.consensus.add_unconfirmed_transaction(transaction).await {
             trace!("[UnconfirmedTransaction] {error}");
-            return true; // Maintain the connection.
+            // Note: A recently received transaction is still propagated, as only REST clients are told of duplicates.
+            if !is_duplicate::<N>(&error) {
+                return true; // Maintain the connection.
+            }
         }
         let message = Message::UnconfirmedTransaction(serialized);
         // Propagate the "UnconfirmedTransaction" to the connected validators.
@@ -301,3 +308,8 @@ impl<N: Network, C: ConsensusStorage<N>> Inbound<N> for Validator<N, C> {
         true
     }
 }
+
+/// Returns `true` if the given error rejects a recently received transmission as a duplicate.
+fn is_duplicate<N: Network>(error: &anyhow::Error) -> bool {
+    matches!(error.downcast_ref::<AdmissionError<N>>(), Some(AdmissionError::Duplicate(..)))
+}
@@ -31,6 +31,13 @@ impl BanDetails {
     }
 }
 
+impl BanDetails {
+    /// Returns the time when the ban was created.
+    pub fn banned_at(&self) -> Instant {
+        self.banned_at
+    }
+}
+
 impl Default for BanDetails {
     fn default() -> Self {
         Self::new()
@@ -62,6 +69,11 @@ impl BannedPeers {
         self.0.write().insert(ip, BanDetails::default());
     }
 
+    /// Lift the ban on the given IP address, returning `false` if it was not banned.
+    pub fn remove_ip_ban(&self, ip: &IpAddr) -> bool {
+        self.0.write().remove(ip).is_some()
+    }
+
     /// Remove the expired entries
     pub fn remove_old_bans(&self, ban_time_in_secs: u64) {
         self.0.write().retain(|_, ban_config| ban_config.banned_at.elapsed().as_secs() < ban_time_in_secs);
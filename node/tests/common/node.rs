@@ -15,7 +15,7 @@
 
 use crate::common::test_peer::sample_genesis_block;
 use snarkos_account::Account;
-use snarkos_node::{Client, Prover, Validator};
+use snarkos_node::{Client, Prover, Validator, consensus::ConsensusConfig, rest::RestConfig};
 use snarkvm::prelude::{MainnetV0 as CurrentNetwork, store::helpers::memory::ConsensusMemory};
 
 use aleo_std::StorageMode;
@@ -25,7 +25,7 @@ pub async fn client() -> Client<CurrentNetwork, ConsensusMemory<CurrentNetwork>>
     Client::new(
         "127.0.0.1:0".parse().unwrap(),
         None,
-        10,
+        RestConfig::new(10),
         Account::<CurrentNetwork>::from_str("APrivateKey1zkp2oVPTci9kKcUprnbzMwq95Di1MQERpYBhEeqvkrDirK1").unwrap(),
         &[],
         sample_genesis_block(),
@@ -56,7 +56,7 @@ pub async fn validator() -> Validator<CurrentNetwork, ConsensusMemory<CurrentNet
         "127.0.0.1:0".parse().unwrap(),
         None,
         None,
-        10,
+        RestConfig::new(10),
         Account::<CurrentNetwork>::from_str("APrivateKey1zkp2oVPTci9kKcUprnbzMwq95Di1MQERpYBhEeqvkrDirK1").unwrap(),
         &[],
         &[],
@@ -65,6 +65,7 @@ pub async fn validator() -> Validator<CurrentNetwork, ConsensusMemory<CurrentNet
         StorageMode::Production,
         true,  // This test requires validators to connect to peers.
         false, // No dev traffic in production mode.
+        ConsensusConfig::default(),
         Default::default(),
     )
     .await